@@ -75,8 +75,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 /// - How to provide helpful error messages to users
 async fn handle_invalid_api_key() {
     let config = ChippConfig {
-        api_key: "invalid-api-key".to_string(),
-        model: "test-app".to_string(),
+        api_key: "invalid-api-key".into(),
+        model: "test-app".into(),
         max_retries: 2, // Won't retry 401 errors
         ..Default::default()
     };
@@ -114,8 +114,8 @@ async fn handle_invalid_api_key() {
 /// - How to detect when max retries are exceeded
 async fn handle_timeout() {
     let config = ChippConfig {
-        api_key: "test-key".to_string(),
-        model: "test-app".to_string(),
+        api_key: "test-key".into(),
+        model: "test-app".into(),
         timeout: Duration::from_millis(1), // Extremely short timeout to force failure
         max_retries: 2,
         initial_retry_delay: Duration::from_millis(10),
@@ -136,12 +136,15 @@ async fn handle_timeout() {
         Err(e) => {
             println!("❌ Error: {}", e);
             match &e {
-                ChippClientError::MaxRetriesExceeded(retries) => {
-                    println!("   → Max retries ({}) exceeded", retries);
+                ChippClientError::MaxRetriesExceeded { attempts, errors } => {
+                    println!("   → Gave up after {} attempts", attempts);
+                    for error in errors {
+                        println!("     - {}", error);
+                    }
                     println!("   → The SDK automatically retried timeout errors");
                     println!("   → Action: Increase timeout or check network connectivity");
                 }
-                ChippClientError::HttpError(http_err) if http_err.is_timeout() => {
+                e if e.is_timeout() => {
                     println!("   → This is a timeout error");
                     println!("   → The SDK will retry timeout errors automatically");
                 }
@@ -178,8 +181,8 @@ async fn handle_successful_request() {
     }
 
     let config = ChippConfig {
-        api_key,
-        model: app_name_id,
+        api_key: api_key.into(),
+        model: app_name_id.into(),
         max_retries: 3,
         ..Default::default()
     };
@@ -214,8 +217,8 @@ async fn handle_successful_request() {
 /// - Graceful degradation patterns
 async fn handle_with_fallback() {
     let config = ChippConfig {
-        api_key: "invalid-key".to_string(),
-        model: "test-app".to_string(),
+        api_key: "invalid-key".into(),
+        model: "test-app".into(),
         max_retries: 1,
         ..Default::default()
     };
@@ -250,8 +253,8 @@ async fn handle_with_fallback() {
 /// - How to handle errors at the appropriate level
 async fn propagate_errors() -> Result<String, ChippClientError> {
     let config = ChippConfig {
-        api_key: "invalid-key".to_string(),
-        model: "test-app".to_string(),
+        api_key: "invalid-key".into(),
+        model: "test-app".into(),
         ..Default::default()
     };
 
@@ -277,8 +280,8 @@ async fn propagate_errors() -> Result<String, ChippClientError> {
 /// Helper function to classify errors for debugging
 fn classify_error(error: &ChippClientError) -> &'static str {
     match error {
-        ChippClientError::HttpError(e) if e.is_timeout() => "Network Timeout (retryable)",
-        ChippClientError::HttpError(e) if e.is_connect() => "Connection Error (retryable)",
+        e if e.is_timeout() => "Network Timeout (retryable)",
+        e if e.is_connect() => "Connection Error (retryable)",
         ChippClientError::HttpError(_) => "HTTP Error (retryable)",
         ChippClientError::ApiError { status, .. } if *status >= 500 => {
             "Server Error 5xx (retryable)"
@@ -289,8 +292,15 @@ fn classify_error(error: &ChippClientError) -> &'static str {
         }
         ChippClientError::ApiError { .. } => "API Error",
         ChippClientError::InvalidResponse(_) => "Invalid Response (NOT retryable)",
+        ChippClientError::TruncatedResponse(_) => "Truncated Response (retryable)",
+        ChippClientError::EmptyResponse => "Empty Response (retryable)",
         ChippClientError::StreamError(_) => "Stream Error (NOT retryable)",
-        ChippClientError::MaxRetriesExceeded(_) => "Max Retries Exceeded",
+        ChippClientError::MaxRetriesExceeded { .. } => "Max Retries Exceeded",
         ChippClientError::ConfigError(_) => "Configuration Error (NOT retryable)",
+        ChippClientError::RequestTooLarge { .. } => "Request Too Large (NOT retryable)",
+        ChippClientError::Timeout { .. } => "Stream Timeout",
+        ChippClientError::DeadlineExceeded { .. } => "Deadline Exceeded",
+        ChippClientError::Cancelled => "Cancelled (NOT retryable)",
+        ChippClientError::ContentFiltered { .. } => "Content Filtered (NOT retryable)",
     }
 }
@@ -0,0 +1,119 @@
+//! Offline-first routing example: Chipp API with a local fallback
+//!
+//! This example demonstrates the routing decision the health API exists for:
+//! - Check API health with `ping()` before sending a request
+//! - Route to a local responder when the API is unreachable or slow
+//! - Fall back when the cloud path gives up after retries (`MaxRetriesExceeded`)
+//! - Log every routing decision
+//!
+//! The local responder is a stub; replace it with your local LLM.
+//!
+//! Run with:
+//! ```bash
+//! export CHIPP_API_KEY="your-api-key"
+//! export CHIPP_APP_NAME_ID="your-app-name-id"
+//! cargo run --example fallback
+//! ```
+
+use chipp::{ChippClient, ChippClientError, ChippConfig, ChippMessage, ChippSession};
+use std::time::Duration;
+
+/// Latency above which the cloud path is considered unhealthy
+const MAX_HEALTHY_LATENCY: Duration = Duration::from_secs(2);
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize tracing to see routing decisions and retry attempts
+    tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::INFO)
+        .init();
+
+    // Get API credentials from environment
+    let api_key =
+        std::env::var("CHIPP_API_KEY").expect("CHIPP_API_KEY environment variable not set");
+    let app_name_id =
+        std::env::var("CHIPP_APP_NAME_ID").expect("CHIPP_APP_NAME_ID environment variable not set");
+
+    let config = ChippConfig {
+        api_key: api_key.into(),
+        model: app_name_id.into(),
+        timeout: Duration::from_secs(10),
+        max_retries: 2,
+        ..Default::default()
+    };
+
+    let client = ChippClient::new(config)?;
+    let mut session = ChippSession::new();
+
+    let messages = vec![ChippMessage::user(
+        "Summarize why offline-first apps matter.",
+    )];
+
+    let response = route(&client, &mut session, &messages).await;
+    println!("\n💬 Response:\n{}", response);
+
+    Ok(())
+}
+
+/// Send the request to Chipp when healthy, otherwise answer locally.
+async fn route(
+    client: &ChippClient,
+    session: &mut ChippSession,
+    messages: &[ChippMessage],
+) -> String {
+    if !is_healthy(client).await {
+        tracing::warn!("Chipp API unhealthy, routing to local responder");
+        return local_respond(messages);
+    }
+
+    tracing::info!("Chipp API healthy, routing to cloud");
+    match client.chat(session, messages).await {
+        Ok(response) => response,
+        Err(ChippClientError::MaxRetriesExceeded { attempts, errors }) => {
+            tracing::warn!(
+                attempts,
+                ?errors,
+                "Cloud request exhausted retries, falling back to local responder"
+            );
+            local_respond(messages)
+        }
+        Err(e) if e.is_connect() || e.is_timeout() => {
+            tracing::warn!(
+                error = %e,
+                "Lost connectivity mid-request, falling back to local responder"
+            );
+            local_respond(messages)
+        }
+        Err(e) => {
+            // Configuration or request errors won't be fixed by the local path
+            tracing::error!(error = %e, "Cloud request failed with a non-transient error");
+            format!("Request failed: {}", e)
+        }
+    }
+}
+
+/// Treat the API as healthy when it answers a ping quickly enough.
+async fn is_healthy(client: &ChippClient) -> bool {
+    match client.ping().await {
+        Ok(latency) => {
+            tracing::info!(latency_ms = latency.as_millis(), "Ping succeeded");
+            latency < MAX_HEALTHY_LATENCY
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, "Ping failed");
+            false
+        }
+    }
+}
+
+/// Stub standing in for a local LLM.
+fn local_respond(messages: &[ChippMessage]) -> String {
+    let prompt = messages
+        .last()
+        .map(|message| message.content.as_str())
+        .unwrap_or_default();
+    format!(
+        "[local] I can't reach the cloud right now, but I received: {:?}",
+        prompt
+    )
+}
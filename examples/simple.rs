@@ -22,8 +22,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Create client configuration
     let config = ChippConfig {
-        api_key,
-        model: app_name_id,
+        api_key: api_key.into(),
+        model: app_name_id.into(),
         ..Default::default()
     };
 
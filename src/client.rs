@@ -1,19 +1,102 @@
 //! Chipp API client implementation.
 
-use crate::config::ChippConfig;
+use crate::config::{
+    normalize_base_url, parse_root_certificates, validate_adaptive_timeout,
+    validate_idempotency_key_header, validate_locale, validate_retry_delays, validate_retry_jitter,
+    validate_timeout, AppId, ChippConfig, RedirectPolicy,
+};
 use crate::error::ChippClientError;
-use crate::stream::ChippStream;
+use crate::request::{ChatRequest, RequestOptions};
+use crate::session_store::SessionStore;
+use crate::stream::{
+    record_gen_ai_usage, BufferedChippStream, ChippEventStream, ChippStream, EventSource,
+    PartialResponse, RawChippStream, SharedBuffer, StreamHandle, TimedChippStream,
+};
 use crate::types::{
-    ChatCompletionRequest, ChatCompletionResponse, ChatResponse, ChippMessage, ChippSession,
+    unknown_response_fields, ChatCompletionRequest, ChatCompletionResponse, ChatResponse,
+    ChippMessage, ChippSession, FinishReason, MessageRole, ResponseHeaders, TranscriptTurn, Usage,
 };
 
 use backoff::backoff::Backoff;
 use backoff::ExponentialBackoffBuilder;
 use futures::StreamExt;
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
+/// Most bytes of a streaming error response's body kept in `ApiError`.
+const MAX_ERROR_BODY_BYTES: usize = 8 * 1024;
+
+/// How a streaming response body should be read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StreamBodyKind {
+    /// Server-Sent Events
+    Sse,
+    /// A single non-streaming JSON completion
+    Json,
+    /// An HTML page, typically a proxy login or error interstitial
+    Html,
+}
+
+/// A failed attempt, with the delay the server asked for before retrying.
+struct AttemptError {
+    error: ChippClientError,
+    retry_after: Option<Duration>,
+}
+
+impl From<ChippClientError> for AttemptError {
+    fn from(error: ChippClientError) -> Self {
+        Self {
+            error,
+            retry_after: None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for AttemptError {
+    fn from(error: reqwest::Error) -> Self {
+        ChippClientError::from(error).into()
+    }
+}
+
+/// Retry bookkeeping for one logical call, shared by chat requests and stream starts.
+struct RetryState {
+    backoff: backoff::ExponentialBackoff,
+    rate_limit_backoff: backoff::ExponentialBackoff,
+    max_retries: usize,
+    /// Failures counted against `max_retries` and `rate_limit_max_retries` respectively
+    failures: usize,
+    rate_limited: usize,
+    /// Every failed attempt, reported if retries run out
+    history: Vec<String>,
+}
+
+/// Identifiers shared by every attempt of one logical call.
+struct CallIds {
+    /// Sent as `X-Correlation-ID`; may be the caller's own trace ID
+    correlation_id: String,
+    /// Sent in `idempotency_key_header`; always fresh, so separate calls
+    /// sharing a correlation ID are never deduplicated
+    idempotency_key: String,
+}
+
+impl CallIds {
+    /// Use the caller's correlation ID if given, and a new idempotency key.
+    fn new(options: &RequestOptions) -> Self {
+        let idempotency_key = Uuid::new_v4().to_string();
+        Self {
+            correlation_id: options
+                .correlation_id
+                .clone()
+                .unwrap_or_else(|| idempotency_key.clone()),
+            idempotency_key,
+        }
+    }
+}
+
 /// Chipp API client.
 ///
 /// # Example
@@ -35,9 +118,24 @@ use uuid::Uuid;
 /// # Ok(())
 /// # }
 /// ```
+///
+/// Cloning a client is cheap: clones share the same HTTP connection pool,
+/// response times for `adaptive_timeout`, and [`shutdown()`](Self::shutdown)
+/// state.
+///
+/// `ChippClient` is `Send + Sync`, so one client can also be shared across
+/// threads behind an `Arc`, e.g. in web framework state. Callbacks stored in
+/// the config ([`RetryPredicate`](crate::RetryPredicate),
+/// [`StreamEventCallback`](crate::StreamEventCallback)) are required to be
+/// `Send + Sync` to keep it that way.
+#[derive(Clone)]
 pub struct ChippClient {
     http: reqwest::Client,
     config: ChippConfig,
+    /// Durations of recent successful non-streaming requests, oldest first
+    response_times: Arc<Mutex<VecDeque<Duration>>>,
+    /// Cancelled by `shutdown()`; shared by every clone
+    shutdown: CancellationToken,
 }
 
 impl ChippClient {
@@ -45,49 +143,2072 @@ impl ChippClient {
     ///
     /// # Errors
     ///
-    /// Returns `ChippClientError::HttpError` if the underlying HTTP client fails to build.
-    pub fn new(config: ChippConfig) -> Result<Self, ChippClientError> {
-        let http = reqwest::Client::builder().timeout(config.timeout).build()?;
-        Ok(Self { http, config })
+    /// Returns `ChippClientError::ConfigError` if the configuration is invalid
+    /// (e.g. a zero timeout, an implausible `locale` or a malformed
+    /// `root_certificate`), as checked by [`ChippConfig::builder()`] too, or
+    /// `ChippClientError::HttpError` if the underlying HTTP client fails to
+    /// build (e.g. the TLS backend can't be initialized).
+    pub fn new(mut config: ChippConfig) -> Result<Self, ChippClientError> {
+        config.base_url = normalize_base_url(config.base_url);
+        Self::validate_config(&config)?;
+
+        // Redirects that keep credentials are followed by hand in `send_request`
+        let redirect = match config.redirect_policy {
+            RedirectPolicy::Limited(max) => reqwest::redirect::Policy::limited(max),
+            RedirectPolicy::None | RedirectPolicy::LimitedWithAuth(_) => {
+                reqwest::redirect::Policy::none()
+            }
+        };
+
+        let mut builder = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .redirect(redirect);
+
+        if let Some(interval) = config.http2_keep_alive_interval {
+            builder = builder
+                .http2_keep_alive_interval(interval)
+                .http2_keep_alive_while_idle(config.http2_keep_alive_while_idle);
+        }
+
+        if let Some(bytes) = &config.root_certificate {
+            for certificate in parse_root_certificates(bytes)? {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+
+        #[cfg(feature = "dangerous-tls")]
+        if config.danger_accept_invalid_certs {
+            tracing::warn!(
+                base_url = %config.base_url,
+                "TLS certificate verification is DISABLED; never use this in production"
+            );
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+
+        let http = builder.build()?;
+        Ok(Self {
+            http,
+            config,
+            response_times: Arc::default(),
+            shutdown: CancellationToken::new(),
+        })
+    }
+
+    /// Check a configuration that may not have come from the builder.
+    ///
+    /// A `ChippConfig { .. }` literal skips the builder's checks, so they are
+    /// repeated here rather than failing on the first request.
+    fn validate_config(config: &ChippConfig) -> Result<(), ChippClientError> {
+        validate_timeout(config.timeout)?;
+        validate_retry_jitter(config.retry_jitter)?;
+        validate_retry_delays(config.initial_retry_delay, config.max_retry_delay)?;
+        if let Some(adaptive) = &config.adaptive_timeout {
+            validate_adaptive_timeout(adaptive)?;
+        }
+        if let Some(locale) = &config.locale {
+            validate_locale(locale)?;
+        }
+        if let Some(header) = &config.idempotency_key_header {
+            validate_idempotency_key_header(header)?;
+        }
+        Ok(())
+    }
+
+    /// Create a client configured from the `CHIPP_API_KEY` and `CHIPP_APP_NAME_ID`
+    /// environment variables.
+    ///
+    /// The recommended zero-boilerplate entry point: unlike building on
+    /// `ChippConfig::default()`, a missing key or app ID fails here instead
+    /// of as a `401` on the first request. See [`ChippConfig::from_env()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `ChippClientError::ConfigError` if either variable is missing or
+    /// empty, otherwise the same errors as [`new()`](Self::new).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use chipp::{ChippClient, ChippMessage, ChippSession};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ChippClient::try_from_env()?;
+    /// let mut session = ChippSession::new();
+    ///
+    /// let response = client.chat(&mut session, &[ChippMessage::user("Hello!")]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_from_env() -> Result<Self, ChippClientError> {
+        Self::new(ChippConfig::from_env()?)
+    }
+
+    /// Create a client that shares this client's connection pool but uses a
+    /// different maximum number of retries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chipp::{ChippClient, ChippConfig};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ChippClient::new(ChippConfig::default())?;
+    /// let patient = client.with_max_retries(10);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_max_retries(&self, max_retries: usize) -> ChippClient {
+        let mut client = self.clone();
+        client.config.max_retries = max_retries;
+        client
+    }
+
+    /// Create a client that shares this client's connection pool but uses a
+    /// modified copy of its configuration.
+    ///
+    /// A general alternative to the `with_*` methods for per-call variations
+    /// such as a different timeout, model or retry policy. The clone shares
+    /// [`shutdown()`](Self::shutdown) state, and also the `adaptive_timeout`
+    /// history unless `api_key` or `base_url` changed.
+    ///
+    /// Settings baked into the connection pool (`redirect_policy`,
+    /// `http2_keep_alive_interval`, `http2_keep_alive_while_idle`,
+    /// `root_certificate` and `danger_accept_invalid_certs`) keep the original
+    /// client's values; use [`new()`](Self::new) when those matter.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ChippClientError::ConfigError` if the modified configuration
+    /// fails the same checks as [`new()`](Self::new).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chipp::{ChippClient, ChippConfig};
+    /// use std::time::Duration;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ChippClient::new(ChippConfig::default())?;
+    /// let quick = client.clone_with(|config| {
+    ///     config.timeout = Duration::from_secs(5);
+    ///     config.max_retries = 0;
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn clone_with(
+        &self,
+        f: impl FnOnce(&mut ChippConfig),
+    ) -> Result<ChippClient, ChippClientError> {
+        let mut config = self.config.clone();
+        f(&mut config);
+        config.base_url = normalize_base_url(config.base_url);
+        Self::validate_config(&config)?;
+
+        let mut client = self.clone();
+        // Response times belong to one credential and endpoint
+        if config.api_key != self.config.api_key || config.base_url != self.config.base_url {
+            client.response_times = Arc::default();
+        }
+        client.config = config;
+        Ok(client)
+    }
+
+    /// Create a client that shares this client's connection pool but never retries.
+    ///
+    /// Useful for latency-critical calls where failing fast beats waiting on backoff.
+    #[must_use]
+    pub fn no_retries(&self) -> ChippClient {
+        self.with_max_retries(0)
+    }
+
+    /// Cancel every in-flight and future request made through this client or its clones.
+    ///
+    /// Meant for graceful shutdown. Requests in progress, including retry
+    /// backoff, stop promptly with [`ChippClientError::Cancelled`]; open
+    /// streams end with a `Cancelled` item; new calls fail with `Cancelled`
+    /// without sending anything. Shutdown can't be undone: create a new client
+    /// to send requests again.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chipp::{ChippClient, ChippClientError, ChippConfig, ChippMessage, ChippSession};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ChippClient::new(ChippConfig::default())?;
+    /// let worker = client.clone();
+    ///
+    /// client.shutdown();
+    ///
+    /// let mut session = ChippSession::new();
+    /// let result = worker.chat(&mut session, &[ChippMessage::user("Hello!")]).await;
+    /// assert!(matches!(result, Err(ChippClientError::Cancelled)));
+    /// assert!(worker.is_shut_down());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn shutdown(&self) {
+        tracing::info!("Shutting down client, cancelling in-flight requests");
+        self.shutdown.cancel();
+    }
+
+    /// Returns `true` once [`shutdown()`](Self::shutdown) was called on this client or a clone.
+    #[must_use]
+    pub fn is_shut_down(&self) -> bool {
+        self.shutdown.is_cancelled()
+    }
+
+    /// Resolve when the client is shut down or `cancel` (if any) is triggered.
+    async fn cancelled(&self, cancel: Option<&CancellationToken>) {
+        match cancel {
+            Some(token) => {
+                tokio::select! {
+                    () = token.cancelled() => {}
+                    () = self.shutdown.cancelled() => {}
+                }
+            }
+            None => self.shutdown.cancelled().await,
+        }
+    }
+
+    /// Run a request unless the client is shut down first.
+    async fn unless_shut_down<T>(
+        &self,
+        request: impl Future<Output = Result<T, ChippClientError>>,
+    ) -> Result<T, ChippClientError> {
+        tokio::select! {
+            biased;
+            () = self.cancelled(None) => Err(ChippClientError::Cancelled),
+            result = request => result,
+        }
+    }
+
+    /// Determine if an error is retryable.
+    fn is_retryable_error(error: &ChippClientError) -> bool {
+        match error {
+            ChippClientError::HttpError(e) => {
+                e.is_timeout() || e.is_connect() || e.is_request() || e.is_body()
+            }
+            ChippClientError::ApiError { status, .. } => *status >= 500 || *status == 429,
+            ChippClientError::TruncatedResponse(_) | ChippClientError::EmptyResponse => true,
+            _ => false,
+        }
+    }
+
+    /// Decide whether to retry an error, deferring to the configured predicate.
+    fn should_retry(&self, error: &ChippClientError) -> bool {
+        match &self.config.should_retry {
+            Some(predicate) => predicate(error),
+            None => Self::is_retryable_error(error),
+        }
+    }
+
+    /// Decide whether to retry an error that ended a stream before its first chunk.
+    ///
+    /// Besides the usual errors, a body cut short while being read as a
+    /// stream surfaces as `is_decode`, and is retried since nothing has
+    /// reached the caller yet.
+    fn should_retry_stream_start(&self, error: &ChippClientError) -> bool {
+        match &self.config.should_retry {
+            Some(predicate) => predicate(error),
+            None => {
+                Self::is_retryable_error(error)
+                    || matches!(error, ChippClientError::HttpError(e) if e.is_decode())
+            }
+        }
+    }
+
+    /// Determine if an error means the server no longer knows the chat session.
+    ///
+    /// The status alone doesn't say so: a 404 may just as well be a wrong
+    /// `base_url` or model, so the body has to name an unknown session.
+    fn is_session_not_found(error: &ChippClientError) -> bool {
+        let ChippClientError::ApiError { message, .. } = error else {
+            return false;
+        };
+        let message = message.to_lowercase();
+        message.contains("session")
+            && ["not found", "unknown", "does not exist", "expired"]
+                .iter()
+                .any(|phrase| message.contains(phrase))
+    }
+
+    /// Build the request body, inserting the configured message prefix.
+    ///
+    /// The prefix goes after any leading system messages so that system
+    /// instructions still come first in the conversation.
+    fn build_request_body(
+        &self,
+        session: &ChippSession,
+        messages: &[ChippMessage],
+        stream: bool,
+        options: &RequestOptions,
+    ) -> ChatCompletionRequest {
+        let system_len = messages
+            .iter()
+            .take_while(|message| message.role == MessageRole::System)
+            .count();
+
+        let mut all_messages =
+            Vec::with_capacity(messages.len() + self.config.message_prefix.len());
+        all_messages.extend_from_slice(&messages[..system_len]);
+        all_messages.extend_from_slice(&self.config.message_prefix);
+        all_messages.extend_from_slice(&messages[system_len..]);
+
+        // PRIVACY: message content is only ever logged when explicitly enabled
+        if self.config.log_message_content {
+            for message in &all_messages {
+                tracing::debug!(role = ?message.role, content = %message.content, "Request message");
+            }
+        }
+
+        ChatCompletionRequest {
+            model: options
+                .model
+                .as_ref()
+                .unwrap_or(&self.config.model)
+                .to_string(),
+            messages: all_messages,
+            stream,
+            chat_session_id: session.chat_session_id.clone(),
+            temperature: options.temperature,
+            max_tokens: options.max_tokens,
+        }
+    }
+
+    /// Serialize a request body, enforcing `max_request_bytes`.
+    fn encode_body(
+        &self,
+        request_body: &ChatCompletionRequest,
+    ) -> Result<Vec<u8>, ChippClientError> {
+        let body = request_body
+            .to_json(&self.config.request_envelope, &self.config.request_format)
+            .to_string()
+            .into_bytes();
+
+        if let Some(limit) = self.config.max_request_bytes {
+            if body.len() > limit {
+                tracing::warn!(
+                    size = body.len(),
+                    limit,
+                    "Request body too large, not sending"
+                );
+                return Err(ChippClientError::RequestTooLarge {
+                    size: body.len(),
+                    limit,
+                });
+            }
+        }
+        Ok(body)
+    }
+
+    /// Build a request to an API path with the headers every request carries.
+    ///
+    /// `path` is joined to `base_url` (a leading `/` is optional). Sets
+    /// `Authorization`, `X-Correlation-ID`, the configured `Accept-Language`
+    /// and the configured timeout.
+    fn base_request(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        correlation_id: &str,
+    ) -> reqwest::RequestBuilder {
+        let url = format!("{}/{}", self.config.base_url, path.trim_start_matches('/'));
+
+        let mut request = self
+            .http
+            .request(method, &url)
+            .header(
+                "Authorization",
+                format!("Bearer {}", self.config.api_key.expose()),
+            )
+            .header("X-Correlation-ID", correlation_id)
+            .timeout(self.config.timeout);
+
+        if let Some(locale) = &self.config.locale {
+            request = request.header("Accept-Language", locale);
+        }
+        request
+    }
+
+    /// Build a POST request to the chat completions endpoint with the standard headers.
+    ///
+    /// Headers from `options.headers` are applied last and replace any
+    /// built-in header of the same name.
+    fn completions_request(
+        &self,
+        ids: &CallIds,
+        attempt: usize,
+        options: &RequestOptions,
+    ) -> reqwest::RequestBuilder {
+        let correlation_id = ids.correlation_id.as_str();
+
+        let mut request = self
+            .base_request(reqwest::Method::POST, "chat/completions", correlation_id)
+            .header("Content-Type", "application/json")
+            // Per-attempt IDs tell retries of one call apart in server logs
+            .header("X-Attempt", attempt)
+            .header("X-Request-ID", format!("{}-{}", correlation_id, attempt));
+
+        // The key is stable across retries of one logical call, which is
+        // exactly the lifetime an idempotency key needs
+        if let Some(header) = &self.config.idempotency_key_header {
+            request = request.header(header.as_str(), ids.idempotency_key.as_str());
+        }
+
+        // `header()` appends, so overrides of built-in headers go through
+        // `headers()`, which replaces
+        let mut overrides = reqwest::header::HeaderMap::new();
+        if let Some(locale) = options
+            .locale
+            .as_deref()
+            .and_then(|locale| reqwest::header::HeaderValue::from_str(locale).ok())
+        {
+            overrides.insert(reqwest::header::ACCEPT_LANGUAGE, locale);
+        }
+        for (name, value) in options.headers.iter().flatten() {
+            if let (Ok(name), Ok(value)) = (
+                reqwest::header::HeaderName::from_bytes(name.as_bytes()),
+                reqwest::header::HeaderValue::from_str(value),
+            ) {
+                overrides.insert(name, value);
+            }
+        }
+        request = request.headers(overrides);
+
+        // Set per request so clients from `clone_with` honor their own timeout
+        request.timeout(options.timeout.unwrap_or(self.config.timeout))
+    }
+
+    /// Send a request, following redirects by hand for `RedirectPolicy::LimitedWithAuth`.
+    ///
+    /// Only same-origin `307` and `308` redirects are followed, since they
+    /// preserve the method and body and keep the API key on the configured
+    /// scheme, host and port. The request is re-sent unchanged, headers
+    /// included, to the `Location` target. Any other response, or a redirect
+    /// past the limit, is returned as is.
+    async fn send_request(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, reqwest::Error> {
+        let RedirectPolicy::LimitedWithAuth(max_redirects) = self.config.redirect_policy else {
+            return request.send().await;
+        };
+
+        let mut request = request.build()?;
+        let mut redirects = 0;
+        loop {
+            let next = request.try_clone();
+            let response = self.http.execute(request).await?;
+
+            let status = response.status();
+            let preserves_body = status == reqwest::StatusCode::TEMPORARY_REDIRECT
+                || status == reqwest::StatusCode::PERMANENT_REDIRECT;
+            let location = response
+                .headers()
+                .get(reqwest::header::LOCATION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| response.url().join(value).ok());
+
+            // SECURITY: credentials never follow a redirect to another origin
+            let location = location.filter(|location| {
+                let same_origin = location.origin() == response.url().origin();
+                if !same_origin {
+                    tracing::warn!(%location, "Not following redirect to another origin");
+                }
+                same_origin
+            });
+
+            match (next, location) {
+                (Some(mut next), Some(location)) if preserves_body && redirects < max_redirects => {
+                    tracing::debug!(%location, "Following redirect with credentials");
+                    *next.url_mut() = location;
+                    request = next;
+                    redirects += 1;
+                }
+                _ => return Ok(response),
+            }
+        }
+    }
+
+    /// Read a `Retry-After` header given in seconds.
+    ///
+    /// The HTTP-date form is not supported and is treated as absent.
+    fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+        response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)?
+            .to_str()
+            .ok()?
+            .trim()
+            .parse()
+            .ok()
+            .map(Duration::from_secs)
+    }
+
+    /// Get the lowercased media type of a response's `Content-Type`, without parameters.
+    fn media_type(response: &reqwest::Response) -> Option<String> {
+        response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|content_type| content_type.split(';').next())
+            .map(|media_type| media_type.trim().to_ascii_lowercase())
+    }
+
+    /// Decide how to read a streaming response body from its `Content-Type`.
+    ///
+    /// Parameters such as `; charset=utf-8` are ignored and the media type is
+    /// compared case-insensitively. Anything other than JSON is read as SSE.
+    fn stream_body_kind(response: &reqwest::Response) -> StreamBodyKind {
+        match Self::media_type(response).as_deref() {
+            Some("text/event-stream") => StreamBodyKind::Sse,
+            Some("application/json") => StreamBodyKind::Json,
+            Some("text/html") => StreamBodyKind::Html,
+            other => {
+                tracing::warn!(
+                    content_type = other.unwrap_or("<none>"),
+                    "Unexpected Content-Type for streaming response, attempting SSE"
+                );
+                StreamBodyKind::Sse
+            }
+        }
+    }
+
+    /// Validate per-request overrides before anything is sent.
+    fn validate_options(options: &RequestOptions) -> Result<(), ChippClientError> {
+        if options.model.as_ref().is_some_and(AppId::is_empty) {
+            return Err(ChippClientError::ConfigError(
+                "model override must not be empty".to_string(),
+            ));
+        }
+        if let Some(locale) = &options.locale {
+            validate_locale(locale)?;
+        }
+        if let Some(timeout) = options.timeout {
+            validate_timeout(timeout)?;
+        }
+        for (name, value) in options.headers.iter().flatten() {
+            if reqwest::header::HeaderName::from_bytes(name.as_bytes()).is_err()
+                || reqwest::header::HeaderValue::from_str(value).is_err()
+            {
+                return Err(ChippClientError::ConfigError(format!(
+                    "header {:?}: {:?} is not a valid HTTP header",
+                    name, value
+                )));
+            }
+        }
+        if let Some(temperature) = options.temperature {
+            if !temperature.is_finite() || temperature < 0.0 {
+                return Err(ChippClientError::ConfigError(format!(
+                    "temperature must be a non-negative number, got {}",
+                    temperature
+                )));
+            }
+        }
+        if options.correlation_id.as_deref() == Some("") {
+            return Err(ChippClientError::ConfigError(
+                "correlation_id override must not be empty".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check the caller's messages before anything is sent.
+    fn validate_messages(&self, messages: &[ChippMessage]) -> Result<(), ChippClientError> {
+        let empty_user_message = messages
+            .iter()
+            .any(|m| m.role == MessageRole::User && m.content.trim().is_empty());
+        if self.config.reject_empty_messages && empty_user_message {
+            return Err(ChippClientError::ConfigError(
+                "empty user message".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Start the retry bookkeeping for one logical call.
+    fn retry_state(&self, max_retries: usize) -> RetryState {
+        RetryState {
+            backoff: self.create_backoff(),
+            rate_limit_backoff: self.create_backoff(),
+            max_retries,
+            failures: 0,
+            rate_limited: 0,
+            history: Vec::new(),
+        }
+    }
+
+    /// Decide how long to wait before retrying a failed attempt, or give up.
+    ///
+    /// `retryable` says whether the error is worth retrying at all. 429s get
+    /// their own budget when `rate_limit_max_retries` is set, honoring
+    /// Retry-After; other errors count against `max_retries`. The failure must
+    /// already be in `retry.history`.
+    fn retry_delay(
+        &self,
+        retry: &mut RetryState,
+        attempt: usize,
+        failure: AttemptError,
+        retryable: bool,
+    ) -> Result<Duration, ChippClientError> {
+        let AttemptError {
+            error: e,
+            retry_after,
+        } = failure;
+
+        let rate_limit_budget = match &e {
+            ChippClientError::ApiError { status: 429, .. } if retryable => {
+                self.config.rate_limit_max_retries
+            }
+            _ => None,
+        };
+        if let Some(max_rate_limit_retries) = rate_limit_budget {
+            retry.rate_limited += 1;
+            if retry.rate_limited > max_rate_limit_retries {
+                tracing::warn!(
+                    attempt,
+                    error = %e,
+                    history = ?retry.history,
+                    "Max rate limit retries exceeded"
+                );
+                return Err(ChippClientError::MaxRetriesExceeded {
+                    attempts: attempt,
+                    errors: std::mem::take(&mut retry.history),
+                });
+            }
+            // A server asking for hours would otherwise park the call that long
+            let retry_after = retry_after.map(|delay| delay.min(self.config.max_retry_delay));
+            let Some(delay) = retry_after.or_else(|| retry.rate_limit_backoff.next_backoff())
+            else {
+                return Err(e);
+            };
+            tracing::warn!(
+                attempt,
+                error = %e,
+                delay_ms = delay.as_millis(),
+                "Rate limited, retrying"
+            );
+            return Ok(delay);
+        }
+
+        retry.failures += 1;
+        if retry.failures > retry.max_retries {
+            tracing::warn!(
+                attempt,
+                error = %e,
+                history = ?retry.history,
+                "Max retry attempts exceeded"
+            );
+            return Err(ChippClientError::MaxRetriesExceeded {
+                attempts: attempt,
+                errors: std::mem::take(&mut retry.history),
+            });
+        }
+        if !retryable {
+            tracing::error!(error = %e, "Non-retryable error");
+            return Err(e);
+        }
+        match retry.backoff.next_backoff() {
+            Some(delay) => {
+                tracing::warn!(attempt, error = %e, delay_ms = delay.as_millis(), "Retrying");
+                Ok(delay)
+            }
+            None => Err(e),
+        }
+    }
+
+    /// Create a backoff strategy for retries.
+    ///
+    /// Backoff state is per call: each request builds its own, so retries
+    /// always start from `initial_retry_delay`. Don't share one across calls.
+    fn create_backoff(&self) -> backoff::ExponentialBackoff {
+        ExponentialBackoffBuilder::new()
+            .with_initial_interval(self.config.initial_retry_delay)
+            .with_max_interval(self.config.max_retry_delay)
+            .with_max_elapsed_time(None)
+            .with_multiplier(2.0)
+            .with_randomization_factor(self.config.retry_jitter)
+            .build()
     }
 
-    /// Determine if an error is retryable.
-    fn is_retryable_error(error: &ChippClientError) -> bool {
-        match error {
-            ChippClientError::HttpError(e) => e.is_timeout() || e.is_connect() || e.is_request(),
-            ChippClientError::ApiError { status, .. } => *status >= 500 || *status == 429,
-            _ => false,
+    /// Send a chat completion request (non-streaming).
+    ///
+    /// This is a convenience method that returns just the response content as a string.
+    /// For access to token usage and other metadata, use [`chat_detailed()`](Self::chat_detailed).
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - Session to track conversation state (updates `chatSessionId`)
+    /// * `messages` - Messages in the conversation
+    ///
+    /// # Returns
+    ///
+    /// The assistant's response text.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if HTTP request fails, API returns error, or response parsing fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chipp::{ChippClient, ChippConfig, ChippSession, ChippMessage};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # // Runs against a local mock so the snippet is executed by `cargo test`
+    /// # let (client, _server) = include!("../tests/doc/mock_client.rs").await;
+    /// let mut session = ChippSession::new();
+    /// let response = client.chat(&mut session, &[ChippMessage::user("Hello!")]).await?;
+    /// println!("Response: {}", response);
+    /// # assert_eq!(response, "Hi there!");
+    /// # assert_eq!(session.id(), Some("session-1"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self, session, messages), fields(correlation_id, attempt))]
+    pub async fn chat(
+        &self,
+        session: &mut ChippSession,
+        messages: &[ChippMessage],
+    ) -> Result<String, ChippClientError> {
+        let response = self.chat_detailed(session, messages).await?;
+        Ok(response.into_content())
+    }
+
+    /// Send several independent conversations, at most `concurrency` at a time.
+    ///
+    /// Each entry of `prompts` is sent in a fresh session with the usual retry
+    /// logic. The returned vector always has one result per prompt, in input
+    /// order: `results[i]` belongs to `prompts[i]` however the requests finish,
+    /// so callers can join results back to their records by index. One
+    /// failure doesn't affect the others. A `concurrency` of `0` is treated
+    /// as `1`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use chipp::{ChippClient, ChippConfig, ChippMessage};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = ChippClient::new(ChippConfig::default())?;
+    /// let prompts = vec![
+    ///     vec![ChippMessage::user("Summarize record 1")],
+    ///     vec![ChippMessage::user("Summarize record 2")],
+    /// ];
+    ///
+    /// for (index, result) in client.chat_batch(&prompts, 4).await.into_iter().enumerate() {
+    ///     match result {
+    ///         Ok(reply) => println!("{}: {}", index, reply),
+    ///         Err(e) => eprintln!("{} failed: {}", index, e),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn chat_batch(
+        &self,
+        prompts: &[Vec<ChippMessage>],
+        concurrency: usize,
+    ) -> Vec<Result<String, ChippClientError>> {
+        // Tasks finish in any order; each carries its index so results can be put back in order
+        let mut results: Vec<(usize, Result<String, ChippClientError>)> =
+            futures::stream::iter(prompts.iter().enumerate())
+                .map(|(index, messages)| async move {
+                    let mut session = ChippSession::new();
+                    (index, self.chat(&mut session, messages).await)
+                })
+                .buffer_unordered(concurrency.max(1))
+                .collect()
+                .await;
+        results.sort_unstable_by_key(|(index, _)| *index);
+
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+
+    /// Send a chat completion request, reading and writing the session ID through a store.
+    ///
+    /// An alternative to [`chat()`](Self::chat) for servers that keep sessions
+    /// in shared state instead of a `&mut ChippSession`. The ID stored under
+    /// `key` (if any) continues that conversation, and a new ID returned by the
+    /// API is written back with [`SessionStore::set()`]. The store is not
+    /// locked during the request, so concurrent calls for the same key race
+    /// like two clones of one `ChippSession` would.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`chat()`](Self::chat). Nothing is written to the store on error.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use chipp::{ChippClient, ChippConfig, ChippMessage, InMemorySessionStore};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = ChippConfig::default();
+    /// # let client = ChippClient::new(config)?;
+    /// let store = InMemorySessionStore::new();
+    ///
+    /// let reply = client
+    ///     .chat_with_session_store(&store, "user-42", &[ChippMessage::user("Hello!")])
+    ///     .await?;
+    /// println!("{}", reply);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn chat_with_session_store(
+        &self,
+        store: &dyn SessionStore,
+        key: &str,
+        messages: &[ChippMessage],
+    ) -> Result<String, ChippClientError> {
+        let stored_id = store.get(key).await;
+        let mut session = stored_id
+            .clone()
+            .map_or_else(ChippSession::new, ChippSession::with_id);
+
+        let response = self.chat(&mut session, messages).await?;
+
+        if let Some(id) = session.chat_session_id {
+            if stored_id.as_ref() != Some(&id) {
+                store.set(key, id).await;
+            }
+        }
+        Ok(response)
+    }
+
+    /// Send a chat completion request and parse the reply as JSON.
+    ///
+    /// For prompts that ask the model to answer in JSON. A reply wrapped in a
+    /// markdown code fence (` ```json ... ``` ` or a bare ` ``` `) is unwrapped
+    /// before parsing; an unfenced reply is parsed as is.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`chat()`](Self::chat), plus [`ChippClientError::InvalidResponse`]
+    /// with the raw reply when it isn't valid JSON for `T`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use chipp::{ChippClient, ChippConfig, ChippMessage, ChippSession};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Sentiment {
+    ///     label: String,
+    ///     score: f64,
+    /// }
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = ChippConfig::builder()
+    /// #     .api_key("YOUR_API_KEY_HERE")
+    /// #     .model("myapp-123")
+    /// #     .build()?;
+    /// # let client = ChippClient::new(config)?;
+    /// let mut session = ChippSession::new();
+    /// let prompt = ChippMessage::user(r#"Reply as {"label": ..., "score": ...}: "I love it""#);
+    ///
+    /// let sentiment: Sentiment = client.chat_json(&mut session, &[prompt]).await?;
+    /// println!("{} ({})", sentiment.label, sentiment.score);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn chat_json<T: serde::de::DeserializeOwned>(
+        &self,
+        session: &mut ChippSession,
+        messages: &[ChippMessage],
+    ) -> Result<T, ChippClientError> {
+        let content = self.chat(session, messages).await?;
+
+        serde_json::from_str(strip_code_fence(&content)).map_err(|e| {
+            ChippClientError::InvalidResponse(format!(
+                "Failed to parse reply as JSON: {}; content: {}",
+                e, content
+            ))
+        })
+    }
+
+    /// Send a chat completion request and return the full response with metadata.
+    ///
+    /// This method returns a [`ChatResponse`] containing:
+    /// - The AI's response content
+    /// - Token usage information for rate limiting and monitoring
+    /// - Completion ID for debugging
+    /// - Timestamps and finish reason
+    ///
+    /// For simple use cases where you only need the response text,
+    /// use [`chat()`](Self::chat) instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - Session to track conversation state (updates `chatSessionId`)
+    /// * `messages` - Messages in the conversation
+    ///
+    /// # Returns
+    ///
+    /// A [`ChatResponse`] containing the response and metadata.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if HTTP request fails, API returns error, or response parsing fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chipp::{ChippClient, ChippConfig, ChippSession, ChippMessage};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # // Runs against a local mock so the snippet is executed by `cargo test`
+    /// # let (client, _server) = include!("../tests/doc/mock_client.rs").await;
+    /// let mut session = ChippSession::new();
+    /// let response = client.chat_detailed(&mut session, &[ChippMessage::user("Hello!")]).await?;
+    ///
+    /// println!("Response: {}", response.content());
+    /// println!("Tokens used: {}", response.usage().total_tokens);
+    /// # assert_eq!(response.content(), "Hi there!");
+    /// # assert_eq!(response.usage().total_tokens, 5);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn chat_detailed(
+        &self,
+        session: &mut ChippSession,
+        messages: &[ChippMessage],
+    ) -> Result<ChatResponse, ChippClientError> {
+        self.chat_detailed_with(session, messages, RequestOptions::default())
+            .await
+    }
+
+    /// Send a chat completion request with per-request overrides.
+    ///
+    /// Behaves like [`chat_detailed()`](Self::chat_detailed), but any field set in
+    /// `options` takes precedence over the client configuration for this call only.
+    /// Unset fields inherit, so `RequestOptions::default()` behaves exactly like
+    /// `chat_detailed()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError` if an override is invalid, otherwise the same errors
+    /// as [`chat_detailed()`](Self::chat_detailed).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use chipp::{ChippClient, ChippConfig, ChippSession, ChippMessage, RequestOptions};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = ChippConfig::default();
+    /// # let client = ChippClient::new(config)?;
+    /// let mut session = ChippSession::new();
+    /// let options = RequestOptions {
+    ///     locale: Some("de-DE".to_string()),
+    ///     max_retries: Some(0),
+    ///     temperature: Some(0.2),
+    ///     ..Default::default()
+    /// };
+    /// let response = client
+    ///     .chat_detailed_with(&mut session, &[ChippMessage::user("Hallo!")], options)
+    ///     .await?;
+    /// println!("Response: {}", response.content());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(
+        skip(self, session, messages, options),
+        fields(correlation_id, attempt)
+    )]
+    pub async fn chat_detailed_with(
+        &self,
+        session: &mut ChippSession,
+        messages: &[ChippMessage],
+        options: RequestOptions,
+    ) -> Result<ChatResponse, ChippClientError> {
+        self.execute(session, messages, &options, None).await
+    }
+
+    /// Send a chat completion request without modifying the session.
+    ///
+    /// Behaves like [`chat_detailed()`](Self::chat_detailed) and continues the
+    /// session's conversation, but never writes back to `session`. Use it for
+    /// speculative calls, e.g. background suggestions, whose outcome shouldn't
+    /// change the canonical conversation. The server's session ID is available
+    /// through [`ChatResponse::session_id()`] for the caller to adopt with
+    /// [`ChippSession::set_id()`] if it wants to.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`chat_detailed()`](Self::chat_detailed).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use chipp::{ChippClient, ChippConfig, ChippSession, ChippMessage};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = ChippConfig::default();
+    /// # let client = ChippClient::new(config)?;
+    /// let session = ChippSession::with_id("session-123");
+    /// let suggestion = client
+    ///     .chat_detailed_readonly(&session, &[ChippMessage::user("Suggest a follow-up")])
+    ///     .await?;
+    /// println!("Suggestion: {}", suggestion.content());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn chat_detailed_readonly(
+        &self,
+        session: &ChippSession,
+        messages: &[ChippMessage],
+    ) -> Result<ChatResponse, ChippClientError> {
+        // Only the ID matters to the request; the transcript needn't be copied
+        let mut scratch = session
+            .id()
+            .map_or_else(ChippSession::new, ChippSession::with_id);
+        self.execute(&mut scratch, messages, &RequestOptions::default(), None)
+            .await
+    }
+
+    /// Send a chat completion request and record both sides in the session transcript.
+    ///
+    /// Behaves like [`chat_detailed()`](Self::chat_detailed). On success, each of
+    /// `messages` and then the assistant's reply (with its token usage) are
+    /// appended to [`ChippSession::transcript()`]. Failed requests record nothing.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`chat_detailed()`](Self::chat_detailed).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use chipp::{ChippClient, ChippConfig, ChippSession, ChippMessage};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = ChippConfig::default();
+    /// # let client = ChippClient::new(config)?;
+    /// let mut session = ChippSession::new();
+    /// client
+    ///     .chat_in_session(&mut session, &[ChippMessage::user("Hello!")])
+    ///     .await?;
+    ///
+    /// std::fs::write("transcript.json", session.transcript().to_json())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn chat_in_session(
+        &self,
+        session: &mut ChippSession,
+        messages: &[ChippMessage],
+    ) -> Result<ChatResponse, ChippClientError> {
+        let sent_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs() as i64);
+
+        let response = self.chat_detailed(session, messages).await?;
+
+        for message in messages {
+            session.record_turn(TranscriptTurn {
+                role: message.role.clone(),
+                content: message.content.clone(),
+                timestamp: sent_at,
+                usage: None,
+            });
+        }
+        session.record_turn(TranscriptTurn {
+            role: MessageRole::Assistant,
+            content: response.content().to_string(),
+            timestamp: response.created_at(),
+            usage: Some(*response.usage()),
+        });
+
+        Ok(response)
+    }
+
+    /// Send a prebuilt [`ChatRequest`].
+    ///
+    /// The request is borrowed, so the same request can be inspected and sent
+    /// again later, e.g. after a failure has been resolved.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`chat_detailed_with()`](Self::chat_detailed_with).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use chipp::{ChatRequest, ChippClient, ChippConfig, ChippSession, ChippMessage};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = ChippConfig::default();
+    /// # let client = ChippClient::new(config)?;
+    /// let request = ChatRequest::new(vec![ChippMessage::user("Hello!")]).with_model("otherapp-456");
+    ///
+    /// let mut session = ChippSession::new();
+    /// let response = client.send(&request, &mut session).await?;
+    /// println!("Response: {}", response.content());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self, request, session), fields(correlation_id, attempt))]
+    pub async fn send(
+        &self,
+        request: &ChatRequest,
+        session: &mut ChippSession,
+    ) -> Result<ChatResponse, ChippClientError> {
+        self.execute(session, &request.messages, &request.options, None)
+            .await
+    }
+
+    /// Send a prebuilt [`ChatRequest`] again with a different per-attempt timeout.
+    ///
+    /// Intended for "try again with more time" after a [`send()`](Self::send)
+    /// ran out of attempts, e.g. because each one timed out: the request is
+    /// re-sent as built, with only the timeout replaced. `request` itself is
+    /// left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`send()`](Self::send).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use chipp::{
+    ///     ChatRequest, ChippClient, ChippClientError, ChippConfig, ChippMessage, ChippSession,
+    /// };
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = ChippConfig::default();
+    /// # let client = ChippClient::new(config)?;
+    /// let request = ChatRequest::new(vec![ChippMessage::user("Summarize this report")]);
+    /// let mut session = ChippSession::new();
+    ///
+    /// let response = match client.send(&request, &mut session).await {
+    ///     Err(ChippClientError::MaxRetriesExceeded { .. }) => {
+    ///         client
+    ///             .retry_with(&request, &mut session, Duration::from_secs(120))
+    ///             .await?
+    ///     }
+    ///     other => other?,
+    /// };
+    /// println!("Response: {}", response.content());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn retry_with(
+        &self,
+        request: &ChatRequest,
+        session: &mut ChippSession,
+        timeout: Duration,
+    ) -> Result<ChatResponse, ChippClientError> {
+        let request = request.clone().with_timeout(timeout);
+        self.send(&request, session).await
+    }
+
+    /// Send a chat completion request that can be cancelled cooperatively.
+    ///
+    /// Behaves like [`chat_detailed()`](Self::chat_detailed), but stops as soon as
+    /// `cancel` is triggered: before each attempt, while a request is in flight,
+    /// and during retry backoff. Dropping the future also stops the request, but
+    /// this variant reports the abandonment as a distinct error.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ChippClientError::Cancelled` if the token is cancelled before the
+    /// request completes, otherwise the same errors as
+    /// [`chat_detailed()`](Self::chat_detailed).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use chipp::{CancellationToken, ChippClient, ChippConfig, ChippSession, ChippMessage};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = ChippConfig::default();
+    /// # let client = ChippClient::new(config)?;
+    /// let mut session = ChippSession::new();
+    /// let shutdown = CancellationToken::new();
+    ///
+    /// // Elsewhere: shutdown.cancel();
+    /// let response = client
+    ///     .chat_detailed_cancellable(&mut session, &[ChippMessage::user("Hello!")], &shutdown)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self, session, messages, cancel), fields(correlation_id, attempt))]
+    pub async fn chat_detailed_cancellable(
+        &self,
+        session: &mut ChippSession,
+        messages: &[ChippMessage],
+        cancel: &CancellationToken,
+    ) -> Result<ChatResponse, ChippClientError> {
+        self.execute(session, messages, &RequestOptions::default(), Some(cancel))
+            .await
+    }
+
+    /// Send a chat completion request that must finish by `deadline`.
+    ///
+    /// Behaves like [`chat_detailed()`](Self::chat_detailed), with the time
+    /// left until `deadline` as the budget for the whole call, retries
+    /// included. Use it to pass an end-to-end deadline down from a request
+    /// handler instead of recomputing a timeout at each call site.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChippClientError::DeadlineExceeded`] without sending anything
+    /// if `deadline` has already passed, or once it passes mid-call. Otherwise
+    /// the same errors as [`chat_detailed()`](Self::chat_detailed).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use chipp::{ChippClient, ChippConfig, ChippSession, ChippMessage};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = ChippConfig::default();
+    /// # let client = ChippClient::new(config)?;
+    /// let deadline = Instant::now() + Duration::from_secs(10);
+    /// // ... pre-processing that uses part of the budget ...
+    ///
+    /// let mut session = ChippSession::new();
+    /// let response = client
+    ///     .chat_detailed_until(&mut session, &[ChippMessage::user("Hello!")], deadline)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn chat_detailed_until(
+        &self,
+        session: &mut ChippSession,
+        messages: &[ChippMessage],
+        deadline: Instant,
+    ) -> Result<ChatResponse, ChippClientError> {
+        let exceeded = || ChippClientError::DeadlineExceeded {
+            deadline,
+            partial: String::new(),
+        };
+        if deadline <= Instant::now() {
+            return Err(exceeded());
+        }
+
+        let call = self.chat_detailed(session, messages);
+        match tokio::time::timeout_at(deadline.into(), call).await {
+            Ok(result) => result,
+            Err(_) => Err(exceeded()),
+        }
+    }
+
+    /// Run a non-streaming request with retries, optionally stopping on cancellation.
+    async fn execute(
+        &self,
+        session: &mut ChippSession,
+        messages: &[ChippMessage],
+        options: &RequestOptions,
+        cancel: Option<&CancellationToken>,
+    ) -> Result<ChatResponse, ChippClientError> {
+        Self::validate_options(options)?;
+        self.validate_messages(messages)?;
+
+        let ids = CallIds::new(options);
+        let max_retries = options.max_retries.unwrap_or(self.config.max_retries);
+        tracing::Span::current().record("correlation_id", &ids.correlation_id);
+        let gen_ai = self.gen_ai_span(options);
+
+        let mut retry = self.retry_state(max_retries);
+        let mut attempt = 0;
+        let mut session_reset = false;
+
+        loop {
+            attempt += 1;
+            tracing::Span::current().record("attempt", attempt);
+
+            let result = tokio::select! {
+                biased;
+                () = self.cancelled(cancel) => {
+                    tracing::info!(attempt, "Request cancelled");
+                    return Err(ChippClientError::Cancelled);
+                }
+                result = self.chat_attempt(session, messages, &ids, attempt, options) => {
+                    result
+                }
+            };
+
+            let failure = match result {
+                Ok(response) => {
+                    Self::record_gen_ai_response(&gen_ai, &response);
+                    return Ok(response);
+                }
+                Err(failure) => failure,
+            };
+            retry
+                .history
+                .push(format!("attempt {}: {}", attempt, failure.error));
+
+            // An expired server-side session is recovered once by starting over
+            if self.config.auto_reset_invalid_session
+                && !session_reset
+                && session.chat_session_id.is_some()
+                && Self::is_session_not_found(&failure.error)
+            {
+                tracing::warn!(
+                    attempt,
+                    error = %failure.error,
+                    "Session not found, retrying with a new session"
+                );
+                // Only the ID: the transcript stays a record of the whole exchange
+                session.chat_session_id = None;
+                session_reset = true;
+                continue;
+            }
+
+            let retryable = self.should_retry(&failure.error);
+            let delay = self.retry_delay(&mut retry, attempt, failure, retryable)?;
+            self.sleep_unless_cancelled(delay, cancel).await?;
+        }
+    }
+
+    /// Sleep for a retry delay, returning `Cancelled` early on cancellation or shutdown.
+    async fn sleep_unless_cancelled(
+        &self,
+        delay: Duration,
+        cancel: Option<&CancellationToken>,
+    ) -> Result<(), ChippClientError> {
+        tokio::select! {
+            biased;
+            () = self.cancelled(cancel) => {
+                tracing::info!("Request cancelled during retry backoff");
+                Err(ChippClientError::Cancelled)
+            }
+            () = tokio::time::sleep(delay) => Ok(()),
+        }
+    }
+
+    /// Open a span for one chat call carrying the OpenTelemetry GenAI fields.
+    ///
+    /// Request fields are set now and response fields recorded as they become
+    /// known. The span lives as long as the call, or the stream it opens. It is
+    /// disabled unless `otel_semantic_conventions` is set, so recording on it
+    /// costs nothing by default.
+    fn gen_ai_span(&self, options: &RequestOptions) -> tracing::Span {
+        if !self.config.otel_semantic_conventions {
+            return tracing::Span::none();
+        }
+        let model = options
+            .model
+            .as_ref()
+            .unwrap_or(&self.config.model)
+            .as_str();
+        tracing::info_span!(
+            "gen_ai.chat",
+            gen_ai.operation.name = "chat",
+            gen_ai.system = "chipp",
+            gen_ai.request.model = model,
+            gen_ai.response.model = tracing::field::Empty,
+            gen_ai.usage.input_tokens = tracing::field::Empty,
+            gen_ai.usage.output_tokens = tracing::field::Empty,
+            gen_ai.response.finish_reasons = tracing::field::Empty,
+        )
+    }
+
+    /// Record the GenAI response fields of a completed chat call on `span`.
+    fn record_gen_ai_response(span: &tracing::Span, response: &ChatResponse) {
+        record_gen_ai_usage(span, response.usage());
+        span.record("gen_ai.response.model", response.model())
+            .record(
+                "gen_ai.response.finish_reasons",
+                tracing::field::debug([response.finish_reason_str()]),
+            );
+    }
+
+    /// Internal method for a single chat attempt.
+    ///
+    /// Returns a `ChatResponse` with all metadata from the API.
+    async fn chat_attempt(
+        &self,
+        session: &mut ChippSession,
+        messages: &[ChippMessage],
+        ids: &CallIds,
+        attempt: usize,
+        options: &RequestOptions,
+    ) -> Result<ChatResponse, AttemptError> {
+        let request_body = self.build_request_body(session, messages, false, options);
+        let body = self.encode_body(&request_body)?;
+
+        let mut request = self.completions_request(ids, attempt, options).body(body);
+        if let Some(timeout) = self
+            .adaptive_timeout()
+            .filter(|_| options.timeout.is_none())
+        {
+            tracing::debug!(timeout_ms = timeout.as_millis(), "Using adaptive timeout");
+            request = request.timeout(timeout);
+        }
+
+        let start = Instant::now();
+        let response = self.send_request(request).await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let retry_after = Self::retry_after(&response);
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(AttemptError {
+                error: ChippClientError::ApiError {
+                    status: status.as_u16(),
+                    message: error_text,
+                },
+                retry_after,
+            });
+        }
+
+        let mut response_body = self.parse_completion(response).await?;
+        self.record_response_time(start.elapsed());
+        Self::fill_session_id(session, &mut response_body);
+
+        // Convert internal response to public type
+        let response: ChatResponse = response_body.into();
+        if self.config.log_message_content {
+            tracing::debug!(content = %response.content(), "Response message");
+        }
+        if self.config.reject_empty_completions
+            && response.is_empty()
+            && *response.finish_reason() == FinishReason::Stop
+        {
+            return Err(ChippClientError::InvalidResponse("empty completion".to_string()).into());
+        }
+        if self.config.reject_filtered_completions && response.was_filtered() {
+            return Err(ChippClientError::ContentFiltered {
+                message: response.into_content(),
+            }
+            .into());
+        }
+        // Only a reply that is returned to the caller moves the session on
+        Self::adopt_session_id(session, response.session_id());
+        Ok(response)
+    }
+
+    /// Get the timeout for the next attempt under `adaptive_timeout`, if any.
+    fn adaptive_timeout(&self) -> Option<Duration> {
+        let adaptive = self.config.adaptive_timeout?;
+        let mut response_times = self
+            .response_times
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        adaptive.timeout_for(response_times.make_contiguous())
+    }
+
+    /// Remember how long a successful request took, for `adaptive_timeout`.
+    fn record_response_time(&self, elapsed: Duration) {
+        let Some(adaptive) = self.config.adaptive_timeout else {
+            return;
+        };
+        let mut response_times = self
+            .response_times
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        response_times.push_back(elapsed);
+        while response_times.len() > adaptive.window {
+            response_times.pop_front();
+        }
+    }
+
+    /// Fill in a completion response's missing session ID from the session.
+    ///
+    /// An absent or empty ID in the response is replaced by the session's
+    /// existing ID, so the response reports the effective ID.
+    fn fill_session_id(session: &ChippSession, response: &mut ChatCompletionResponse) {
+        if response.chat_session_id.trim().is_empty() {
+            tracing::debug!("Response did not include a session ID, keeping existing one");
+            response.chat_session_id = session.chat_session_id.clone().unwrap_or_default();
+        }
+    }
+
+    /// Store the effective session ID of an accepted response in the session.
+    ///
+    /// An empty ID means neither the response nor the session had one, and
+    /// leaves the session untouched.
+    fn adopt_session_id(session: &mut ChippSession, session_id: &str) {
+        if !session_id.is_empty() {
+            session.chat_session_id = Some(session_id.to_string());
+        }
+    }
+
+    /// Parse a successful non-streaming response body.
+    ///
+    /// With `strict_response_parsing` enabled, unknown fields are rejected.
+    async fn parse_completion(
+        &self,
+        response: reqwest::Response,
+    ) -> Result<ChatCompletionResponse, ChippClientError> {
+        let html_content_type = Self::media_type(&response).as_deref() == Some("text/html");
+        let body = response.bytes().await?;
+
+        // Proxies often answer an expired login with a 200 HTML page
+        if html_content_type || Self::looks_like_html(&body) {
+            return Err(Self::html_error(&body));
+        }
+
+        // A success status with nothing in it is a transient server state,
+        // not malformed JSON
+        if body.iter().all(u8::is_ascii_whitespace) {
+            return Err(ChippClientError::EmptyResponse);
+        }
+
+        let response_body: ChatCompletionResponse = if self.config.strict_response_parsing {
+            let value: serde_json::Value =
+                serde_json::from_slice(&body).map_err(Self::parse_error)?;
+
+            let unknown = unknown_response_fields(&value);
+            if !unknown.is_empty() {
+                return Err(ChippClientError::InvalidResponse(format!(
+                    "Unknown fields in response: {}",
+                    unknown.join(", ")
+                )));
+            }
+
+            serde_json::from_value(value).map_err(Self::parse_error)?
+        } else {
+            serde_json::from_slice(&body).map_err(Self::parse_error)?
+        };
+
+        // Validate we have at least one choice before converting
+        if response_body.choices.is_empty() {
+            return Err(ChippClientError::InvalidResponse(
+                "No choices in response".to_string(),
+            ));
+        }
+
+        Ok(response_body)
+    }
+
+    /// Check whether a body starts like an HTML document.
+    fn looks_like_html(body: &[u8]) -> bool {
+        let body = body.trim_ascii_start();
+        [&b"<!doctype"[..], b"<html"].iter().any(|tag| {
+            body.get(..tag.len())
+                .is_some_and(|start| start.eq_ignore_ascii_case(tag))
+        })
+    }
+
+    /// Describe an HTML body received where JSON was expected.
+    fn html_error(body: &[u8]) -> ChippClientError {
+        let text = String::from_utf8_lossy(body);
+        let first_line: String = text
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or_default()
+            .chars()
+            .take(200)
+            .collect();
+        ChippClientError::InvalidResponse(format!(
+            "received HTML instead of JSON — check proxy/auth (first line: {:?})",
+            first_line
+        ))
+    }
+
+    /// Classify a response parse failure.
+    ///
+    /// Running out of input mid-document means the body was cut short in
+    /// transit, which a retry can fix; anything else is a schema mismatch.
+    fn parse_error(error: serde_json::Error) -> ChippClientError {
+        if error.is_eof() {
+            ChippClientError::TruncatedResponse(format!("Failed to parse response: {}", error))
+        } else {
+            ChippClientError::InvalidResponse(format!("Failed to parse response: {}", error))
+        }
+    }
+
+    /// Send a streaming chat completion request (SSE).
+    ///
+    /// Returns a stream of text chunks as they arrive from the API.
+    /// The session ID from the stream's metadata is available through
+    /// [`ChippStream::session_id()`] once received; copy it into the session
+    /// (or use [`chat_stream_collect()`](Self::chat_stream_collect), which does).
+    ///
+    /// If the server ignores `stream: true` and replies with a single
+    /// `application/json` completion instead of SSE, the content is yielded as
+    /// one chunk and the session is updated immediately.
+    ///
+    /// Failures while opening the stream are not retried by default. With
+    /// `retry_stream_start` enabled, this waits for the first text chunk
+    /// before returning, and re-sends the request if it fails before then.
+    ///
+    /// # Concurrency
+    ///
+    /// The session is borrowed mutably, so requests on one session are
+    /// serialized: a conversation can only have one turn in flight. Streams on
+    /// distinct sessions are independent and can run in parallel, even from the
+    /// same (cheaply cloned) client; each tracks its own session ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - Session to track conversation state
+    /// * `messages` - Messages in the conversation
+    ///
+    /// # Returns
+    ///
+    /// A stream of `Result<String, ChippClientError>` where each `Ok(String)` is a text chunk.
+    ///
+    /// Opening the stream waits for the first event, so a server `error` event
+    /// sent straight away is returned here as `ChippClientError::StreamError`.
+    /// An `error` event later on ends the stream with the same error. For a
+    /// non-2xx status, at most 8KB of the error body is read into `ApiError`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chipp::{ChippClient, ChippConfig, ChippSession, ChippMessage};
+    /// use futures::StreamExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # // Runs against a local mock so the snippet is executed by `cargo test`
+    /// # let (client, _server) = include!("../tests/doc/mock_client.rs").await;
+    /// let mut session = ChippSession::new();
+    /// let mut stream = client.chat_stream(&mut session, &[ChippMessage::user("Hello")]).await?;
+    ///
+    /// # let mut text = String::new();
+    /// while let Some(chunk) = stream.next().await {
+    ///     # let chunk = chunk.map(|chunk| { text.push_str(&chunk); chunk });
+    ///     print!("{}", chunk?);
+    /// }
+    /// # assert_eq!(text, "Hi there!");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn chat_stream(
+        &self,
+        session: &mut ChippSession,
+        messages: &[ChippMessage],
+    ) -> Result<ChippStream, ChippClientError> {
+        self.chat_stream_with(session, messages, RequestOptions::default())
+            .await
+    }
+
+    /// Send a streaming chat completion request with per-request overrides.
+    ///
+    /// Behaves like [`chat_stream()`](Self::chat_stream), but any field set in
+    /// `options` takes precedence over the client configuration for this call only.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError` if an override is invalid, otherwise the same errors
+    /// as [`chat_stream()`](Self::chat_stream).
+    pub async fn chat_stream_with(
+        &self,
+        session: &mut ChippSession,
+        messages: &[ChippMessage],
+        options: RequestOptions,
+    ) -> Result<ChippStream, ChippClientError> {
+        let (events, _) = self
+            .open_stream(session, messages, &options, false, None)
+            .await?;
+        Ok(ChippStream::new(events))
+    }
+
+    /// Send a streaming chat completion request that can be cancelled cooperatively.
+    ///
+    /// Behaves like [`chat_stream()`](Self::chat_stream), but stops as soon as
+    /// `cancel` is triggered: while the stream is being opened, including
+    /// retry backoff, and while it is read, where the stream ends with a
+    /// `Cancelled` item.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ChippClientError::Cancelled` if the token is cancelled before the
+    /// stream opens, otherwise the same errors as [`chat_stream()`](Self::chat_stream).
+    pub async fn chat_stream_cancellable(
+        &self,
+        session: &mut ChippSession,
+        messages: &[ChippMessage],
+        cancel: &CancellationToken,
+    ) -> Result<ChippStream, ChippClientError> {
+        let (events, _) = self
+            .open_stream(
+                session,
+                messages,
+                &RequestOptions::default(),
+                false,
+                Some(cancel),
+            )
+            .await?;
+        Ok(ChippStream::new(events))
+    }
+
+    /// Send a streaming chat completion and collect the text as it streams.
+    ///
+    /// Behaves like [`chat_stream()`](Self::chat_stream), but every chunk is
+    /// also appended to the returned [`SharedBuffer`]. Iterate the stream for
+    /// real-time display; once it ends, the buffer holds the full response,
+    /// ready to persist.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// # use chipp::{ChippClient, ChippConfig, ChippSession, ChippMessage};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = ChippConfig::default();
+    /// # let client = ChippClient::new(config)?;
+    /// let mut session = ChippSession::new();
+    /// let (mut stream, buffer) = client
+    ///     .chat_stream_tee(&mut session, &[ChippMessage::user("Hello")])
+    ///     .await?;
+    ///
+    /// while let Some(chunk) = stream.next().await {
+    ///     print!("{}", chunk?);
+    /// }
+    /// println!("\nSaving {} bytes", buffer.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`chat_stream()`](Self::chat_stream).
+    pub async fn chat_stream_tee(
+        &self,
+        session: &mut ChippSession,
+        messages: &[ChippMessage],
+    ) -> Result<(ChippStream, SharedBuffer), ChippClientError> {
+        let (events, _) = self
+            .open_stream(session, messages, &RequestOptions::default(), false, None)
+            .await?;
+        let buffer = SharedBuffer::default();
+        let stream = ChippStream::new(events.with_tee(buffer.clone()));
+        Ok((stream, buffer))
+    }
+
+    /// Send a streaming chat completion read ahead into a bounded buffer.
+    ///
+    /// Behaves like [`chat_stream()`](Self::chat_stream), but the response is
+    /// read by a spawned task that stays up to `capacity` chunks ahead of the
+    /// consumer and then pauses until the consumer catches up. Use this when
+    /// the consumer is the bottleneck (e.g. forwarding to a rate-limited sink)
+    /// and network reads should not stall on it chunk by chunk.
+    ///
+    /// See [`BufferedChippStream`](crate::BufferedChippStream) for details.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// # use chipp::{ChippClient, ChippConfig, ChippSession, ChippMessage};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = ChippConfig::default();
+    /// # let client = ChippClient::new(config)?;
+    /// let mut session = ChippSession::new();
+    /// let mut stream = client
+    ///     .chat_stream_buffered(&mut session, &[ChippMessage::user("Hello")], 32)
+    ///     .await?;
+    ///
+    /// while let Some(chunk) = stream.next().await {
+    ///     // slow sink...
+    ///     print!("{}", chunk?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError` if `capacity` is zero, otherwise the same errors
+    /// as [`chat_stream()`](Self::chat_stream).
+    pub async fn chat_stream_buffered(
+        &self,
+        session: &mut ChippSession,
+        messages: &[ChippMessage],
+        capacity: usize,
+    ) -> Result<BufferedChippStream, ChippClientError> {
+        if capacity == 0 {
+            return Err(ChippClientError::ConfigError(
+                "stream buffer capacity must be greater than zero".to_string(),
+            ));
+        }
+
+        let stream = self.chat_stream(session, messages).await?;
+        Ok(BufferedChippStream::spawn(stream, capacity))
+    }
+
+    /// Send a streaming chat completion that reports when each chunk arrived.
+    ///
+    /// Behaves like [`chat_stream()`](Self::chat_stream), but each item is the
+    /// text paired with the gap since the previous chunk; the first chunk's
+    /// gap is measured from when the request was sent, so it includes
+    /// time-to-first-token. See [`TimedChippStream`](crate::TimedChippStream).
+    ///
+    /// # Errors
+    ///
+    /// Same as [`chat_stream()`](Self::chat_stream).
+    pub async fn chat_stream_timed(
+        &self,
+        session: &mut ChippSession,
+        messages: &[ChippMessage],
+    ) -> Result<TimedChippStream, ChippClientError> {
+        let start = Instant::now();
+        let stream = self.chat_stream(session, messages).await?;
+        Ok(TimedChippStream::new(stream, start))
+    }
+
+    /// Send a streaming chat completion and keep the response headers.
+    ///
+    /// Behaves like [`chat_stream()`](Self::chat_stream), but also exposes
+    /// headers such as the request ID and rate-limit counters through
+    /// [`StreamHandle::headers()`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`chat_stream()`](Self::chat_stream).
+    pub async fn chat_stream_with_headers(
+        &self,
+        session: &mut ChippSession,
+        messages: &[ChippMessage],
+    ) -> Result<StreamHandle, ChippClientError> {
+        let (events, headers) = self
+            .open_stream(session, messages, &RequestOptions::default(), false, None)
+            .await?;
+        Ok(StreamHandle::new(headers, ChippStream::new(events)))
+    }
+
+    /// Send a streaming chat completion request and receive structured events.
+    ///
+    /// Unlike [`chat_stream()`](Self::chat_stream), which yields only response
+    /// text, this yields every [`StreamEvent`](crate::StreamEvent): text deltas, reasoning deltas
+    /// from reasoning models, session metadata, and the final `Done`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`chat_stream()`](Self::chat_stream).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use chipp::{ChippClient, ChippConfig, ChippSession, ChippMessage, StreamEvent};
+    /// use futures::StreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = ChippConfig::default();
+    /// # let client = ChippClient::new(config)?;
+    /// let mut session = ChippSession::new();
+    /// let mut events = client
+    ///     .chat_stream_events(&mut session, &[ChippMessage::user("Hello")])
+    ///     .await?;
+    ///
+    /// while let Some(event) = events.next().await {
+    ///     if let StreamEvent::TextDelta(text) = event? {
+    ///         print!("{}", text);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn chat_stream_events(
+        &self,
+        session: &mut ChippSession,
+        messages: &[ChippMessage],
+    ) -> Result<ChippEventStream, ChippClientError> {
+        let (events, _) = self
+            .open_stream(session, messages, &RequestOptions::default(), false, None)
+            .await?;
+        Ok(ChippEventStream::new(events))
+    }
+
+    /// Send a streaming chat request and stream the raw `data:` payloads.
+    ///
+    /// Yields each SSE payload verbatim, before parsing: the JSON of text,
+    /// metadata and finish events, and the closing `[DONE]`. Meant for
+    /// capturing exact event sequences to replay with
+    /// [`replay_sse_payloads()`](crate::replay_sse_payloads) when diagnosing
+    /// streaming issues. See [`RawChippStream`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`chat_stream()`](Self::chat_stream).
+    pub async fn chat_stream_raw(
+        &self,
+        session: &mut ChippSession,
+        messages: &[ChippMessage],
+    ) -> Result<RawChippStream, ChippClientError> {
+        let (events, _) = self
+            .open_stream(session, messages, &RequestOptions::default(), true, None)
+            .await?;
+        Ok(RawChippStream::new(events))
+    }
+
+    /// Send a streaming request and return the parsed event source.
+    ///
+    /// With `raw_lines`, the source also keeps every `data:` payload for a
+    /// [`RawChippStream`]. Opening stops on shutdown or once `cancel` is
+    /// cancelled, and the returned source ends on either.
+    async fn open_stream(
+        &self,
+        session: &mut ChippSession,
+        messages: &[ChippMessage],
+        options: &RequestOptions,
+        raw_lines: bool,
+        cancel: Option<&CancellationToken>,
+    ) -> Result<(EventSource, ResponseHeaders), ChippClientError> {
+        Self::validate_options(options)?;
+        self.validate_messages(messages)?;
+
+        let ids = CallIds::new(options);
+        let max_retries = options.max_retries.unwrap_or(self.config.max_retries);
+        let gen_ai = self.gen_ai_span(options);
+        if !self.config.retry_stream_start {
+            let opening = self.stream_attempt(session, messages, options, &ids, 1, raw_lines);
+            let (events, headers) = tokio::select! {
+                biased;
+                () = self.cancelled(cancel) => return Err(ChippClientError::Cancelled),
+                result = opening => result.map_err(|failure| failure.error)?,
+            };
+            return Ok((events.with_cancel(cancel).with_gen_ai_span(gen_ai), headers));
+        }
+
+        let mut retry = self.retry_state(max_retries);
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+
+            let opening = self
+                .stream_attempt_prefetched(session, messages, options, &ids, attempt, raw_lines);
+            let attempt_result = tokio::select! {
+                biased;
+                () = self.cancelled(cancel) => return Err(ChippClientError::Cancelled),
+                result = opening => result,
+            };
+            let failure = match attempt_result {
+                Ok((events, headers)) => {
+                    return Ok((events.with_cancel(cancel).with_gen_ai_span(gen_ai), headers))
+                }
+                Err(failure) => failure,
+            };
+            retry
+                .history
+                .push(format!("attempt {}: {}", attempt, failure.error));
+
+            let retryable = self.should_retry_stream_start(&failure.error);
+            let delay = self.retry_delay(&mut retry, attempt, failure, retryable)?;
+            self.sleep_unless_cancelled(delay, cancel).await?;
+        }
+    }
+
+    /// Send one streaming request and read ahead to its first text chunk.
+    async fn stream_attempt_prefetched(
+        &self,
+        session: &mut ChippSession,
+        messages: &[ChippMessage],
+        options: &RequestOptions,
+        ids: &CallIds,
+        attempt: usize,
+        raw_lines: bool,
+    ) -> Result<(EventSource, ResponseHeaders), AttemptError> {
+        let (mut events, headers) = self
+            .stream_attempt(session, messages, options, ids, attempt, raw_lines)
+            .await?;
+        events.prefetch_first_text().await?;
+        Ok((events, headers))
+    }
+
+    /// Send one streaming request and open its body.
+    async fn stream_attempt(
+        &self,
+        session: &mut ChippSession,
+        messages: &[ChippMessage],
+        options: &RequestOptions,
+        ids: &CallIds,
+        attempt: usize,
+        raw_lines: bool,
+    ) -> Result<(EventSource, ResponseHeaders), AttemptError> {
+        let request_body = self.build_request_body(session, messages, true, options);
+        let body = self.encode_body(&request_body)?;
+
+        tracing::debug!("Sending Chipp API streaming request");
+
+        let request = self
+            .completions_request(ids, attempt, options)
+            .header("Accept", "text/event-stream")
+            .body(body);
+        let response = self.send_request(request).await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let retry_after = Self::retry_after(&response);
+            let error_text = Self::read_error_body(response).await;
+            return Err(AttemptError {
+                error: ChippClientError::ApiError {
+                    status: status.as_u16(),
+                    message: error_text,
+                },
+                retry_after,
+            });
+        }
+
+        let headers = ResponseHeaders::new(response.headers().clone());
+
+        let body_kind = Self::stream_body_kind(&response);
+        if body_kind == StreamBodyKind::Html {
+            let body = response.bytes().await?;
+            return Err(Self::html_error(&body).into());
         }
-    }
 
-    /// Create a backoff strategy for retries.
-    fn create_backoff(&self) -> backoff::ExponentialBackoff {
-        ExponentialBackoffBuilder::new()
-            .with_initial_interval(self.config.initial_retry_delay)
-            .with_max_interval(self.config.max_retry_delay)
-            .with_max_elapsed_time(None)
-            .with_multiplier(2.0)
-            .with_randomization_factor(0.3)
-            .build()
+        // Some backends don't support SSE and answer with a plain completion body
+        if body_kind == StreamBodyKind::Json {
+            tracing::debug!("Streaming request answered with JSON, falling back to single chunk");
+            let mut response_body = self.parse_completion(response).await?;
+            Self::fill_session_id(session, &mut response_body);
+            let response: ChatResponse = response_body.into();
+            Self::adopt_session_id(session, response.session_id());
+            let (content, _, session_id) = response.into_parts();
+            let events = EventSource::from_complete(content, session_id)
+                .with_observer(self.config.on_stream_event.clone())
+                .with_shutdown(self.shutdown.clone());
+            return Ok((events, headers));
+        }
+
+        // Get the byte stream for true streaming (not buffered!)
+        let byte_stream = response.bytes_stream();
+
+        let mut events = EventSource::new(Box::pin(byte_stream))
+            .with_raw_lines(raw_lines)
+            .with_observer(self.config.on_stream_event.clone())
+            .with_shutdown(self.shutdown.clone());
+        // Fail here, rather than on the first poll, if the server errors immediately
+        events.prefetch_first_event().await?;
+        Ok((events, headers))
     }
 
-    /// Send a chat completion request (non-streaming).
-    ///
-    /// This is a convenience method that returns just the response content as a string.
-    /// For access to token usage and other metadata, use [`chat_detailed()`](Self::chat_detailed).
-    ///
-    /// # Arguments
-    ///
-    /// * `session` - Session to track conversation state (updates `chatSessionId`)
-    /// * `messages` - Messages in the conversation
+    /// Read at most `MAX_ERROR_BODY_BYTES` of an error response's body.
     ///
-    /// # Returns
-    ///
-    /// The assistant's response text.
+    /// The error body of a streaming request may itself be streamed and of any
+    /// length, so the rest is left unread.
+    async fn read_error_body(response: reqwest::Response) -> String {
+        let mut body = Vec::new();
+        let mut chunks = response.bytes_stream();
+        while let Some(Ok(chunk)) = chunks.next().await {
+            let room = MAX_ERROR_BODY_BYTES - body.len();
+            body.extend_from_slice(&chunk[..chunk.len().min(room)]);
+            if body.len() == MAX_ERROR_BODY_BYTES {
+                break;
+            }
+        }
+        String::from_utf8_lossy(&body).into_owned()
+    }
+
+    /// Send a streaming chat completion and collect the full response.
     ///
-    /// # Errors
+    /// This is a convenience method that consumes the entire stream and
+    /// updates the session with the captured session ID.
     ///
-    /// Returns error if HTTP request fails, API returns error, or response parsing fails.
+    /// For true streaming where you process chunks as they arrive,
+    /// use [`chat_stream`](Self::chat_stream) instead.
     ///
     /// # Example
     ///
@@ -98,280 +2219,256 @@ impl ChippClient {
     /// # let config = ChippConfig::default();
     /// # let client = ChippClient::new(config)?;
     /// let mut session = ChippSession::new();
-    /// let response = client.chat(&mut session, &[ChippMessage::user("Hello!")]).await?;
+    /// let response = client.chat_stream_collect(&mut session, &[ChippMessage::user("Hello")]).await?;
     /// println!("Response: {}", response);
+    /// println!("Session ID: {:?}", session.chat_session_id);
     /// # Ok(())
     /// # }
     /// ```
-    #[tracing::instrument(skip(self, session, messages), fields(correlation_id))]
-    pub async fn chat(
+    pub async fn chat_stream_collect(
         &self,
         session: &mut ChippSession,
         messages: &[ChippMessage],
     ) -> Result<String, ChippClientError> {
-        let response = self.chat_detailed(session, messages).await?;
-        Ok(response.content().to_string())
+        let mut stream = self.chat_stream(session, messages).await?;
+        let mut full_response = String::new();
+
+        while let Some(chunk) = stream.next().await {
+            full_response.push_str(&chunk?);
+        }
+
+        // Update session with captured ID after stream completes
+        if let Some(id) = stream.captured_session_id() {
+            session.chat_session_id = Some(id);
+        }
+
+        Ok(full_response)
     }
 
-    /// Send a chat completion request and return the full response with metadata.
-    ///
-    /// This method returns a [`ChatResponse`] containing:
-    /// - The AI's response content
-    /// - Token usage information for rate limiting and monitoring
-    /// - Completion ID for debugging
-    /// - Timestamps and finish reason
-    ///
-    /// For simple use cases where you only need the response text,
-    /// use [`chat()`](Self::chat) instead.
-    ///
-    /// # Arguments
+    /// Like [`chat_stream_collect()`](Self::chat_stream_collect), but keeps the text
+    /// received before a mid-stream failure.
     ///
-    /// * `session` - Session to track conversation state (updates `chatSessionId`)
-    /// * `messages` - Messages in the conversation
-    ///
-    /// # Returns
-    ///
-    /// A [`ChatResponse`] containing the response and metadata.
+    /// If the stream breaks after some chunks arrived, the returned
+    /// [`PartialResponse`] holds the text received so far together with the
+    /// error, instead of discarding the text. The session ID is written back to
+    /// `session` whenever the server sent one.
     ///
     /// # Errors
     ///
-    /// Returns error if HTTP request fails, API returns error, or response parsing fails.
+    /// Returns an error only if the stream could not be opened; see
+    /// [`chat_stream()`](Self::chat_stream). Failures after that are reported in
+    /// [`PartialResponse::error`].
     ///
     /// # Example
     ///
     /// ```no_run
-    /// use chipp::{ChippClient, ChippConfig, ChippSession, ChippMessage};
+    /// use chipp::{ChippClient, ChippConfig, ChippMessage, ChippSession};
     ///
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// # let config = ChippConfig::default();
     /// # let client = ChippClient::new(config)?;
     /// let mut session = ChippSession::new();
-    /// let response = client.chat_detailed(&mut session, &[ChippMessage::user("Hello!")]).await?;
+    /// let response = client
+    ///     .chat_stream_collect_lossy(&mut session, &[ChippMessage::user("Hello")])
+    ///     .await?;
     ///
-    /// println!("Response: {}", response.content());
-    /// println!("Tokens used: {}", response.usage().total_tokens);
+    /// println!("{}", response.text);
+    /// if let Some(error) = &response.error {
+    ///     eprintln!("(answer cut short: {})", error);
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    #[tracing::instrument(skip(self, session, messages), fields(correlation_id))]
-    pub async fn chat_detailed(
+    pub async fn chat_stream_collect_lossy(
         &self,
         session: &mut ChippSession,
         messages: &[ChippMessage],
-    ) -> Result<ChatResponse, ChippClientError> {
-        let correlation_id = Uuid::new_v4().to_string();
-        tracing::Span::current().record("correlation_id", &correlation_id);
-
-        let mut backoff = self.create_backoff();
-        let mut attempt = 0;
-        let max_attempts = self.config.max_retries + 1;
+    ) -> Result<PartialResponse, ChippClientError> {
+        let mut stream = self.chat_stream(session, messages).await?;
+        let mut text = String::new();
 
-        loop {
-            attempt += 1;
-            let result = self.chat_attempt(session, messages, &correlation_id).await;
-
-            match result {
-                Ok(response) => return Ok(response),
-                Err(e) if attempt >= max_attempts => {
-                    tracing::warn!(attempt, error = %e, "Max retry attempts exceeded");
-                    return Err(ChippClientError::MaxRetriesExceeded(
-                        self.config.max_retries,
-                    ));
-                }
-                Err(e) if Self::is_retryable_error(&e) => {
-                    if let Some(delay) = backoff.next_backoff() {
-                        tracing::warn!(attempt, error = %e, delay_ms = delay.as_millis(), "Retrying");
-                        tokio::time::sleep(delay).await;
-                    } else {
-                        return Err(e);
-                    }
-                }
-                Err(e) => {
-                    tracing::error!(error = %e, "Non-retryable error");
-                    return Err(e);
-                }
+        let error = loop {
+            match stream.next().await {
+                Some(Ok(chunk)) => text.push_str(&chunk),
+                Some(Err(e)) => break Some(e),
+                None => break None,
             }
-        }
-    }
-
-    /// Internal method for a single chat attempt.
-    ///
-    /// Returns a `ChatResponse` with all metadata from the API.
-    async fn chat_attempt(
-        &self,
-        session: &mut ChippSession,
-        messages: &[ChippMessage],
-        correlation_id: &str,
-    ) -> Result<ChatResponse, ChippClientError> {
-        let request_body = ChatCompletionRequest {
-            model: self.config.model.clone(),
-            messages: messages.to_vec(),
-            stream: false,
-            chat_session_id: session.chat_session_id.clone(),
         };
 
-        let url = format!("{}/chat/completions", self.config.base_url);
-
-        let response = self
-            .http
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.config.api_key))
-            .header("Content-Type", "application/json")
-            .header("X-Correlation-ID", correlation_id)
-            .json(&request_body)
-            .send()
-            .await?;
-
-        let status = response.status();
-        if !status.is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(ChippClientError::ApiError {
-                status: status.as_u16(),
-                message: error_text,
-            });
-        }
-
-        let response_body: ChatCompletionResponse = response.json().await.map_err(|e| {
-            ChippClientError::InvalidResponse(format!("Failed to parse response: {}", e))
-        })?;
-
-        // Validate we have at least one choice before converting
-        if response_body.choices.is_empty() {
-            return Err(ChippClientError::InvalidResponse(
-                "No choices in response".to_string(),
-            ));
+        // Update session with whatever ID was captured, even on failure
+        if let Some(id) = stream.captured_session_id() {
+            session.chat_session_id = Some(id);
         }
 
-        // Update session with the new session ID
-        session.chat_session_id = Some(response_body.chat_session_id.clone());
-
-        // Convert internal response to public type
-        Ok(response_body.into())
+        Ok(PartialResponse { text, error })
     }
 
-    /// Send a streaming chat completion request (SSE).
-    ///
-    /// Returns a stream of text chunks as they arrive from the API.
-    /// The session's `chatSessionId` is updated when the stream receives metadata.
-    ///
-    /// # Arguments
+    /// Like [`chat_stream_collect()`](Self::chat_stream_collect), but gives up after `total`.
     ///
-    /// * `session` - Session to track conversation state
-    /// * `messages` - Messages in the conversation
-    ///
-    /// # Returns
-    ///
-    /// A stream of `Result<String, ChippClientError>` where each `Ok(String)` is a text chunk.
+    /// The deadline covers opening the stream and reading every chunk. When it
+    /// passes, this returns [`ChippClientError::Timeout`] carrying the text
+    /// received so far. The session ID is written back to `session` whenever
+    /// the server sent one, including on timeout.
     ///
     /// # Example
     ///
     /// ```no_run
-    /// use chipp::{ChippClient, ChippConfig, ChippSession, ChippMessage};
-    /// use futures::StreamExt;
+    /// use chipp::{ChippClient, ChippClientError, ChippConfig, ChippMessage, ChippSession};
+    /// use std::time::Duration;
     ///
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let config = ChippConfig::default();
+    /// # let config = ChippConfig::builder()
+    /// #     .api_key("YOUR_API_KEY_HERE")
+    /// #     .model("myapp-123")
+    /// #     .build()?;
     /// # let client = ChippClient::new(config)?;
     /// let mut session = ChippSession::new();
-    /// let mut stream = client.chat_stream(&mut session, &[ChippMessage::user("Hello")]).await?;
+    /// let messages = [ChippMessage::user("Hello")];
     ///
-    /// while let Some(chunk) = stream.next().await {
-    ///     print!("{}", chunk?);
+    /// match client
+    ///     .chat_stream_collect_timeout(&mut session, &messages, Duration::from_secs(30))
+    ///     .await
+    /// {
+    ///     Ok(response) => println!("{}", response),
+    ///     Err(ChippClientError::Timeout { partial, .. }) => println!("Partial: {}", partial),
+    ///     Err(e) => return Err(e.into()),
     /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn chat_stream(
+    pub async fn chat_stream_collect_timeout(
         &self,
         session: &mut ChippSession,
         messages: &[ChippMessage],
-    ) -> Result<ChippStream, ChippClientError> {
-        let correlation_id = Uuid::new_v4().to_string();
-
-        let request_body = ChatCompletionRequest {
-            model: self.config.model.clone(),
-            messages: messages.to_vec(),
-            stream: true,
-            chat_session_id: session.chat_session_id.clone(),
+        total: Duration,
+    ) -> Result<String, ChippClientError> {
+        let deadline = tokio::time::Instant::now() + total;
+        let timed_out = |partial: String| ChippClientError::Timeout {
+            limit: total,
+            partial,
         };
 
-        let url = format!("{}/chat/completions", self.config.base_url);
-
-        tracing::debug!("Sending Chipp API streaming request");
+        let mut stream =
+            match tokio::time::timeout_at(deadline, self.chat_stream(session, messages)).await {
+                Ok(stream) => stream?,
+                Err(_) => return Err(timed_out(String::new())),
+            };
+        let mut full_response = String::new();
 
-        let response = self
-            .http
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.config.api_key))
-            .header("Content-Type", "application/json")
-            .header("X-Correlation-ID", &correlation_id)
-            .header("Accept", "text/event-stream")
-            .json(&request_body)
-            .send()
-            .await?;
+        let result = loop {
+            match tokio::time::timeout_at(deadline, stream.next()).await {
+                Ok(Some(Ok(chunk))) => full_response.push_str(&chunk),
+                Ok(Some(Err(e))) => break Err(e),
+                Ok(None) => break Ok(()),
+                Err(_) => break Err(timed_out(std::mem::take(&mut full_response))),
+            }
+        };
 
-        let status = response.status();
-        if !status.is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(ChippClientError::ApiError {
-                status: status.as_u16(),
-                message: error_text,
-            });
+        // Update session with whatever ID was captured, even on timeout
+        if let Some(id) = stream.captured_session_id() {
+            session.chat_session_id = Some(id);
         }
 
-        // Create shared session ID that stream will update
-        let session_id = Arc::new(Mutex::new(None::<String>));
-
-        // Get the byte stream for true streaming (not buffered!)
-        let byte_stream = response.bytes_stream();
-
-        // Create the stream
-        let stream = ChippStream::new(Box::pin(byte_stream), session_id);
+        result.map(|()| full_response)
+    }
 
-        Ok(stream)
+    /// Like [`chat_stream_collect_timeout()`](Self::chat_stream_collect_timeout),
+    /// but with a deadline instead of a duration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChippClientError::DeadlineExceeded`] without sending anything
+    /// if `deadline` has already passed, or with the text received so far once
+    /// it passes mid-stream. Otherwise the same errors as
+    /// [`chat_stream_collect_timeout()`](Self::chat_stream_collect_timeout).
+    pub async fn chat_stream_collect_until(
+        &self,
+        session: &mut ChippSession,
+        messages: &[ChippMessage],
+        deadline: Instant,
+    ) -> Result<String, ChippClientError> {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(ChippClientError::DeadlineExceeded {
+                deadline,
+                partial: String::new(),
+            });
+        }
+        self.chat_stream_collect_timeout(session, messages, remaining)
+            .await
+            .map_err(|e| match e {
+                ChippClientError::Timeout { partial, .. } => {
+                    ChippClientError::DeadlineExceeded { deadline, partial }
+                }
+                other => other,
+            })
     }
 
-    /// Send a streaming chat completion and collect the full response.
+    /// Stream a chat completion into a channel, e.g. one read by UI tasks.
     ///
-    /// This is a convenience method that consumes the entire stream and
-    /// updates the session with the captured session ID.
+    /// Each chunk is sent to `tx` in order as it arrives, waiting for capacity
+    /// when the channel is full. If the receiver is dropped, the stream is
+    /// abandoned and this returns `Ok` with whatever usage was reported so far
+    /// (usually none). The session ID is written back to `session` either way.
     ///
-    /// For true streaming where you process chunks as they arrive,
-    /// use [`chat_stream`](Self::chat_stream) instead.
+    /// Returns the token usage from the server's `finish` event, or
+    /// `Usage::default()` if it didn't report any.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`chat_stream()`](Self::chat_stream), plus any error from the
+    /// stream itself. Chunks received before an error have already been sent.
     ///
     /// # Example
     ///
     /// ```no_run
-    /// use chipp::{ChippClient, ChippConfig, ChippSession, ChippMessage};
+    /// use chipp::{ChippClient, ChippConfig, ChippMessage, ChippSession};
     ///
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// # let config = ChippConfig::default();
     /// # let client = ChippClient::new(config)?;
+    /// let (tx, mut rx) = tokio::sync::mpsc::channel(32);
+    /// let ui = tokio::spawn(async move {
+    ///     while let Some(chunk) = rx.recv().await {
+    ///         print!("{}", chunk);
+    ///     }
+    /// });
+    ///
     /// let mut session = ChippSession::new();
-    /// let response = client.chat_stream_collect(&mut session, &[ChippMessage::user("Hello")]).await?;
-    /// println!("Response: {}", response);
-    /// println!("Session ID: {:?}", session.chat_session_id);
+    /// let usage = client
+    ///     .chat_stream_to_sender(&mut session, &[ChippMessage::user("Hello")], tx)
+    ///     .await?;
+    /// ui.await?;
+    /// println!("\nTokens: {}", usage.total_tokens);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn chat_stream_collect(
+    pub async fn chat_stream_to_sender(
         &self,
         session: &mut ChippSession,
         messages: &[ChippMessage],
-    ) -> Result<String, ChippClientError> {
+        tx: tokio::sync::mpsc::Sender<String>,
+    ) -> Result<Usage, ChippClientError> {
         let mut stream = self.chat_stream(session, messages).await?;
-        let mut full_response = String::new();
 
-        while let Some(chunk) = stream.next().await {
-            full_response.push_str(&chunk?);
-        }
+        let result = loop {
+            match stream.next().await {
+                Some(Ok(chunk)) => {
+                    if tx.send(chunk).await.is_err() {
+                        tracing::debug!("Receiver dropped, abandoning stream");
+                        break Ok(());
+                    }
+                }
+                Some(Err(e)) => break Err(e),
+                None => break Ok(()),
+            }
+        };
 
-        // Update session with captured ID after stream completes
-        if let Some(id) = stream.session_id().await {
+        if let Some(id) = stream.captured_session_id() {
             session.chat_session_id = Some(id);
         }
 
-        Ok(full_response)
+        result.map(|()| stream.final_usage().unwrap_or_default())
     }
 
     /// Measure the round-trip latency to the Chipp API.
@@ -427,11 +2524,113 @@ impl ChippClient {
         let start = std::time::Instant::now();
 
         // Use HEAD request for minimal overhead
-        let _response = self.http.head(&url).send().await?;
+        let head = async { Ok(self.http.head(&url).send().await?) };
+        let _response = self.unless_shut_down(head).await?;
 
         // Calculate elapsed time
         let latency = start.elapsed();
 
         Ok(latency)
     }
+
+    /// Prime the connection pool by opening a connection to `base_url`.
+    ///
+    /// Performs the DNS lookup, TCP connect, and TLS handshake ahead of time so
+    /// the first real request doesn't pay for them. The connection is parked in
+    /// the pool and reused by subsequent requests. Call this at startup, e.g. in
+    /// a background task.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use chipp::{ChippClient, ChippConfig};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = ChippConfig::default();
+    /// let client = ChippClient::new(config)?;
+    ///
+    /// if let Err(e) = client.warm_up().await {
+    ///     eprintln!("Warm-up failed, first request will be slower: {}", e);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `ChippClientError::HttpError` if the connection cannot be
+    /// established within the configured timeout. Failing to warm up is not
+    /// fatal; the caller decides whether to continue.
+    pub async fn warm_up(&self) -> Result<(), ChippClientError> {
+        let latency = self.ping().await?;
+        tracing::debug!(
+            latency_ms = latency.as_millis(),
+            "Connection pool warmed up"
+        );
+        Ok(())
+    }
+
+    /// POST a JSON body to an API path and return the untouched response.
+    ///
+    /// An escape hatch for endpoints or body handling this client doesn't
+    /// model. `path` is joined to `base_url` (a leading `/` is optional), and
+    /// the request carries the same `Authorization`, `X-Correlation-ID`,
+    /// `Accept-Language` and idempotency headers, timeout and redirect policy
+    /// as a chat request. Nothing else is done: the request is not retried,
+    /// and the response is returned whatever its status, so callers own
+    /// checking `status()` and reading the body.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use chipp::{ChippClient, ChippConfig};
+    /// use serde_json::json;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = ChippClient::new(ChippConfig::default())?;
+    /// let response = client
+    ///     .raw_post("chat/completions", json!({"model": "myapp-123", "messages": []}))
+    ///     .await?;
+    ///
+    /// if !response.status().is_success() {
+    ///     eprintln!("Request failed with {}", response.status());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `ChippClientError::HttpError` only if the request couldn't be
+    /// sent or no response arrived; error statuses are not errors here.
+    pub async fn raw_post(
+        &self,
+        path: &str,
+        body: serde_json::Value,
+    ) -> Result<reqwest::Response, ChippClientError> {
+        let correlation_id = Uuid::new_v4().to_string();
+
+        let mut request = self
+            .base_request(reqwest::Method::POST, path, &correlation_id)
+            .json(&body);
+        if let Some(header) = &self.config.idempotency_key_header {
+            request = request.header(header.as_str(), &correlation_id);
+        }
+
+        self.unless_shut_down(async { Ok(self.send_request(request).await?) })
+            .await
+    }
+}
+
+/// Unwrap a reply enclosed in a markdown code fence, with or without a language tag.
+///
+/// Content that isn't fenced is returned trimmed but otherwise unchanged.
+fn strip_code_fence(content: &str) -> &str {
+    let trimmed = content.trim();
+    let Some(rest) = trimmed.strip_prefix("```") else {
+        return trimmed;
+    };
+    // Drop the rest of the opening line (the language tag, if any)
+    let body = rest.split_once('\n').map_or(rest, |(_, body)| body);
+    body.trim_end().strip_suffix("```").unwrap_or(body).trim()
 }
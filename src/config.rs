@@ -1,8 +1,140 @@
 //! Configuration for the Chipp API client.
 
 use crate::error::ChippClientError;
+use crate::stream::{StreamEvent, StreamEventCallback};
+use crate::types::ChippMessage;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
 use std::time::Duration;
 
+/// A Chipp API key.
+///
+/// A distinct type so a key can't be passed where an [`AppId`] is expected,
+/// and so it never leaks through logging: both `Debug` and `Display` print
+/// `[REDACTED]`. Use [`expose()`](Self::expose) where the raw key is needed.
+///
+/// # Example
+///
+/// ```
+/// use chipp::ApiKey;
+///
+/// let key = ApiKey::from("live_secret");
+/// assert_eq!(format!("{:?}", key), "ApiKey([REDACTED])");
+/// assert_eq!(key.expose(), "live_secret");
+/// ```
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct ApiKey(String);
+
+impl ApiKey {
+    /// Get the raw key, e.g. to build an `Authorization` header.
+    #[must_use]
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// Check whether the key is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+// SECURITY: the key itself is never formatted
+impl fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ApiKey([REDACTED])")
+    }
+}
+
+impl fmt::Display for ApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+impl From<&str> for ApiKey {
+    fn from(key: &str) -> Self {
+        Self(key.to_string())
+    }
+}
+
+impl From<String> for ApiKey {
+    fn from(key: String) -> Self {
+        Self(key)
+    }
+}
+
+impl PartialEq<str> for ApiKey {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for ApiKey {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+/// A Chipp app identifier (the `appNameId`), sent as the request `model`.
+///
+/// # Example
+///
+/// ```
+/// use chipp::AppId;
+///
+/// let app = AppId::from("myapp-123");
+/// assert_eq!(app.as_str(), "myapp-123");
+/// assert_eq!(app.to_string(), "myapp-123");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct AppId(String);
+
+impl AppId {
+    /// Get the identifier as a string slice.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Check whether the identifier is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Display for AppId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for AppId {
+    fn from(id: &str) -> Self {
+        Self(id.to_string())
+    }
+}
+
+impl From<String> for AppId {
+    fn from(id: String) -> Self {
+        Self(id)
+    }
+}
+
+impl PartialEq<str> for AppId {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for AppId {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
 /// Configuration for Chipp API client.
 ///
 /// Use [`ChippConfigBuilder`] for ergonomic construction, or create directly.
@@ -14,8 +146,8 @@ use std::time::Duration;
 /// use std::time::Duration;
 ///
 /// let config = ChippConfig {
-///     api_key: "YOUR_API_KEY_HERE".to_string(),
-///     model: "myapp-123".to_string(),
+///     api_key: "YOUR_API_KEY_HERE".into(),
+///     model: "myapp-123".into(),
 ///     ..Default::default()
 /// };
 /// ```
@@ -34,61 +166,845 @@ use std::time::Duration;
 #[derive(Clone)]
 pub struct ChippConfig {
     /// Chipp API key (from Share → API tab in Chipp dashboard)
-    pub api_key: String,
+    pub api_key: ApiKey,
 
     /// Base URL for Chipp API (default: `https://app.chipp.ai/api/v1`)
+    ///
+    /// A single trailing slash is removed by the builder and `ChippClient::new`.
     pub base_url: String,
 
     /// Chipp appNameId (e.g., "myapp-123" from your Chipp dashboard)
-    pub model: String,
+    pub model: AppId,
 
     /// Request timeout (default: 30 seconds)
     pub timeout: Duration,
 
-    /// Maximum number of retry attempts for transient failures (default: 3)
+    /// Maximum number of retries for transient failures (default: 3)
+    ///
+    /// Counts retries, not attempts: `0` still makes one request, it just never
+    /// retries it. Use [`ChippConfigBuilder::attempts()`] to set the total
+    /// number of requests instead.
     pub max_retries: usize,
 
     /// Initial delay before first retry (default: 100ms)
+    ///
+    /// Delays roughly double after each failed attempt, with `retry_jitter`
+    /// applied on top. The backoff is per call: every request starts again
+    /// from this delay, no matter how many retries earlier calls on the same
+    /// client needed.
     pub initial_retry_delay: Duration,
 
     /// Maximum delay between retries (default: 10 seconds)
     pub max_retry_delay: Duration,
+
+    /// Random spread applied to each retry delay, as a fraction (default: 0.3)
+    ///
+    /// A delay `d` becomes a random value in `d × (1 ± retry_jitter)`, so clients
+    /// failing together don't retry in lockstep. `0.0` gives exact delays.
+    /// Must be between `0.0` and `1.0`.
+    pub retry_jitter: f64,
+
+    /// Locale sent as the `Accept-Language` header (e.g., "en-US"; default: none)
+    pub locale: Option<String>,
+
+    /// Messages prepended to every request, such as few-shot example turns (default: empty)
+    ///
+    /// The prefix is inserted after any leading system messages in the caller's
+    /// messages and before the rest of the conversation.
+    pub message_prefix: Vec<ChippMessage>,
+
+    /// Reject responses containing fields the SDK doesn't know about (default: false)
+    ///
+    /// Useful in contract tests to detect API drift early. When disabled,
+    /// unknown fields are ignored for forward compatibility.
+    pub strict_response_parsing: bool,
+
+    /// Log message content at debug level (default: false)
+    ///
+    /// When disabled, the client never records request or response message
+    /// content in tracing spans or events. Leave this off wherever message
+    /// content may contain personal or confidential data.
+    pub log_message_content: bool,
+
+    /// Interval between HTTP/2 keep-alive pings (default: none, pings disabled)
+    ///
+    /// Pings detect dead connections much sooner than the idle timeout, which
+    /// helps long-lived streaming responses on flaky networks.
+    pub http2_keep_alive_interval: Option<Duration>,
+
+    /// Send HTTP/2 keep-alive pings even when no request is in flight (default: false)
+    ///
+    /// Only has an effect when `http2_keep_alive_interval` is set.
+    pub http2_keep_alive_while_idle: bool,
+
+    /// Observer invoked for every parsed streaming event (default: none)
+    ///
+    /// Called as each event is processed, whether or not the consumer reads it,
+    /// so it can record per-token metrics such as inter-token latency without
+    /// changing how streams are consumed. Keep it cheap; it runs inline while
+    /// polling the stream.
+    pub on_stream_event: Option<StreamEventCallback>,
+
+    /// Header carrying an idempotency key (default: `Some("Idempotency-Key")`)
+    ///
+    /// The key is generated once per logical call and reused by every retry,
+    /// so the server can deduplicate requests whose response was lost. Set to
+    /// `None` to stop sending it.
+    pub idempotency_key_header: Option<String>,
+
+    /// Separate retry budget for `429 Too Many Requests` responses (default: none)
+    ///
+    /// When set, 429s are retried up to this many times, waiting for the
+    /// `Retry-After` header (in seconds, capped at `max_retry_delay`) when
+    /// present, and don't count against `max_retries`. When unset, 429s are
+    /// retried like any other transient error.
+    pub rate_limit_max_retries: Option<usize>,
+
+    /// Decide whether a failed attempt is retried (default: none)
+    ///
+    /// When set, this fully replaces the built-in rule (network errors, 5xx,
+    /// 429, empty or truncated bodies), so it can inspect the status and
+    /// message, e.g. to give up on a 503 that reports the app as disabled.
+    /// Retries it allows still count against `max_retries`.
+    pub should_retry: Option<RetryPredicate>,
+
+    /// Start a new session when the server no longer knows the session ID (default: false)
+    ///
+    /// When the API answers a request carrying a `chatSessionId` with an
+    /// error whose body names an unknown session (e.g. "session not found"),
+    /// the session is reset and the request is sent once more without the ID,
+    /// transparently starting a fresh conversation. Other errors, including a
+    /// bare 404, are handled as usual.
+    pub auto_reset_invalid_session: bool,
+
+    /// Shape of the request body (default: [`RequestEnvelope::Standard`])
+    ///
+    /// Only needs changing for Chipp-compatible relays that expect the
+    /// messages nested inside another object.
+    pub request_envelope: RequestEnvelope,
+
+    /// How the conversation is sent (default: [`RequestFormat::Messages`])
+    ///
+    /// Only needs changing for legacy endpoints that take a single `prompt`
+    /// string instead of a `messages` array.
+    pub request_format: RequestFormat,
+
+    /// How HTTP redirects are followed (default: `RedirectPolicy::Limited(10)`)
+    ///
+    /// See [`RedirectPolicy`] for the security trade-off of forwarding the API
+    /// key to another host.
+    pub redirect_policy: RedirectPolicy,
+
+    /// Derive each non-streaming request's timeout from recent response times
+    /// instead of using `timeout` (default: none)
+    ///
+    /// See [`AdaptiveTimeout`].
+    pub adaptive_timeout: Option<AdaptiveTimeout>,
+
+    /// Retry streaming requests that fail before the first text chunk (default: false)
+    ///
+    /// When enabled, opening a stream waits for the first `text-delta`, and a
+    /// retryable failure up to that point (such as a connection reset right
+    /// after the headers) re-sends the request under the same retry policy as
+    /// non-streaming calls: `max_retries`, `rate_limit_max_retries` and
+    /// Retry-After all apply. A body cut short before the first chunk is also
+    /// retried here. Once text has been handed out, errors surface on the
+    /// stream as before, since a retry would repeat it.
+    pub retry_stream_start: bool,
+
+    /// Largest request body, in bytes, the client will send (default: no limit)
+    ///
+    /// Checked after serialization and before anything is sent; a larger body
+    /// fails with [`ChippClientError::RequestTooLarge`] instead of a round-trip
+    /// to a `400`.
+    pub max_request_bytes: Option<usize>,
+
+    /// Treat a blank reply that finished with `"stop"` as an error (default: false)
+    ///
+    /// When enabled, a non-streaming completion whose content is empty or only
+    /// whitespace (see [`ChatResponse::is_empty()`](crate::ChatResponse::is_empty))
+    /// fails with [`ChippClientError::InvalidResponse`] instead of returning `""`.
+    /// Blank replies cut off for another reason (e.g. `"length"`) are returned as is.
+    pub reject_empty_completions: bool,
+
+    /// Treat replies blocked by the content filter as errors (default: false)
+    ///
+    /// When enabled, a non-streaming completion that finished with
+    /// `"content_filter"` (see [`ChatResponse::was_filtered()`](crate::ChatResponse::was_filtered))
+    /// fails with [`ChippClientError::ContentFiltered`] instead of returning
+    /// whatever canned text came back. It is not retried.
+    pub reject_filtered_completions: bool,
+
+    /// Reject user messages with blank content before sending (default: true)
+    ///
+    /// The API answers an empty user message with an opaque `400`; with this
+    /// enabled the request fails locally with
+    /// `ChippClientError::ConfigError("empty user message")` instead. System
+    /// and assistant messages are never checked, since an empty system prompt
+    /// is legitimate in some setups.
+    pub reject_empty_messages: bool,
+
+    /// Record OpenTelemetry GenAI attributes on a `gen_ai.chat` span (default: false)
+    ///
+    /// When enabled, every chat call, streaming or not, opens a `gen_ai.chat` span
+    /// with the semantic convention fields `gen_ai.operation.name`,
+    /// `gen_ai.system` (`"chipp"`), `gen_ai.request.model`,
+    /// `gen_ai.response.model`, `gen_ai.usage.input_tokens`,
+    /// `gen_ai.usage.output_tokens` and `gen_ai.response.finish_reasons` (a
+    /// list), so standard GenAI dashboards can use them. A stream's span stays
+    /// open while the stream is read and records usage as it is reported. The
+    /// existing request spans and field names are unchanged.
+    pub otel_semantic_conventions: bool,
+
+    /// Extra trusted root certificate, PEM or DER encoded (default: none)
+    ///
+    /// For mirrors behind an internal CA. The certificate is trusted in
+    /// addition to the system roots, which stay trusted; a PEM bundle may hold
+    /// several certificates. [`ChippClient::new()`](crate::ChippClient::new)
+    /// fails with [`ChippClientError::ConfigError`] if it can't be parsed.
+    pub root_certificate: Option<Vec<u8>>,
+
+    /// Accept any TLS certificate, including self-signed and expired ones (default: false)
+    ///
+    /// # Danger
+    ///
+    /// This turns off certificate verification entirely: anyone on the network
+    /// path can impersonate the server and read the API key and every message.
+    /// Only use it against a local or development server with a self-signed
+    /// certificate. Requires the non-default `dangerous-tls` feature, and the
+    /// client logs a warning whenever it is enabled.
+    #[cfg(feature = "dangerous-tls")]
+    pub danger_accept_invalid_certs: bool,
 }
 
-// SECURITY: Custom Debug implementation to prevent API key exposure in logs
+/// Retry settings applied together with [`ChippConfigBuilder::retry()`].
+///
+/// The [`Default`] matches [`ChippConfig::default()`]. Use the presets for
+/// common trade-offs instead of tuning four numbers by hand.
+///
+/// # Example
+///
+/// ```
+/// use chipp::{ChippConfig, RetryConfig};
+///
+/// let config = ChippConfig::builder()
+///     .api_key("YOUR_API_KEY_HERE")
+///     .model("myapp-123")
+///     .retry(RetryConfig::conservative())
+///     .build()
+///     .expect("Invalid config");
+///
+/// assert_eq!(config.max_retries, RetryConfig::conservative().max_retries);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryConfig {
+    /// Maximum retry attempts after the first request
+    pub max_retries: usize,
+    /// Delay before the first retry
+    pub initial_delay: Duration,
+    /// Upper bound for the exponentially growing delay
+    pub max_delay: Duration,
+    /// Fraction each delay is randomly varied by, from 0.0 to 1.0
+    pub jitter: f64,
+}
+
+impl RetryConfig {
+    /// Retry quickly and often: 5 retries, 50ms initial delay, 2 second cap,
+    /// 0.5 jitter.
+    ///
+    /// Suits interactive use where a fast answer matters more than load on the
+    /// API. The wider jitter keeps many clients' rapid retries from lining up.
+    #[must_use]
+    pub fn aggressive() -> Self {
+        Self {
+            max_retries: 5,
+            initial_delay: Duration::from_millis(50),
+            max_delay: Duration::from_secs(2),
+            jitter: 0.5,
+        }
+    }
+
+    /// Retry sparingly with long waits: 2 retries, 1 second initial delay,
+    /// 30 second cap, 0.3 jitter.
+    ///
+    /// Suits background jobs that should back off while the API recovers.
+    #[must_use]
+    pub fn conservative() -> Self {
+        Self {
+            max_retries: 2,
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+            jitter: 0.3,
+        }
+    }
+
+    /// Never retry; the delays and jitter keep their defaults and are unused.
+    #[must_use]
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        let defaults = ChippConfig::default();
+        Self {
+            max_retries: defaults.max_retries,
+            initial_delay: defaults.initial_retry_delay,
+            max_delay: defaults.max_retry_delay,
+            jitter: defaults.retry_jitter,
+        }
+    }
+}
+
+/// Per-request timeout derived from recent response times.
+///
+/// Set on [`ChippConfig::adaptive_timeout`]. The client keeps the durations
+/// of the last `window` successful non-streaming requests (shared by all
+/// clones) and gives each attempt a timeout of `max(min, p95 * factor)`.
+/// Until the first request succeeds, the fixed [`ChippConfig::timeout`]
+/// applies.
+///
+/// # Example
+///
+/// ```
+/// use chipp::AdaptiveTimeout;
+/// use std::time::Duration;
+///
+/// let adaptive = AdaptiveTimeout {
+///     min: Duration::from_secs(2),
+///     factor: 3.0,
+///     window: 20,
+/// };
+///
+/// let recent = [Duration::from_secs(1), Duration::from_millis(1500)];
+/// assert_eq!(adaptive.timeout_for(&recent), Some(Duration::from_millis(4500)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdaptiveTimeout {
+    /// Lower bound for the computed timeout
+    pub min: Duration,
+    /// Multiplier applied to the p95 response time (must be finite and positive)
+    pub factor: f64,
+    /// Number of recent successful requests the p95 is computed over (must be non-zero)
+    pub window: usize,
+}
+
+impl AdaptiveTimeout {
+    /// Compute the timeout for the next request from recent response times.
+    ///
+    /// Returns `None` if `recent` is empty. The p95 uses the nearest-rank method.
+    #[must_use]
+    pub fn timeout_for(&self, recent: &[Duration]) -> Option<Duration> {
+        if recent.is_empty() {
+            return None;
+        }
+
+        let mut sorted = recent.to_vec();
+        sorted.sort_unstable();
+        let rank = (sorted.len() * 95).div_ceil(100);
+        let p95 = sorted[rank.saturating_sub(1)];
+
+        let scaled =
+            Duration::try_from_secs_f64(p95.as_secs_f64() * self.factor).unwrap_or(Duration::MAX);
+        Some(scaled.max(self.min))
+    }
+}
+
+impl Default for AdaptiveTimeout {
+    /// 5 second floor, twice the p95, over the last 50 requests.
+    fn default() -> Self {
+        Self {
+            min: Duration::from_secs(5),
+            factor: 2.0,
+            window: 50,
+        }
+    }
+}
+
+/// Predicate deciding whether a failed request is retried.
+///
+/// See [`ChippConfig::should_retry`].
+pub type RetryPredicate = Arc<dyn Fn(&ChippClientError) -> bool + Send + Sync>;
+
+/// Shape of the JSON request body sent to the chat completions endpoint.
+///
+/// # Example
+///
+/// ```
+/// use chipp::{ChippConfig, RequestEnvelope};
+///
+/// // Sends {"model": ..., "input": {"messages": [...]}, "stream": ...}
+/// let config = ChippConfig {
+///     request_envelope: RequestEnvelope::Nested("input".to_string()),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum RequestEnvelope {
+    /// OpenAI-compatible shape used by the public Chipp API:
+    /// `{"model", "messages": [...], "stream", "chatSessionId"}`
+    #[default]
+    Standard,
+    /// Messages nested under the given field:
+    /// `{"model", "<field>": {"messages": [...]}, "stream", "chatSessionId"}`
+    Nested(String),
+}
+
+/// How the conversation is encoded in the request body.
+///
+/// # Example
+///
+/// ```
+/// use chipp::{ChippConfig, RequestFormat};
+///
+/// // Sends {"model": ..., "prompt": "system: ...\nuser: ...", "stream": ...}
+/// let config = ChippConfig {
+///     request_format: RequestFormat::flat_prompt(),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum RequestFormat {
+    /// A `messages` array of `{"role", "content"}` objects
+    #[default]
+    Messages,
+    /// A single `prompt` string, one line per message rendered from the given
+    /// template, joined with `\n`
+    ///
+    /// `{role}` is replaced with the lowercase role (`system`, `user` or
+    /// `assistant`) and `{content}` with the message text. Line breaks within
+    /// a message are followed by two spaces of indentation, so only the
+    /// template starts a new turn.
+    FlatPrompt(String),
+}
+
+impl RequestFormat {
+    /// Template used by [`flat_prompt()`](Self::flat_prompt).
+    pub const DEFAULT_PROMPT_TEMPLATE: &'static str = "{role}: {content}";
+
+    /// [`FlatPrompt`](Self::FlatPrompt) with the default `{role}: {content}` template.
+    #[must_use]
+    pub fn flat_prompt() -> Self {
+        Self::FlatPrompt(Self::DEFAULT_PROMPT_TEMPLATE.to_string())
+    }
+}
+
+/// How redirects from the Chipp API are followed.
+///
+/// # Security
+///
+/// With [`Limited`](Self::Limited), the `Authorization` header is dropped when
+/// a redirect points to a different host, so the API key never leaves the
+/// configured origin; the redirected request then typically fails with 401.
+/// [`LimitedWithAuth`](Self::LimitedWithAuth) re-sends the API key, but only to
+/// the same origin (scheme, host and port); a redirect anywhere else, including
+/// an `https` to `http` downgrade, is not followed and is returned as is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedirectPolicy {
+    /// Never follow redirects; a 3xx response is returned as an `ApiError`
+    None,
+    /// Follow up to this many redirects, dropping credentials on cross-host hops
+    Limited(usize),
+    /// Follow up to this many same-origin `307`/`308` redirects, keeping the
+    /// method, body and all headers, including `Authorization`
+    LimitedWithAuth(usize),
+}
+
+impl Default for RedirectPolicy {
+    fn default() -> Self {
+        Self::Limited(10)
+    }
+}
+
+// SECURITY: the API key is redacted by `ApiKey`'s own Debug implementation
 impl std::fmt::Debug for ChippConfig {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("ChippConfig")
-            .field("api_key", &"[REDACTED]")
+        let mut debug = f.debug_struct("ChippConfig");
+        debug
+            .field("api_key", &self.api_key)
             .field("base_url", &self.base_url)
             .field("model", &self.model)
             .field("timeout", &self.timeout)
             .field("max_retries", &self.max_retries)
             .field("initial_retry_delay", &self.initial_retry_delay)
             .field("max_retry_delay", &self.max_retry_delay)
-            .finish()
+            .field("retry_jitter", &self.retry_jitter)
+            .field("locale", &self.locale)
+            .field("message_prefix", &self.message_prefix)
+            .field("strict_response_parsing", &self.strict_response_parsing)
+            .field("log_message_content", &self.log_message_content)
+            .field("http2_keep_alive_interval", &self.http2_keep_alive_interval)
+            .field(
+                "http2_keep_alive_while_idle",
+                &self.http2_keep_alive_while_idle,
+            )
+            .field(
+                "on_stream_event",
+                &self.on_stream_event.as_ref().map(|_| "<callback>"),
+            )
+            .field("idempotency_key_header", &self.idempotency_key_header)
+            .field("rate_limit_max_retries", &self.rate_limit_max_retries)
+            .field(
+                "should_retry",
+                &self.should_retry.as_ref().map(|_| "<predicate>"),
+            )
+            .field(
+                "auto_reset_invalid_session",
+                &self.auto_reset_invalid_session,
+            )
+            .field("request_envelope", &self.request_envelope)
+            .field("request_format", &self.request_format)
+            .field("redirect_policy", &self.redirect_policy)
+            .field("adaptive_timeout", &self.adaptive_timeout)
+            .field("retry_stream_start", &self.retry_stream_start)
+            .field("max_request_bytes", &self.max_request_bytes)
+            .field("reject_empty_completions", &self.reject_empty_completions)
+            .field(
+                "reject_filtered_completions",
+                &self.reject_filtered_completions,
+            )
+            .field("reject_empty_messages", &self.reject_empty_messages)
+            .field("otel_semantic_conventions", &self.otel_semantic_conventions)
+            .field(
+                "root_certificate",
+                &self.root_certificate.as_ref().map(Vec::len),
+            );
+        #[cfg(feature = "dangerous-tls")]
+        debug.field(
+            "danger_accept_invalid_certs",
+            &self.danger_accept_invalid_certs,
+        );
+        debug.finish()
+    }
+}
+
+/// Defaults for every setting, with an empty `api_key` and `model`.
+///
+/// Not usable on its own: requests fail with `401` until both are set. Prefer
+/// [`ChippConfig::builder()`], which checks for them, or
+/// [`ChippClient::try_from_env()`](crate::ChippClient::try_from_env). Use
+/// `..Default::default()` only to fill in the remaining fields.
+impl Default for ChippConfig {
+    fn default() -> Self {
+        Self {
+            api_key: ApiKey::default(),
+            base_url: "https://app.chipp.ai/api/v1".to_string(),
+            model: AppId::default(),
+            timeout: Duration::from_secs(30),
+            max_retries: 3,
+            initial_retry_delay: Duration::from_millis(100),
+            max_retry_delay: Duration::from_secs(10),
+            retry_jitter: 0.3,
+            locale: None,
+            message_prefix: Vec::new(),
+            strict_response_parsing: false,
+            log_message_content: false,
+            http2_keep_alive_interval: None,
+            http2_keep_alive_while_idle: false,
+            on_stream_event: None,
+            idempotency_key_header: Some("Idempotency-Key".to_string()),
+            rate_limit_max_retries: None,
+            should_retry: None,
+            auto_reset_invalid_session: false,
+            request_envelope: RequestEnvelope::Standard,
+            request_format: RequestFormat::Messages,
+            redirect_policy: RedirectPolicy::default(),
+            adaptive_timeout: None,
+            retry_stream_start: false,
+            max_request_bytes: None,
+            reject_empty_completions: false,
+            reject_filtered_completions: false,
+            reject_empty_messages: true,
+            otel_semantic_conventions: false,
+            root_certificate: None,
+            #[cfg(feature = "dangerous-tls")]
+            danger_accept_invalid_certs: false,
+        }
+    }
+}
+
+impl ChippConfig {
+    /// Environment variable read by [`from_env()`](Self::from_env) for the API key.
+    pub const API_KEY_ENV: &'static str = "CHIPP_API_KEY";
+
+    /// Environment variable read by [`from_env()`](Self::from_env) for the app name ID.
+    pub const APP_NAME_ID_ENV: &'static str = "CHIPP_APP_NAME_ID";
+
+    /// Create a builder for `ChippConfig`.
+    #[must_use]
+    pub fn builder() -> ChippConfigBuilder {
+        ChippConfigBuilder::default()
+    }
+
+    /// Create a builder that only offers `build()` once `api_key` and `model` are set.
+    ///
+    /// See [`TypedConfigBuilder`]. Use [`builder()`](Self::builder) instead
+    /// when the required values are only known at runtime.
+    #[must_use]
+    pub fn typed_builder() -> TypedConfigBuilder<typestate::Missing, typestate::Missing> {
+        TypedConfigBuilder {
+            inner: ChippConfigBuilder::default(),
+            state: PhantomData,
+        }
+    }
+
+    /// Total number of requests made per call: the first attempt plus `max_retries`.
+    #[must_use]
+    pub fn attempts(&self) -> usize {
+        self.max_retries.saturating_add(1)
+    }
+
+    /// Build a configuration from the `CHIPP_API_KEY` and `CHIPP_APP_NAME_ID`
+    /// environment variables, with defaults for everything else.
+    ///
+    /// Shorthand for the builder with [`api_key_from_env()`](ChippConfigBuilder::api_key_from_env)
+    /// and [`model_from_env()`](ChippConfigBuilder::model_from_env); use those
+    /// directly to read other variables or change other settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError` if either variable is unset, not valid Unicode, or empty.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use chipp::ChippConfig;
+    ///
+    /// let config = ChippConfig::from_env()
+    ///     .expect("CHIPP_API_KEY and CHIPP_APP_NAME_ID must be set");
+    /// ```
+    pub fn from_env() -> Result<Self, ChippClientError> {
+        for var_name in [Self::API_KEY_ENV, Self::APP_NAME_ID_ENV] {
+            if std::env::var_os(var_name).is_some_and(|value| value.is_empty()) {
+                return Err(ChippClientError::ConfigError(format!(
+                    "environment variable {} is empty",
+                    var_name
+                )));
+            }
+        }
+        Self::builder()
+            .api_key_from_env(Self::API_KEY_ENV)
+            .model_from_env(Self::APP_NAME_ID_ENV)
+            .build()
+    }
+
+    /// Get a stable, redacted one-line summary of the settings that most often
+    /// explain behavior differences.
+    ///
+    /// Covers `base_url`, `model`, `timeout`, the retry parameters and the
+    /// request shape, as `name=value` pairs in a fixed order. The API key is
+    /// only reported as `set` or `empty`, so the fingerprint is safe to log at
+    /// startup and compare between environments; see [`diff()`](Self::diff)
+    /// for the fields that differ.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chipp::ChippConfig;
+    /// use std::time::Duration;
+    ///
+    /// let config = ChippConfig::builder()
+    ///     .api_key("secret-key")
+    ///     .model("myapp-123")
+    ///     .timeout(Duration::from_secs(60))
+    ///     .build()?;
+    ///
+    /// let fingerprint = config.fingerprint();
+    /// assert!(fingerprint.contains("model=myapp-123"));
+    /// assert!(fingerprint.contains("timeout=60s"));
+    /// assert!(!fingerprint.contains("secret-key"));
+    /// # Ok::<(), chipp::ChippClientError>(())
+    /// ```
+    #[must_use]
+    pub fn fingerprint(&self) -> String {
+        self.summary()
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// List the fingerprinted fields that differ from `other`, as
+    /// `"name: self_value -> other_value"`.
+    ///
+    /// Empty when both configurations have the same
+    /// [`fingerprint()`](Self::fingerprint).
+    #[must_use]
+    pub fn diff(&self, other: &ChippConfig) -> Vec<String> {
+        self.summary()
+            .into_iter()
+            .zip(other.summary())
+            .filter(|((_, ours), (_, theirs))| ours != theirs)
+            .map(|((name, ours), (_, theirs))| format!("{}: {} -> {}", name, ours, theirs))
+            .collect()
+    }
+
+    /// Redacted values of the fingerprinted fields, in a fixed order.
+    fn summary(&self) -> Vec<(&'static str, String)> {
+        let api_key = if self.api_key.is_empty() {
+            "empty"
+        } else {
+            "set"
+        };
+        vec![
+            ("api_key", api_key.to_string()),
+            ("base_url", self.base_url.clone()),
+            ("model", self.model.to_string()),
+            ("timeout", format!("{:?}", self.timeout)),
+            ("max_retries", self.max_retries.to_string()),
+            (
+                "initial_retry_delay",
+                format!("{:?}", self.initial_retry_delay),
+            ),
+            ("max_retry_delay", format!("{:?}", self.max_retry_delay)),
+            ("retry_jitter", self.retry_jitter.to_string()),
+            ("retry_stream_start", self.retry_stream_start.to_string()),
+            ("redirect_policy", format!("{:?}", self.redirect_policy)),
+            ("request_envelope", format!("{:?}", self.request_envelope)),
+            ("request_format", format!("{:?}", self.request_format)),
+        ]
+    }
+}
+
+/// Parse a `chipp://` connection string.
+///
+/// The format is `chipp://API_KEY@HOST[:PORT][/BASE_PATH]/MODEL[?PARAMS]`:
+///
+/// - The userinfo is the API key, percent-decoded; a password
+///   (`API_KEY:PASSWORD@`) is rejected rather than ignored
+/// - The last path segment is the model (app name ID)
+/// - Host, port and any remaining path form the base URL; without a base
+///   path the standard `/api/v1` is used
+/// - Supported query parameters are `timeout` (seconds), `max_retries` and
+///   `scheme` (`https`, the default, or `http` for local servers)
+///
+/// Any other option keeps its default. Malformed strings return
+/// [`ChippClientError::ConfigError`].
+///
+/// # Example
+///
+/// ```
+/// use chipp::ChippConfig;
+/// use std::time::Duration;
+///
+/// let config: ChippConfig = "chipp://YOUR_API_KEY@app.chipp.ai/myapp-123?timeout=60"
+///     .parse()
+///     .expect("Invalid connection string");
+///
+/// assert_eq!(config.base_url, "https://app.chipp.ai/api/v1");
+/// assert_eq!(config.model, "myapp-123");
+/// assert_eq!(config.timeout, Duration::from_secs(60));
+/// ```
+impl std::str::FromStr for ChippConfig {
+    type Err = ChippClientError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: String| {
+            ChippClientError::ConfigError(format!("invalid connection string: {}", reason))
+        };
+
+        let url = reqwest::Url::parse(s).map_err(|e| invalid(e.to_string()))?;
+        if url.scheme() != "chipp" {
+            return Err(invalid(format!(
+                "expected scheme \"chipp\", got {:?}",
+                url.scheme()
+            )));
+        }
+
+        let host = url
+            .host_str()
+            .filter(|host| !host.is_empty())
+            .ok_or_else(|| invalid("missing host".to_string()))?;
+        if url.username().is_empty() {
+            return Err(invalid("missing API key".to_string()));
+        }
+        if url.password().is_some() {
+            return Err(invalid(
+                "unexpected password; the userinfo is only the API key".to_string(),
+            ));
+        }
+        let api_key = percent_decode(url.username())
+            .ok_or_else(|| invalid("API key is not valid percent-encoded UTF-8".to_string()))?;
+
+        let path = url.path().trim_matches('/');
+        let (base_path, model) = match path.rsplit_once('/') {
+            Some((base_path, model)) => (base_path, model),
+            None => ("api/v1", path),
+        };
+        if model.is_empty() {
+            return Err(invalid("missing model".to_string()));
+        }
+
+        let mut scheme = "https";
+        let mut builder = ChippConfig::builder().api_key(api_key).model(model);
+
+        for (key, value) in url.query_pairs() {
+            match key.as_ref() {
+                "scheme" => {
+                    scheme = match value.as_ref() {
+                        "https" => "https",
+                        "http" => "http",
+                        _ => {
+                            return Err(invalid(format!(
+                                "scheme {:?} is not \"https\" or \"http\"",
+                                value
+                            )))
+                        }
+                    };
+                }
+                "timeout" => {
+                    let secs = value
+                        .parse()
+                        .map_err(|_| invalid(format!("timeout {:?} is not a number", value)))?;
+                    builder = builder.timeout(Duration::from_secs(secs));
+                }
+                "max_retries" => {
+                    let retries = value
+                        .parse()
+                        .map_err(|_| invalid(format!("max_retries {:?} is not a number", value)))?;
+                    builder = builder.max_retries(retries);
+                }
+                other => return Err(invalid(format!("unknown parameter {:?}", other))),
+            }
+        }
+
+        let mut base_url = format!("{}://{}", scheme, host);
+        if let Some(port) = url.port() {
+            base_url.push_str(&format!(":{}", port));
+        }
+        base_url.push('/');
+        base_url.push_str(base_path);
+
+        builder.base_url(base_url).build()
     }
 }
 
-impl Default for ChippConfig {
-    fn default() -> Self {
-        Self {
-            api_key: String::new(),
-            base_url: "https://app.chipp.ai/api/v1".to_string(),
-            model: String::new(),
-            timeout: Duration::from_secs(30),
-            max_retries: 3,
-            initial_retry_delay: Duration::from_millis(100),
-            max_retry_delay: Duration::from_secs(10),
+/// Decode `%XX` escapes, returning `None` for a bad escape or invalid UTF-8.
+fn percent_decode(encoded: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut rest = encoded.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
         }
     }
+    String::from_utf8(bytes).ok()
 }
 
-impl ChippConfig {
-    /// Create a builder for `ChippConfig`.
-    #[must_use]
-    pub fn builder() -> ChippConfigBuilder {
-        ChippConfigBuilder::default()
+impl TryFrom<&str> for ChippConfig {
+    type Error = ChippClientError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
     }
 }
 
@@ -109,42 +1025,222 @@ impl ChippConfig {
 /// ```
 #[derive(Default)]
 pub struct ChippConfigBuilder {
-    api_key: Option<String>,
+    api_key: Option<ApiKey>,
+    api_key_env: Option<String>,
     base_url: Option<String>,
-    model: Option<String>,
+    model: Option<AppId>,
+    model_env: Option<String>,
     timeout: Option<Duration>,
     max_retries: Option<usize>,
+    attempts: Option<usize>,
     initial_retry_delay: Option<Duration>,
     max_retry_delay: Option<Duration>,
+    retry_jitter: Option<f64>,
+    locale: Option<String>,
+    message_prefix: Option<Vec<ChippMessage>>,
+    strict_response_parsing: Option<bool>,
+    log_message_content: Option<bool>,
+    http2_keep_alive_interval: Option<Duration>,
+    http2_keep_alive_while_idle: Option<bool>,
+    on_stream_event: Option<StreamEventCallback>,
+    idempotency_key_header: Option<Option<String>>,
+    rate_limit_max_retries: Option<usize>,
+    should_retry: Option<RetryPredicate>,
+    auto_reset_invalid_session: Option<bool>,
+    request_envelope: Option<RequestEnvelope>,
+    request_format: Option<RequestFormat>,
+    redirect_policy: Option<RedirectPolicy>,
+    adaptive_timeout: Option<AdaptiveTimeout>,
+    retry_stream_start: Option<bool>,
+    max_request_bytes: Option<usize>,
+    reject_empty_completions: Option<bool>,
+    reject_filtered_completions: Option<bool>,
+    reject_empty_messages: Option<bool>,
+    otel_semantic_conventions: Option<bool>,
+    root_certificate: Option<Vec<u8>>,
+    #[cfg(feature = "dangerous-tls")]
+    danger_accept_invalid_certs: Option<bool>,
 }
 
-// SECURITY: Custom Debug implementation to prevent API key exposure in logs
+// SECURITY: the API key is redacted by `ApiKey`'s own Debug implementation
 impl std::fmt::Debug for ChippConfigBuilder {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("ChippConfigBuilder")
-            .field("api_key", &self.api_key.as_ref().map(|_| "[REDACTED]"))
+        let mut debug = f.debug_struct("ChippConfigBuilder");
+        debug
+            .field("api_key", &self.api_key)
+            .field("api_key_env", &self.api_key_env)
             .field("base_url", &self.base_url)
             .field("model", &self.model)
+            .field("model_env", &self.model_env)
             .field("timeout", &self.timeout)
             .field("max_retries", &self.max_retries)
+            .field("attempts", &self.attempts)
             .field("initial_retry_delay", &self.initial_retry_delay)
             .field("max_retry_delay", &self.max_retry_delay)
-            .finish()
+            .field("retry_jitter", &self.retry_jitter)
+            .field("locale", &self.locale)
+            .field("message_prefix", &self.message_prefix)
+            .field("strict_response_parsing", &self.strict_response_parsing)
+            .field("log_message_content", &self.log_message_content)
+            .field("http2_keep_alive_interval", &self.http2_keep_alive_interval)
+            .field(
+                "http2_keep_alive_while_idle",
+                &self.http2_keep_alive_while_idle,
+            )
+            .field(
+                "on_stream_event",
+                &self.on_stream_event.as_ref().map(|_| "<callback>"),
+            )
+            .field("idempotency_key_header", &self.idempotency_key_header)
+            .field("rate_limit_max_retries", &self.rate_limit_max_retries)
+            .field(
+                "should_retry",
+                &self.should_retry.as_ref().map(|_| "<predicate>"),
+            )
+            .field(
+                "auto_reset_invalid_session",
+                &self.auto_reset_invalid_session,
+            )
+            .field("request_envelope", &self.request_envelope)
+            .field("request_format", &self.request_format)
+            .field("redirect_policy", &self.redirect_policy)
+            .field("adaptive_timeout", &self.adaptive_timeout)
+            .field("retry_stream_start", &self.retry_stream_start)
+            .field("max_request_bytes", &self.max_request_bytes)
+            .field("reject_empty_completions", &self.reject_empty_completions)
+            .field(
+                "reject_filtered_completions",
+                &self.reject_filtered_completions,
+            )
+            .field("reject_empty_messages", &self.reject_empty_messages)
+            .field("otel_semantic_conventions", &self.otel_semantic_conventions)
+            .field(
+                "root_certificate",
+                &self.root_certificate.as_ref().map(Vec::len),
+            );
+        #[cfg(feature = "dangerous-tls")]
+        debug.field(
+            "danger_accept_invalid_certs",
+            &self.danger_accept_invalid_certs,
+        );
+        debug.finish()
     }
 }
 
 impl ChippConfigBuilder {
+    /// Create a builder seeded with every field of an existing configuration.
+    ///
+    /// Useful for layering: load a base configuration once, then override
+    /// only the fields that differ (e.g., per-tenant `api_key` and `model`).
+    /// Fields that aren't overridden keep the base value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chipp::{ChippConfig, ChippConfigBuilder};
+    /// use std::time::Duration;
+    ///
+    /// let base = ChippConfig {
+    ///     timeout: Duration::from_secs(60),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let tenant = ChippConfigBuilder::from_config(base)
+    ///     .api_key("tenant-key")
+    ///     .model("tenant-app")
+    ///     .build()
+    ///     .expect("Invalid config");
+    ///
+    /// assert_eq!(tenant.timeout, Duration::from_secs(60));
+    /// ```
+    #[must_use]
+    pub fn from_config(config: ChippConfig) -> Self {
+        Self {
+            api_key: Some(config.api_key),
+            api_key_env: None,
+            base_url: Some(config.base_url),
+            model: Some(config.model),
+            model_env: None,
+            timeout: Some(config.timeout),
+            max_retries: Some(config.max_retries),
+            attempts: None,
+            initial_retry_delay: Some(config.initial_retry_delay),
+            max_retry_delay: Some(config.max_retry_delay),
+            retry_jitter: Some(config.retry_jitter),
+            locale: config.locale,
+            message_prefix: Some(config.message_prefix),
+            strict_response_parsing: Some(config.strict_response_parsing),
+            log_message_content: Some(config.log_message_content),
+            http2_keep_alive_interval: config.http2_keep_alive_interval,
+            http2_keep_alive_while_idle: Some(config.http2_keep_alive_while_idle),
+            on_stream_event: config.on_stream_event,
+            idempotency_key_header: Some(config.idempotency_key_header),
+            rate_limit_max_retries: config.rate_limit_max_retries,
+            should_retry: config.should_retry,
+            auto_reset_invalid_session: Some(config.auto_reset_invalid_session),
+            request_envelope: Some(config.request_envelope),
+            request_format: Some(config.request_format),
+            redirect_policy: Some(config.redirect_policy),
+            adaptive_timeout: config.adaptive_timeout,
+            retry_stream_start: Some(config.retry_stream_start),
+            max_request_bytes: config.max_request_bytes,
+            reject_empty_completions: Some(config.reject_empty_completions),
+            reject_filtered_completions: Some(config.reject_filtered_completions),
+            reject_empty_messages: Some(config.reject_empty_messages),
+            otel_semantic_conventions: Some(config.otel_semantic_conventions),
+            root_certificate: config.root_certificate,
+            #[cfg(feature = "dangerous-tls")]
+            danger_accept_invalid_certs: Some(config.danger_accept_invalid_certs),
+        }
+    }
+
     /// Set the API key (required).
     #[must_use]
-    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+    pub fn api_key(mut self, api_key: impl Into<ApiKey>) -> Self {
         self.api_key = Some(api_key.into());
+        self.api_key_env = None;
+        self
+    }
+
+    /// Read the API key from the environment variable `var_name` at
+    /// [`build()`](Self::build) time.
+    ///
+    /// Replaces any key set with [`api_key()`](Self::api_key), and vice versa.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use chipp::ChippConfig;
+    ///
+    /// let config = ChippConfig::builder()
+    ///     .api_key_from_env("CHIPP_API_KEY")
+    ///     .model("myapp-123")
+    ///     .build()
+    ///     .expect("CHIPP_API_KEY must be set");
+    /// ```
+    #[must_use]
+    pub fn api_key_from_env(mut self, var_name: &str) -> Self {
+        self.api_key_env = Some(var_name.to_string());
+        self.api_key = None;
         self
     }
 
     /// Set the model/app name ID (required).
     #[must_use]
-    pub fn model(mut self, model: impl Into<String>) -> Self {
+    pub fn model(mut self, model: impl Into<AppId>) -> Self {
         self.model = Some(model.into());
+        self.model_env = None;
+        self
+    }
+
+    /// Read the model/app name ID from the environment variable `var_name` at
+    /// [`build()`](Self::build) time.
+    ///
+    /// Replaces any ID set with [`model()`](Self::model), and vice versa.
+    #[must_use]
+    pub fn model_from_env(mut self, var_name: &str) -> Self {
+        self.model_env = Some(var_name.to_string());
+        self.model = None;
         self
     }
 
@@ -162,10 +1258,41 @@ impl ChippConfigBuilder {
         self
     }
 
-    /// Set the maximum retry attempts (default: 3).
+    /// Set the maximum number of retries after the first attempt (default: 3).
+    ///
+    /// `max_retries(0)` makes exactly one request. Replaces any value set with
+    /// [`attempts()`](Self::attempts), and vice versa.
     #[must_use]
     pub fn max_retries(mut self, max_retries: usize) -> Self {
         self.max_retries = Some(max_retries);
+        self.attempts = None;
+        self
+    }
+
+    /// Set the total number of requests made per call, first attempt included.
+    ///
+    /// `attempts(1)` never retries; `attempts(n)` is `max_retries(n - 1)`.
+    /// Must be at least 1, checked at [`build()`](Self::build). Replaces any
+    /// value set with [`max_retries()`](Self::max_retries), and vice versa.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chipp::ChippConfig;
+    ///
+    /// let config = ChippConfig::builder()
+    ///     .api_key("YOUR_API_KEY_HERE")
+    ///     .model("myapp-123")
+    ///     .attempts(1)
+    ///     .build()?;
+    /// assert_eq!(config.max_retries, 0);
+    /// assert_eq!(config.attempts(), 1);
+    /// # Ok::<(), chipp::ChippClientError>(())
+    /// ```
+    #[must_use]
+    pub fn attempts(mut self, attempts: usize) -> Self {
+        self.attempts = Some(attempts);
+        self.max_retries = None;
         self
     }
 
@@ -183,31 +1310,561 @@ impl ChippConfigBuilder {
         self
     }
 
+    /// Set the random spread applied to retry delays, as a fraction (default: 0.3).
+    ///
+    /// `0.0` disables jitter, which makes retry timing exact.
+    #[must_use]
+    pub fn retry_jitter(mut self, jitter: f64) -> Self {
+        self.retry_jitter = Some(jitter);
+        self
+    }
+
+    /// Set `max_retries`, `initial_retry_delay`, `max_retry_delay` and
+    /// `retry_jitter` together.
+    ///
+    /// Later calls to the individual setters still override single values.
+    #[must_use]
+    pub fn retry(mut self, retry: RetryConfig) -> Self {
+        self.max_retries = Some(retry.max_retries);
+        self.attempts = None;
+        self.initial_retry_delay = Some(retry.initial_delay);
+        self.max_retry_delay = Some(retry.max_delay);
+        self.retry_jitter = Some(retry.jitter);
+        self
+    }
+
+    /// Set the locale sent as the `Accept-Language` header (e.g., "en-US").
+    #[must_use]
+    pub fn locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Set messages prepended to every request, such as few-shot examples (default: empty).
+    ///
+    /// The prefix is inserted after any leading system messages in the caller's
+    /// messages and before the rest of the conversation.
+    #[must_use]
+    pub fn message_prefix(mut self, messages: Vec<ChippMessage>) -> Self {
+        self.message_prefix = Some(messages);
+        self
+    }
+
+    /// Reject responses containing unknown fields (default: false).
+    #[must_use]
+    pub fn strict_response_parsing(mut self, strict: bool) -> Self {
+        self.strict_response_parsing = Some(strict);
+        self
+    }
+
+    /// Log message content at debug level (default: false).
+    #[must_use]
+    pub fn log_message_content(mut self, enabled: bool) -> Self {
+        self.log_message_content = Some(enabled);
+        self
+    }
+
+    /// Set the interval between HTTP/2 keep-alive pings (default: disabled).
+    ///
+    /// Helps streaming reliability on flaky networks by detecting dead connections early.
+    #[must_use]
+    pub fn http2_keep_alive_interval(mut self, interval: Duration) -> Self {
+        self.http2_keep_alive_interval = Some(interval);
+        self
+    }
+
+    /// Send HTTP/2 keep-alive pings even when idle (default: false).
+    #[must_use]
+    pub fn http2_keep_alive_while_idle(mut self, enabled: bool) -> Self {
+        self.http2_keep_alive_while_idle = Some(enabled);
+        self
+    }
+
+    /// Set an observer invoked for every parsed streaming event (default: none).
+    #[must_use]
+    pub fn on_stream_event(
+        mut self,
+        callback: impl Fn(&StreamEvent) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_stream_event = Some(Arc::new(callback));
+        self
+    }
+
+    /// Set the header used to send the idempotency key (default: `Idempotency-Key`).
+    #[must_use]
+    pub fn idempotency_key_header(mut self, header: impl Into<String>) -> Self {
+        self.idempotency_key_header = Some(Some(header.into()));
+        self
+    }
+
+    /// Stop sending an idempotency key with requests.
+    #[must_use]
+    pub fn no_idempotency_key(mut self) -> Self {
+        self.idempotency_key_header = Some(None);
+        self
+    }
+
+    /// Set a separate retry budget for 429 responses (default: none).
+    #[must_use]
+    pub fn rate_limit_max_retries(mut self, max_retries: usize) -> Self {
+        self.rate_limit_max_retries = Some(max_retries);
+        self
+    }
+
+    /// Set a predicate that decides which failures are retried (default: built-in rule).
+    #[must_use]
+    pub fn should_retry(
+        mut self,
+        predicate: impl Fn(&ChippClientError) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.should_retry = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Start a new session when the server no longer knows the session ID (default: false).
+    #[must_use]
+    pub fn auto_reset_invalid_session(mut self, enabled: bool) -> Self {
+        self.auto_reset_invalid_session = Some(enabled);
+        self
+    }
+
+    /// Set the shape of the request body (default: [`RequestEnvelope::Standard`]).
+    #[must_use]
+    pub fn request_envelope(mut self, envelope: RequestEnvelope) -> Self {
+        self.request_envelope = Some(envelope);
+        self
+    }
+
+    /// Set how the conversation is sent (default: [`RequestFormat::Messages`]).
+    #[must_use]
+    pub fn request_format(mut self, format: RequestFormat) -> Self {
+        self.request_format = Some(format);
+        self
+    }
+
+    /// Set how redirects are followed (default: `RedirectPolicy::Limited(10)`).
+    #[must_use]
+    pub fn redirect_policy(mut self, policy: RedirectPolicy) -> Self {
+        self.redirect_policy = Some(policy);
+        self
+    }
+
+    /// Derive request timeouts from recent response times (default: none).
+    #[must_use]
+    pub fn adaptive_timeout(mut self, adaptive: AdaptiveTimeout) -> Self {
+        self.adaptive_timeout = Some(adaptive);
+        self
+    }
+
+    /// Retry streams that fail before their first text chunk (default: false).
+    #[must_use]
+    pub fn retry_stream_start(mut self, enabled: bool) -> Self {
+        self.retry_stream_start = Some(enabled);
+        self
+    }
+
+    /// Reject request bodies larger than `max_bytes` before sending (default: no limit).
+    #[must_use]
+    pub fn max_request_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_request_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Fail blank completions that finished with `"stop"` (default: false).
+    #[must_use]
+    pub fn reject_empty_completions(mut self, enabled: bool) -> Self {
+        self.reject_empty_completions = Some(enabled);
+        self
+    }
+
+    /// Fail completions blocked by the content filter (default: false).
+    #[must_use]
+    pub fn reject_filtered_completions(mut self, enabled: bool) -> Self {
+        self.reject_filtered_completions = Some(enabled);
+        self
+    }
+
+    /// Fail requests containing a blank user message before sending (default: true).
+    #[must_use]
+    pub fn reject_empty_messages(mut self, enabled: bool) -> Self {
+        self.reject_empty_messages = Some(enabled);
+        self
+    }
+
+    /// Record OpenTelemetry GenAI attributes on request spans (default: false).
+    #[must_use]
+    pub fn otel_semantic_conventions(mut self, enabled: bool) -> Self {
+        self.otel_semantic_conventions = Some(enabled);
+        self
+    }
+
+    /// Trust an extra root certificate, PEM or DER encoded, alongside the system roots.
+    #[must_use]
+    pub fn root_certificate(mut self, certificate: impl Into<Vec<u8>>) -> Self {
+        self.root_certificate = Some(certificate.into());
+        self
+    }
+
+    /// Accept invalid TLS certificates (default: false). **Development only.**
+    ///
+    /// See [`ChippConfig::danger_accept_invalid_certs`] for the risks.
+    #[cfg(feature = "dangerous-tls")]
+    #[must_use]
+    pub fn danger_accept_invalid_certs(mut self, enabled: bool) -> Self {
+        self.danger_accept_invalid_certs = Some(enabled);
+        self
+    }
+
     /// Build the configuration.
     ///
     /// # Errors
     ///
-    /// Returns `ConfigError` if required fields (`api_key`, `model`) are missing.
+    /// Returns `ConfigError` if required fields (`api_key`, `model`) are missing,
+    /// if an environment variable they should be read from is not set, if
+    /// `locale` is not a plausible language tag, if `idempotency_key_header`
+    /// is not a valid header name, if `timeout` or `adaptive_timeout` could
+    /// never allow a request to complete, or if `initial_retry_delay` exceeds
+    /// `max_retry_delay`.
     pub fn build(self) -> Result<ChippConfig, ChippClientError> {
-        let api_key = self
-            .api_key
-            .ok_or_else(|| ChippClientError::ConfigError("api_key is required".to_string()))?;
-        let model = self
-            .model
-            .ok_or_else(|| ChippClientError::ConfigError("model is required".to_string()))?;
+        let api_key = match &self.api_key_env {
+            Some(var_name) => Some(ApiKey::from(read_env(var_name)?)),
+            None => self.api_key,
+        }
+        .ok_or_else(|| ChippClientError::ConfigError("api_key is required".to_string()))?;
+        let model = match &self.model_env {
+            Some(var_name) => Some(AppId::from(read_env(var_name)?)),
+            None => self.model,
+        }
+        .ok_or_else(|| ChippClientError::ConfigError("model is required".to_string()))?;
+
+        if let Some(locale) = &self.locale {
+            validate_locale(locale)?;
+        }
+
+        if let Some(timeout) = self.timeout {
+            validate_timeout(timeout)?;
+        }
+
+        if let Some(adaptive) = &self.adaptive_timeout {
+            validate_adaptive_timeout(adaptive)?;
+        }
+
+        if let Some(jitter) = self.retry_jitter {
+            validate_retry_jitter(jitter)?;
+        }
+
+        let max_retries = match self.attempts {
+            Some(0) => {
+                return Err(ChippClientError::ConfigError(
+                    "attempts must be at least 1".to_string(),
+                ))
+            }
+            Some(attempts) => Some(attempts - 1),
+            None => self.max_retries,
+        };
+
+        if let Some(Some(header)) = &self.idempotency_key_header {
+            validate_idempotency_key_header(header)?;
+        }
 
         let defaults = ChippConfig::default();
+        let initial_retry_delay = self
+            .initial_retry_delay
+            .unwrap_or(defaults.initial_retry_delay);
+        let max_retry_delay = self.max_retry_delay.unwrap_or(defaults.max_retry_delay);
+        validate_retry_delays(initial_retry_delay, max_retry_delay)?;
 
         Ok(ChippConfig {
             api_key,
             model,
-            base_url: self.base_url.unwrap_or(defaults.base_url),
+            base_url: normalize_base_url(self.base_url.unwrap_or(defaults.base_url)),
             timeout: self.timeout.unwrap_or(defaults.timeout),
-            max_retries: self.max_retries.unwrap_or(defaults.max_retries),
-            initial_retry_delay: self
-                .initial_retry_delay
-                .unwrap_or(defaults.initial_retry_delay),
-            max_retry_delay: self.max_retry_delay.unwrap_or(defaults.max_retry_delay),
+            max_retries: max_retries.unwrap_or(defaults.max_retries),
+            initial_retry_delay,
+            max_retry_delay,
+            retry_jitter: self.retry_jitter.unwrap_or(defaults.retry_jitter),
+            locale: self.locale,
+            message_prefix: self.message_prefix.unwrap_or(defaults.message_prefix),
+            strict_response_parsing: self
+                .strict_response_parsing
+                .unwrap_or(defaults.strict_response_parsing),
+            log_message_content: self
+                .log_message_content
+                .unwrap_or(defaults.log_message_content),
+            http2_keep_alive_interval: self.http2_keep_alive_interval,
+            http2_keep_alive_while_idle: self
+                .http2_keep_alive_while_idle
+                .unwrap_or(defaults.http2_keep_alive_while_idle),
+            on_stream_event: self.on_stream_event,
+            idempotency_key_header: self
+                .idempotency_key_header
+                .unwrap_or(defaults.idempotency_key_header),
+            rate_limit_max_retries: self.rate_limit_max_retries,
+            should_retry: self.should_retry,
+            auto_reset_invalid_session: self
+                .auto_reset_invalid_session
+                .unwrap_or(defaults.auto_reset_invalid_session),
+            request_envelope: self.request_envelope.unwrap_or(defaults.request_envelope),
+            request_format: self.request_format.unwrap_or(defaults.request_format),
+            redirect_policy: self.redirect_policy.unwrap_or(defaults.redirect_policy),
+            adaptive_timeout: self.adaptive_timeout,
+            retry_stream_start: self
+                .retry_stream_start
+                .unwrap_or(defaults.retry_stream_start),
+            max_request_bytes: self.max_request_bytes,
+            reject_empty_completions: self
+                .reject_empty_completions
+                .unwrap_or(defaults.reject_empty_completions),
+            reject_filtered_completions: self
+                .reject_filtered_completions
+                .unwrap_or(defaults.reject_filtered_completions),
+            reject_empty_messages: self
+                .reject_empty_messages
+                .unwrap_or(defaults.reject_empty_messages),
+            otel_semantic_conventions: self
+                .otel_semantic_conventions
+                .unwrap_or(defaults.otel_semantic_conventions),
+            root_certificate: self.root_certificate,
+            #[cfg(feature = "dangerous-tls")]
+            danger_accept_invalid_certs: self
+                .danger_accept_invalid_certs
+                .unwrap_or(defaults.danger_accept_invalid_certs),
         })
     }
 }
+
+/// Marker types tracking which required fields a [`TypedConfigBuilder`] has.
+pub mod typestate {
+    /// The field has not been set yet.
+    #[derive(Debug, Clone, Copy)]
+    pub struct Missing;
+
+    /// The field has been set.
+    #[derive(Debug, Clone, Copy)]
+    pub struct Present;
+}
+
+/// Builder for [`ChippConfig`] that checks the required fields at compile time.
+///
+/// Created with [`ChippConfig::typed_builder()`]. The type parameters record
+/// whether `api_key` and `model` have been set, and `build()` only exists
+/// once both have, so forgetting one is a compile error rather than a runtime
+/// `ConfigError`. Other settings are applied to the wrapped
+/// [`ChippConfigBuilder`] through [`configure()`](Self::configure).
+///
+/// # Example
+///
+/// ```
+/// use chipp::ChippConfig;
+/// use std::time::Duration;
+///
+/// let config = ChippConfig::typed_builder()
+///     .api_key("YOUR_API_KEY_HERE")
+///     .model("myapp-123")
+///     .configure(|builder| builder.timeout(Duration::from_secs(60)))
+///     .build()
+///     .expect("Invalid config");
+/// ```
+///
+/// Leaving out a required field doesn't compile:
+///
+/// ```compile_fail
+/// use chipp::ChippConfig;
+///
+/// let config = ChippConfig::typed_builder().model("myapp-123").build();
+/// ```
+#[derive(Debug)]
+pub struct TypedConfigBuilder<Key, Model> {
+    inner: ChippConfigBuilder,
+    state: PhantomData<(Key, Model)>,
+}
+
+impl<Key, Model> TypedConfigBuilder<Key, Model> {
+    /// Set the API key.
+    #[must_use]
+    pub fn api_key(
+        self,
+        api_key: impl Into<ApiKey>,
+    ) -> TypedConfigBuilder<typestate::Present, Model> {
+        TypedConfigBuilder {
+            inner: self.inner.api_key(api_key),
+            state: PhantomData,
+        }
+    }
+
+    /// Set the model/app name ID.
+    #[must_use]
+    pub fn model(self, model: impl Into<AppId>) -> TypedConfigBuilder<Key, typestate::Present> {
+        TypedConfigBuilder {
+            inner: self.inner.model(model),
+            state: PhantomData,
+        }
+    }
+
+    /// Apply optional settings with the regular [`ChippConfigBuilder`] setters.
+    ///
+    /// Set `api_key` and `model` with this builder's own setters: values
+    /// changed here (e.g. with `api_key_from_env`) are only checked at
+    /// [`build()`](TypedConfigBuilder::build) time.
+    #[must_use]
+    pub fn configure(self, f: impl FnOnce(ChippConfigBuilder) -> ChippConfigBuilder) -> Self {
+        Self {
+            inner: f(self.inner),
+            state: PhantomData,
+        }
+    }
+}
+
+impl TypedConfigBuilder<typestate::Present, typestate::Present> {
+    /// Build the configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError` for the same invalid settings as
+    /// [`ChippConfigBuilder::build()`], other than missing required fields.
+    pub fn build(self) -> Result<ChippConfig, ChippClientError> {
+        self.inner.build()
+    }
+}
+
+/// Read a required environment variable for the builder.
+fn read_env(var_name: &str) -> Result<String, ChippClientError> {
+    std::env::var(var_name).map_err(|e| {
+        let reason = match e {
+            std::env::VarError::NotPresent => "is not set",
+            std::env::VarError::NotUnicode(_) => "is not valid Unicode",
+        };
+        ChippClientError::ConfigError(format!("environment variable {} {}", var_name, reason))
+    })
+}
+
+/// Check that an adaptive timeout can produce usable timeouts.
+pub(crate) fn validate_adaptive_timeout(
+    adaptive: &AdaptiveTimeout,
+) -> Result<(), ChippClientError> {
+    if !(adaptive.factor.is_finite() && adaptive.factor > 0.0) {
+        return Err(ChippClientError::ConfigError(format!(
+            "adaptive timeout factor must be finite and positive, got {}",
+            adaptive.factor
+        )));
+    }
+    if adaptive.window == 0 {
+        return Err(ChippClientError::ConfigError(
+            "adaptive timeout window must be greater than zero".to_string(),
+        ));
+    }
+    if adaptive.min.is_zero() {
+        return Err(ChippClientError::ConfigError(
+            "adaptive timeout min must be greater than zero".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Remove a single trailing slash so endpoint paths can be appended with `/`.
+///
+/// `https://app.chipp.ai/api/v1/` would otherwise produce
+/// `.../api/v1//chat/completions`, which some servers answer with `404`.
+pub(crate) fn normalize_base_url(mut base_url: String) -> String {
+    if base_url.ends_with('/') {
+        base_url.pop();
+    }
+    base_url
+}
+
+/// Check that a request timeout is usable.
+///
+/// A zero timeout would fail every request before it is sent, which is always
+/// a configuration mistake rather than a network condition.
+pub(crate) fn validate_timeout(timeout: Duration) -> Result<(), ChippClientError> {
+    if timeout.is_zero() {
+        return Err(ChippClientError::ConfigError(
+            "timeout must be greater than zero".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Check that a retry jitter fraction is usable.
+pub(crate) fn validate_retry_jitter(jitter: f64) -> Result<(), ChippClientError> {
+    if !(0.0..=1.0).contains(&jitter) {
+        return Err(ChippClientError::ConfigError(format!(
+            "retry_jitter must be between 0.0 and 1.0, got {}",
+            jitter
+        )));
+    }
+    Ok(())
+}
+
+/// Check that the first retry delay does not exceed the cap on retry delays.
+///
+/// The cap would silently shorten every delay, so the initial one is never
+/// what was configured.
+pub(crate) fn validate_retry_delays(
+    initial: Duration,
+    max: Duration,
+) -> Result<(), ChippClientError> {
+    if initial > max {
+        return Err(ChippClientError::ConfigError(format!(
+            "initial_retry_delay ({:?}) must not exceed max_retry_delay ({:?})",
+            initial, max
+        )));
+    }
+    Ok(())
+}
+
+/// Parse `root_certificate` bytes into certificates, as PEM if they look like PEM.
+///
+/// A PEM bundle yields one certificate per block; anything else is read as a
+/// single DER certificate.
+pub(crate) fn parse_root_certificates(
+    bytes: &[u8],
+) -> Result<Vec<reqwest::Certificate>, ChippClientError> {
+    let invalid = |reason: String| {
+        ChippClientError::ConfigError(format!("invalid root_certificate: {}", reason))
+    };
+
+    if bytes.trim_ascii_start().starts_with(b"-----BEGIN") {
+        let certificates =
+            reqwest::Certificate::from_pem_bundle(bytes).map_err(|e| invalid(e.to_string()))?;
+        if certificates.is_empty() {
+            return Err(invalid("no certificate found in PEM data".to_string()));
+        }
+        Ok(certificates)
+    } else {
+        let certificate =
+            reqwest::Certificate::from_der(bytes).map_err(|e| invalid(e.to_string()))?;
+        Ok(vec![certificate])
+    }
+}
+
+/// Check that an idempotency key header name can be sent.
+pub(crate) fn validate_idempotency_key_header(header: &str) -> Result<(), ChippClientError> {
+    if reqwest::header::HeaderName::from_bytes(header.as_bytes()).is_err() {
+        return Err(ChippClientError::ConfigError(format!(
+            "idempotency_key_header {:?} is not a valid header name",
+            header
+        )));
+    }
+    Ok(())
+}
+
+/// Check that a locale looks like a language tag (e.g., "en", "pt-BR").
+///
+/// This is a plausibility check, not full BCP 47 validation: the tag must be
+/// non-empty and consist only of ASCII letters, digits, and hyphens.
+pub(crate) fn validate_locale(locale: &str) -> Result<(), ChippClientError> {
+    let plausible = !locale.is_empty()
+        && locale
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-');
+
+    if plausible {
+        Ok(())
+    } else {
+        Err(ChippClientError::ConfigError(format!(
+            "locale {:?} is not a valid language tag",
+            locale
+        )))
+    }
+}
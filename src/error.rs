@@ -1,7 +1,12 @@
 //! Error types for the Chipp API client.
 
+use std::borrow::Cow;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
+/// Longest `ApiError` message, in characters, shown by `Display`.
+const MAX_DISPLAYED_MESSAGE_CHARS: usize = 500;
+
 /// Errors that can occur when using the Chipp API client.
 #[derive(Error, Debug)]
 pub enum ChippClientError {
@@ -13,8 +18,22 @@ pub enum ChippClientError {
     #[error("Invalid API response: {0}")]
     InvalidResponse(String),
 
+    /// API response body ended before the JSON was complete (retryable)
+    ///
+    /// Usually a transfer cut short by the network rather than a schema mismatch.
+    #[error("Truncated API response: {0}")]
+    TruncatedResponse(String),
+
+    /// API returned a success status with an empty body (retryable)
+    #[error("Empty response body")]
+    EmptyResponse,
+
     /// API returned an error response (4xx, 5xx)
-    #[error("API returned error: {status} - {message}")]
+    ///
+    /// `Display` shortens messages over 500 characters, since some error pages
+    /// are full HTML or stack-trace dumps; see
+    /// [`full_message()`](ChippClientError::full_message) for the complete text.
+    #[error("API returned error: {status} - {}", truncate_message(.message))]
     ApiError {
         /// HTTP status code
         status: u16,
@@ -27,12 +46,201 @@ pub enum ChippClientError {
     StreamError(String),
 
     /// Maximum retry attempts exceeded
-    #[error("Maximum retry attempts ({0}) exceeded")]
-    MaxRetriesExceeded(usize),
+    #[error(
+        "Maximum retry attempts exceeded after {attempts} attempts: {}",
+        .errors.join("; ")
+    )]
+    MaxRetriesExceeded {
+        /// Number of attempts made, including the first
+        attempts: usize,
+        /// Error from each failed attempt, oldest first (e.g. "attempt 1: ...")
+        errors: Vec<String>,
+    },
 
     /// Configuration validation error
     #[error("Configuration error: {0}")]
     ConfigError(String),
+
+    /// Request body is larger than `max_request_bytes`; nothing was sent
+    #[error("Request body of {size} bytes exceeds max_request_bytes ({limit})")]
+    RequestTooLarge {
+        /// Size of the serialized request body in bytes
+        size: usize,
+        /// Configured limit in bytes
+        limit: usize,
+    },
+
+    /// Streamed response did not finish within the total time allowed
+    ///
+    /// Returned by `chat_stream_collect_timeout`. Calls given a deadline (the
+    /// `_until` methods) fail with [`DeadlineExceeded`](Self::DeadlineExceeded)
+    /// instead, since they have an `Instant` rather than a time limit to
+    /// report; match both to handle every missed time bound.
+    #[error("Stream did not complete within {limit:?}")]
+    Timeout {
+        /// Total time allowed for the stream
+        limit: Duration,
+        /// Text received before the deadline
+        partial: String,
+    },
+
+    /// Request did not finish by the caller's deadline
+    ///
+    /// Returned by the `_until` methods in place of [`Timeout`](Self::Timeout),
+    /// including when the deadline had already passed and nothing was sent.
+    #[error("Request did not complete by its deadline")]
+    DeadlineExceeded {
+        /// Deadline the call was given
+        deadline: Instant,
+        /// Text received before the deadline (always empty for non-streaming requests)
+        partial: String,
+    },
+
+    /// Request was cancelled before it completed
+    #[error("Request cancelled")]
+    Cancelled,
+
+    /// The reply was blocked by the content filter
+    ///
+    /// Only returned when `reject_filtered_completions` is enabled.
+    #[error("Response blocked by content filter")]
+    ContentFiltered {
+        /// Text returned alongside the block (often empty or a canned notice)
+        message: String,
+    },
+}
+
+impl ChippClientError {
+    /// Get the complete error body of an [`ApiError`](Self::ApiError).
+    ///
+    /// Unlike the `Display` output, this is never truncated. Returns `None` for
+    /// other variants.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chipp::ChippClientError;
+    ///
+    /// let error = ChippClientError::ApiError {
+    ///     status: 500,
+    ///     message: "x".repeat(10_000),
+    /// };
+    ///
+    /// assert!(error.to_string().len() < 600);
+    /// assert_eq!(error.full_message().map(str::len), Some(10_000));
+    /// ```
+    #[must_use]
+    pub fn full_message(&self) -> Option<&str> {
+        match self {
+            Self::ApiError { message, .. } => Some(message),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the error came from failing to connect to the API.
+    ///
+    /// Always `false` for variants other than [`HttpError`](Self::HttpError).
+    #[must_use]
+    pub fn is_connect(&self) -> bool {
+        matches!(self, Self::HttpError(e) if e.is_connect())
+    }
+
+    /// Returns `true` if the HTTP request timed out.
+    ///
+    /// Always `false` for variants other than [`HttpError`](Self::HttpError).
+    #[must_use]
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, Self::HttpError(e) if e.is_timeout())
+    }
+
+    /// Returns `true` if the error occurred while reading the response body.
+    ///
+    /// Always `false` for variants other than [`HttpError`](Self::HttpError).
+    #[must_use]
+    pub fn is_body(&self) -> bool {
+        matches!(self, Self::HttpError(e) if e.is_body())
+    }
+
+    /// Returns `true` if the response body could not be decoded.
+    ///
+    /// Always `false` for variants other than [`HttpError`](Self::HttpError).
+    #[must_use]
+    pub fn is_decode(&self) -> bool {
+        matches!(self, Self::HttpError(e) if e.is_decode())
+    }
+
+    /// Convert into a [`SharedChippError`] that can be cloned and sent to many consumers.
+    ///
+    /// The underlying `reqwest::Error` of an [`HttpError`](Self::HttpError) is
+    /// flattened into its message.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chipp::ChippClientError;
+    ///
+    /// let error = ChippClientError::ApiError {
+    ///     status: 503,
+    ///     message: "Service Unavailable".to_string(),
+    /// };
+    /// let shared = error.to_shared();
+    ///
+    /// assert_eq!(shared.status, Some(503));
+    /// let copy = shared.clone();
+    /// assert_eq!(copy, shared);
+    /// ```
+    #[must_use]
+    pub fn to_shared(&self) -> SharedChippError {
+        let (kind, status) = match self {
+            Self::HttpError(e) => ("HttpError", e.status().map(|s| s.as_u16())),
+            Self::InvalidResponse(_) => ("InvalidResponse", None),
+            Self::TruncatedResponse(_) => ("TruncatedResponse", None),
+            Self::EmptyResponse => ("EmptyResponse", None),
+            Self::ApiError { status, .. } => ("ApiError", Some(*status)),
+            Self::StreamError(_) => ("StreamError", None),
+            Self::MaxRetriesExceeded { .. } => ("MaxRetriesExceeded", None),
+            Self::ConfigError(_) => ("ConfigError", None),
+            Self::RequestTooLarge { .. } => ("RequestTooLarge", None),
+            Self::Timeout { .. } => ("Timeout", None),
+            Self::DeadlineExceeded { .. } => ("DeadlineExceeded", None),
+            Self::Cancelled => ("Cancelled", None),
+            Self::ContentFiltered { .. } => ("ContentFiltered", None),
+        };
+
+        SharedChippError {
+            kind,
+            message: self.to_string(),
+            status,
+        }
+    }
+}
+
+/// A cloneable snapshot of a [`ChippClientError`].
+///
+/// `ChippClientError` can't be `Clone` because `reqwest::Error` isn't. Use
+/// [`ChippClientError::to_shared()`] to fan a failure out to several
+/// consumers, e.g. through a broadcast channel.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{message}")]
+pub struct SharedChippError {
+    /// Name of the original variant (e.g. `"ApiError"`)
+    pub kind: &'static str,
+    /// Display text of the original error
+    pub message: String,
+    /// HTTP status code, if the error carried one
+    pub status: Option<u16>,
+}
+
+/// Shorten an API error message for display, noting the full length when cut.
+fn truncate_message(message: &str) -> Cow<'_, str> {
+    match message.char_indices().nth(MAX_DISPLAYED_MESSAGE_CHARS) {
+        Some((cut, _)) => Cow::Owned(format!(
+            "{}… ({} bytes total)",
+            &message[..cut],
+            message.len()
+        )),
+        None => Cow::Borrowed(message),
+    }
 }
 
 /// Result type alias for Chipp operations.
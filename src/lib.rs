@@ -7,10 +7,11 @@
 //!
 //! - **Non-streaming chat**: Simple request/response with `chat()`
 //! - **Streaming chat**: Server-Sent Events (SSE) with `chat_stream()`
+//! - **Structured events**: Text, reasoning, and metadata events with `chat_stream_events()`
 //! - **Session management**: Automatic `chatSessionId` tracking for conversation continuity
 //! - **Retry logic**: Exponential backoff for transient failures (5xx, network errors)
 //! - **Configurable timeouts**: Per-request timeout configuration
-//! - **Correlation IDs**: Automatic UUID generation for request tracing
+//! - **Correlation IDs**: Automatic UUID generation for request tracing, plus per-attempt IDs
 //!
 //! # API Reference
 //!
@@ -66,12 +67,32 @@
 mod client;
 mod config;
 mod error;
+mod request;
+mod session_store;
 mod stream;
 mod types;
 
+#[cfg(feature = "test-util")]
+pub mod test_util;
+
 // Re-export public API
 pub use client::ChippClient;
-pub use config::{ChippConfig, ChippConfigBuilder};
-pub use error::{ChippClientError, Result};
-pub use stream::ChippStream;
-pub use types::{ChatResponse, ChippMessage, ChippSession, MessageRole, Usage};
+pub use config::{
+    typestate, AdaptiveTimeout, ApiKey, AppId, ChippConfig, ChippConfigBuilder, RedirectPolicy,
+    RequestEnvelope, RequestFormat, RetryConfig, RetryPredicate, TypedConfigBuilder,
+};
+pub use error::{ChippClientError, Result, SharedChippError};
+pub use request::{ChatRequest, RequestOptions};
+pub use session_store::{InMemorySessionStore, SessionStore};
+pub use stream::{
+    parse_sse_line, replay_sse_payloads, BufferedChippStream, ChippEventStream, ChippStream,
+    ChippUsageStream, PartialResponse, RawChippStream, SentenceStream, SharedBuffer, StreamEvent,
+    StreamEventCallback, StreamHandle, TimedChippStream,
+};
+pub use types::{
+    truncate_messages, ChatResponse, ChippMessage, ChippSession, CostModel, FinishReason,
+    MessageRole, ResponseHeaders, Transcript, TranscriptTurn, Usage,
+};
+
+/// Re-exported for [`ChippClient::chat_detailed_cancellable`].
+pub use tokio_util::sync::CancellationToken;
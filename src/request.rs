@@ -0,0 +1,122 @@
+//! Per-request options and reusable requests for the Chipp API client.
+
+use crate::config::AppId;
+use crate::types::ChippMessage;
+use std::time::Duration;
+
+/// Options that override the client configuration for a single request.
+///
+/// Every field is optional; unset fields inherit from [`ChippConfig`](crate::ChippConfig)
+/// or, for the generation parameters, are left to the server. Pass them to
+/// [`ChippClient::chat_detailed_with()`](crate::ChippClient::chat_detailed_with)
+/// or attach them to a [`ChatRequest`] instead of reaching for a separate
+/// method per override.
+///
+/// # Example
+///
+/// ```
+/// use chipp::RequestOptions;
+/// use std::time::Duration;
+///
+/// let options = RequestOptions {
+///     locale: Some("fr-FR".to_string()),
+///     timeout: Some(Duration::from_secs(120)),
+///     max_retries: Some(0),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+    /// Locale sent as the `Accept-Language` header, overriding `ChippConfig::locale`
+    pub locale: Option<String>,
+
+    /// Chipp appNameId to use instead of `ChippConfig::model`
+    pub model: Option<AppId>,
+
+    /// Timeout for each attempt, overriding `ChippConfig::timeout` and `adaptive_timeout`
+    pub timeout: Option<Duration>,
+
+    /// Maximum retries after the first attempt, overriding `ChippConfig::max_retries`
+    pub max_retries: Option<usize>,
+
+    /// Extra HTTP headers sent with every attempt, as `(name, value)` pairs
+    ///
+    /// These win over the client's own headers: a name such as `Authorization`
+    /// or `X-Correlation-ID` replaces the built-in value instead of being sent
+    /// twice.
+    pub headers: Option<Vec<(String, String)>>,
+
+    /// Sampling temperature sent as `temperature` in the request body
+    pub temperature: Option<f32>,
+
+    /// Completion token limit sent as `max_tokens` in the request body
+    pub max_tokens: Option<u32>,
+
+    /// Correlation ID sent as `X-Correlation-ID` instead of a generated one
+    ///
+    /// Lets a caller tie the request to its own trace or request ID. It is
+    /// not used as the idempotency key, which stays unique per call.
+    pub correlation_id: Option<String>,
+}
+
+/// A chat request that can be built once, inspected, and sent repeatedly.
+///
+/// Send it with [`ChippClient::send()`](crate::ChippClient::send). The
+/// convenience methods such as [`ChippClient::chat()`](crate::ChippClient::chat)
+/// cover the common case; use `ChatRequest` when the request itself needs to be
+/// kept around.
+///
+/// # Example
+///
+/// ```
+/// use chipp::{AppId, ChatRequest, ChippMessage};
+///
+/// let request = ChatRequest::new(vec![
+///     ChippMessage::system("Answer in one sentence."),
+///     ChippMessage::user("What is Rust?"),
+/// ])
+/// .with_model("myapp-123");
+///
+/// assert_eq!(request.messages.len(), 2);
+/// assert_eq!(request.options.model.as_ref().map(AppId::as_str), Some("myapp-123"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ChatRequest {
+    /// Messages to send
+    pub messages: Vec<ChippMessage>,
+
+    /// Per-request overrides of the client configuration
+    pub options: RequestOptions,
+}
+
+impl ChatRequest {
+    /// Create a request for the given messages using the client's defaults.
+    #[must_use]
+    pub fn new(messages: impl Into<Vec<ChippMessage>>) -> Self {
+        Self {
+            messages: messages.into(),
+            options: RequestOptions::default(),
+        }
+    }
+
+    /// Use a different Chipp appNameId than the client's configured model.
+    #[must_use]
+    pub fn with_model(mut self, model: impl Into<AppId>) -> Self {
+        self.options.model = Some(model.into());
+        self
+    }
+
+    /// Give each attempt of this request a different timeout than the client's.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.options.timeout = Some(timeout);
+        self
+    }
+
+    /// Replace the request's per-request options.
+    #[must_use]
+    pub fn with_options(mut self, options: RequestOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
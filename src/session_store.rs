@@ -0,0 +1,110 @@
+//! Shared storage for chat session IDs, keyed by the caller.
+
+use futures::future::BoxFuture;
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+/// Storage for `chatSessionId`s used by [`ChippClient::chat_with_session_store()`].
+///
+/// Implement this when sessions live in shared state (a map keyed by user ID,
+/// a cache, a database) rather than in a [`ChippSession`](crate::ChippSession)
+/// you can borrow mutably across `.await` points. Both methods return boxed
+/// futures so the trait can be used as `&dyn SessionStore`.
+///
+/// [`ChippClient::chat_with_session_store()`]: crate::ChippClient::chat_with_session_store
+///
+/// # Example
+///
+/// ```
+/// use chipp::SessionStore;
+/// use futures::future::BoxFuture;
+/// use std::collections::HashMap;
+/// use tokio::sync::RwLock;
+///
+/// struct SharedSessions(RwLock<HashMap<String, String>>);
+///
+/// impl SessionStore for SharedSessions {
+///     fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<String>> {
+///         Box::pin(async move { self.0.read().await.get(key).cloned() })
+///     }
+///
+///     fn set<'a>(&'a self, key: &'a str, session_id: String) -> BoxFuture<'a, ()> {
+///         Box::pin(async move {
+///             self.0.write().await.insert(key.to_string(), session_id);
+///         })
+///     }
+/// }
+/// ```
+pub trait SessionStore: Send + Sync {
+    /// Get the session ID stored under `key`, if any.
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<String>>;
+
+    /// Store `session_id` under `key`, replacing any previous ID.
+    fn set<'a>(&'a self, key: &'a str, session_id: String) -> BoxFuture<'a, ()>;
+}
+
+/// A [`SessionStore`] backed by an in-process `HashMap`.
+///
+/// Share one across tasks with an `Arc`. IDs are lost when the process exits.
+///
+/// # Example
+///
+/// ```
+/// use chipp::{InMemorySessionStore, SessionStore};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let store = InMemorySessionStore::new();
+/// store.set("user-42", "session-abc".to_string()).await;
+///
+/// assert_eq!(store.get("user-42").await, Some("session-abc".to_string()));
+/// assert_eq!(store.get("user-7").await, None);
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct InMemorySessionStore {
+    ids: Mutex<HashMap<String, String>>,
+}
+
+impl InMemorySessionStore {
+    /// Create an empty store.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of keys with a stored session ID.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ids
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Returns `true` if no session IDs are stored.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl SessionStore for InMemorySessionStore {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<String>> {
+        let id = self
+            .ids
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(key)
+            .cloned();
+        Box::pin(async move { id })
+    }
+
+    fn set<'a>(&'a self, key: &'a str, session_id: String) -> BoxFuture<'a, ()> {
+        self.ids
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(key.to_string(), session_id);
+        Box::pin(async {})
+    }
+}
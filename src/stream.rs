@@ -3,8 +3,16 @@
 //! The Chipp API uses Server-Sent Events (SSE) with custom JSON event types:
 //!
 //! - `text-delta`: Content chunks with `delta` field
-//! - `message-metadata`: Contains `persistedMessageId` for session tracking
-//! - `finish`: Stream completion signal
+//! - `reasoning-delta`: Reasoning ("thinking") chunks from reasoning models
+//! - `message-metadata`: Contains `persistedMessageId` for session tracking, among
+//!   other annotations ([`ChippStream::metadata_annotations()`])
+//! - `finish`: Stream completion signal, optionally carrying token `usage`
+//!
+//! Reported `usage` is cumulative; servers may also attach it to
+//! `message-metadata` events while streaming.
+//!
+//! A standard SSE `retry: <ms>` line is recorded as a reconnection hint
+//! ([`ChippStream::retry_hint()`]); other non-`data` lines are ignored.
 //!
 //! # Example Format
 //!
@@ -16,25 +24,45 @@
 //! ```
 
 use crate::error::ChippClientError;
+use crate::types::{ResponseHeaders, Usage};
 use bytes::Bytes;
-use futures::Stream;
+use futures::stream::FusedStream;
+use futures::{Stream, StreamExt};
 use serde::Deserialize;
+use std::collections::{HashSet, VecDeque};
+use std::future::Future;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, PoisonError};
 use std::task::{Context, Poll};
-use tokio::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio_util::sync::{CancellationToken, WaitForCancellationFutureOwned};
 
 /// A stream event from the Chipp API.
-#[derive(Debug, Clone)]
+///
+/// Yielded by [`ChippEventStream`]; [`ChippStream`] only yields the text of
+/// [`TextDelta`](Self::TextDelta) events.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum StreamEvent {
     /// Text content chunk
     TextDelta(String),
+    /// Reasoning ("thinking") chunk from a reasoning model
+    ReasoningDelta(String),
     /// Session ID from message metadata
     SessionId(String),
+    /// Cumulative token usage reported by a `finish` or `message-metadata` event
+    ///
+    /// See [`ChippEventStream::usage_delta()`] for the increment since the
+    /// previous report.
+    Usage(Usage),
     /// Stream finished
     Done,
 }
 
+/// Observer invoked for every parsed [`StreamEvent`].
+///
+/// See [`ChippConfig::on_stream_event`](crate::ChippConfig::on_stream_event).
+pub type StreamEventCallback = Arc<dyn Fn(&StreamEvent) + Send + Sync>;
+
 /// Internal JSON structure for SSE events.
 #[derive(Debug, Deserialize)]
 struct SseEvent {
@@ -44,108 +72,354 @@ struct SseEvent {
     delta: Option<String>,
     #[serde(rename = "messageMetadata")]
     message_metadata: Option<MessageMetadata>,
+    #[serde(default)]
+    usage: Option<Usage>,
+    #[serde(rename = "errorText", default)]
+    error_text: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct MessageMetadata {
-    annotations: Vec<Annotation>,
+    /// Annotation objects, kept whole; `persistedMessageId` is one of their fields
+    annotations: Vec<serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
-struct Annotation {
-    #[serde(rename = "persistedMessageId")]
-    persisted_message_id: Option<String>,
+/// A parsed SSE `data:` payload.
+enum SsePayload {
+    /// The `[DONE]` signal
+    Done,
+    /// A JSON event
+    Event(SseEvent),
 }
 
-/// Parse a single SSE line into an event.
-pub fn parse_sse_line(line: &str) -> Option<StreamEvent> {
+/// Parse a single SSE line into its `data:` payload.
+fn parse_sse_payload(line: &str) -> Option<SsePayload> {
     let data = line.strip_prefix("data: ")?;
 
     // Handle [DONE] signal
     if data == "[DONE]" {
-        return Some(StreamEvent::Done);
+        return Some(SsePayload::Done);
     }
 
     // Parse JSON event
-    let event: SseEvent = serde_json::from_str(data).ok()?;
-
-    match event.event_type.as_str() {
-        "text-delta" => event.delta.map(StreamEvent::TextDelta),
-        "message-metadata" => {
-            // Extract persistedMessageId from annotations
-            event.message_metadata.and_then(|meta| {
-                meta.annotations
-                    .into_iter()
-                    .find_map(|ann| ann.persisted_message_id.map(StreamEvent::SessionId))
-            })
+    serde_json::from_str(data).ok().map(SsePayload::Event)
+}
+
+impl SseEvent {
+    /// The error an `error` event reports.
+    fn error(&self) -> Option<ChippClientError> {
+        (self.event_type == "error").then(|| {
+            ChippClientError::StreamError(format!(
+                "Server sent an error event: {}",
+                self.error_text.as_deref().unwrap_or("unknown error")
+            ))
+        })
+    }
+
+    /// Convert into the stream events this carries, in order.
+    ///
+    /// A `message-metadata` event may carry both a session ID and usage; the
+    /// session ID comes first.
+    fn into_stream_events(self) -> Vec<StreamEvent> {
+        let usage = self
+            .usage
+            .map(|usage| StreamEvent::Usage(usage.reconciled()));
+        match self.event_type.as_str() {
+            "text-delta" => self.delta.map(StreamEvent::TextDelta).into_iter().collect(),
+            "reasoning-delta" => self
+                .delta
+                .map(StreamEvent::ReasoningDelta)
+                .into_iter()
+                .collect(),
+            "message-metadata" => {
+                let Some(meta) = self.message_metadata else {
+                    return Vec::new();
+                };
+                // Extract persistedMessageId from annotations
+                let session_id = meta.annotations.iter().find_map(|annotation| {
+                    annotation
+                        .get("persistedMessageId")
+                        .and_then(serde_json::Value::as_str)
+                        .map(|id| StreamEvent::SessionId(id.to_string()))
+                });
+                session_id.into_iter().chain(usage).collect()
+            }
+            "finish" => usage.into_iter().collect(),
+            _ => Vec::new(),
         }
-        _ => None,
     }
 }
 
-/// Stream of text chunks from Chipp API.
+/// Parse a single SSE line into an event.
 ///
-/// Implements `Stream<Item = Result<String, ChippClientError>>`.
+/// A `message-metadata` line carrying both a session ID and usage parses to
+/// the [`SessionId`](StreamEvent::SessionId) event only, and `error` events
+/// parse to `None`. Use [`replay_sse_payloads()`] to replay a capture.
+pub fn parse_sse_line(line: &str) -> Option<StreamEvent> {
+    match parse_sse_payload(line)? {
+        SsePayload::Done => Some(StreamEvent::Done),
+        SsePayload::Event(event) => event.into_stream_events().into_iter().next(),
+    }
+}
+
+/// Replay `data:` payloads captured with [`RawChippStream`].
 ///
-/// Use with `futures::StreamExt` to iterate over chunks:
+/// Yields the events a [`ChippEventStream`] would have yielded for the same
+/// payloads: every event of a combined `message-metadata` payload, repeated
+/// session IDs once, and an `error` payload as a
+/// [`StreamError`](ChippClientError::StreamError). Replay ends after an error
+/// or [`StreamEvent::Done`].
 ///
-/// ```no_run
-/// use futures::StreamExt;
-/// # use chipp::{ChippClient, ChippConfig, ChippSession, ChippMessage};
-/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-/// # let config = ChippConfig::default();
-/// # let client = ChippClient::new(config)?;
-/// # let mut session = ChippSession::new();
-/// let mut stream = client.chat_stream(&mut session, &[ChippMessage::user("Hi")]).await?;
+/// ```
+/// use chipp::{replay_sse_payloads, StreamEvent};
 ///
-/// while let Some(chunk) = stream.next().await {
-///     print!("{}", chunk?);
-/// }
-/// # Ok(())
-/// # }
+/// let payloads = [r#"{"type":"text-delta","id":"m","delta":"Hi"}"#, "[DONE]"];
+/// let events: Vec<_> = replay_sse_payloads(payloads).collect::<Result<_, _>>().unwrap();
+/// assert_eq!(events, [StreamEvent::TextDelta("Hi".to_string()), StreamEvent::Done]);
 /// ```
-pub struct ChippStream {
+pub fn replay_sse_payloads<I>(
+    payloads: I,
+) -> impl Iterator<Item = Result<StreamEvent, ChippClientError>>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let lines: Vec<Result<Bytes, reqwest::Error>> = payloads
+        .into_iter()
+        .map(|payload| Ok(Bytes::from(format!("data: {}\n", payload.as_ref()))))
+        .collect();
+    let mut source = EventSource::new(Box::pin(futures::stream::iter(lines)));
+    // The payloads are all in memory, so polling never waits
+    let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+    std::iter::from_fn(move || match source.poll_event(&mut cx) {
+        Poll::Ready(event) => event,
+        Poll::Pending => None,
+    })
+}
+
+/// Parse an SSE `retry: <ms>` line into the suggested reconnection delay.
+///
+/// Per the SSE spec the value must consist of ASCII digits only; anything else
+/// is ignored.
+fn parse_retry_line(line: &str) -> Option<Duration> {
+    let value = line.strip_prefix("retry:")?;
+    let value = value.strip_prefix(' ').unwrap_or(value);
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok().map(Duration::from_millis)
+}
+
+/// Record token usage as OpenTelemetry GenAI fields on `span`.
+pub(crate) fn record_gen_ai_usage(span: &tracing::Span, usage: &Usage) {
+    span.record("gen_ai.usage.input_tokens", usage.prompt_tokens)
+        .record("gen_ai.usage.output_tokens", usage.completion_tokens);
+}
+
+/// Byte stream type produced by reqwest for streaming responses.
+type ByteStream = Pin<Box<dyn Stream<Item = Result<Bytes, reqwest::Error>> + Send>>;
+
+/// Transformation applied to every text delta, see [`ChippStream::map_text()`].
+type TextMap = Box<dyn Fn(String) -> String + Send>;
+
+/// An event read ahead of the consumer, with the state it was parsed in.
+struct QueuedEvent {
+    event: StreamEvent,
+    /// When the bytes completing the event arrived
+    arrived_at: Instant,
+    /// `retry_hint` as of this event, so read-ahead `retry:` lines surface in order
+    retry_hint: Option<Duration>,
+}
+
+/// Parses SSE lines from a byte stream into [`StreamEvent`]s.
+///
+/// Shared by [`ChippStream`] and [`ChippEventStream`], which differ only in
+/// which events they hand to the consumer.
+pub(crate) struct EventSource {
     /// Inner byte stream from reqwest
-    inner: Pin<Box<dyn Stream<Item = Result<Bytes, reqwest::Error>> + Send>>,
+    inner: ByteStream,
     /// Buffer for incomplete SSE lines
     buffer: String,
-    /// Shared reference to session for updating chatSessionId
-    session_id: Arc<Mutex<Option<String>>>,
+    /// Session ID captured from `message-metadata`, owned by the stream so
+    /// recording it while polling can never fail
+    session_id: Option<String>,
+    /// Events to yield before reading from `inner`, read ahead while
+    /// prefetching or replayed for the non-SSE fallback
+    queued: VecDeque<QueuedEvent>,
+    /// Transport error hit while prefetching, yielded once `queued` drains
+    queued_error: Option<ChippClientError>,
+    /// Latest cumulative token usage, if the server sent any
+    usage: Option<Usage>,
+    /// Usage added by the latest report, relative to the one before it
+    usage_delta: Option<Usage>,
     /// Whether stream has finished
     finished: bool,
+    /// When the latest bytes were read from `inner`
+    received_at: Instant,
+    /// When the bytes of the latest yielded event arrived
+    arrived_at: Option<Instant>,
+    /// Observer notified of every event as it is processed
+    observer: Option<StreamEventCallback>,
+    /// Buffer that accumulates every text delta, if teeing
+    tee: Option<SharedBuffer>,
+    /// Reconnection delay suggested by the latest SSE `retry:` line
+    retry_hint: Option<Duration>,
+    /// Transformation applied to text deltas before anything else sees them
+    text_map: Option<TextMap>,
+    /// Distinct `message-metadata` annotations seen so far, in arrival order
+    annotations: Vec<serde_json::Value>,
+    /// Serialized form of every entry in `annotations`, for deduplication
+    annotation_keys: HashSet<String>,
+    /// Further events parsed from the latest line, not yet returned
+    line_events: VecDeque<StreamEvent>,
+    /// `data:` payloads read but not yet yielded by a [`RawChippStream`]
+    ///
+    /// Only collected for streams opened with `with_raw_lines`.
+    raw_lines: Option<VecDeque<String>>,
+    /// Resolve when the client is shut down or the caller cancels, ending the
+    /// stream with `Cancelled`
+    cancelled: Vec<Pin<Box<WaitForCancellationFutureOwned>>>,
+    /// Span of the call that opened the stream, updated with the reported usage
+    gen_ai_span: tracing::Span,
 }
 
-impl std::fmt::Debug for ChippStream {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("ChippStream")
-            .field("finished", &self.finished)
-            .finish_non_exhaustive()
-    }
-}
-
-impl ChippStream {
-    /// Create a new stream from a reqwest byte stream.
-    pub(crate) fn new(
-        inner: Pin<Box<dyn Stream<Item = Result<Bytes, reqwest::Error>> + Send>>,
-        session_id: Arc<Mutex<Option<String>>>,
-    ) -> Self {
+impl EventSource {
+    /// Create a new event source from a reqwest byte stream.
+    pub(crate) fn new(inner: ByteStream) -> Self {
         Self {
             inner,
             buffer: String::new(),
-            session_id,
+            session_id: None,
+            queued: VecDeque::new(),
+            queued_error: None,
+            usage: None,
+            usage_delta: None,
             finished: false,
+            received_at: Instant::now(),
+            arrived_at: None,
+            observer: None,
+            tee: None,
+            retry_hint: None,
+            text_map: None,
+            annotations: Vec::new(),
+            annotation_keys: HashSet::new(),
+            line_events: VecDeque::new(),
+            raw_lines: None,
+            cancelled: Vec::new(),
+            gen_ai_span: tracing::Span::none(),
         }
     }
 
-    /// Get the session ID captured during streaming (if available).
+    /// Notify `observer` of every event this source produces.
+    pub(crate) fn with_observer(mut self, observer: Option<StreamEventCallback>) -> Self {
+        self.observer = observer;
+        self
+    }
+
+    /// End the stream with [`ChippClientError::Cancelled`] once `token` is cancelled.
+    pub(crate) fn with_shutdown(mut self, token: CancellationToken) -> Self {
+        self.cancelled.push(Box::pin(token.cancelled_owned()));
+        self
+    }
+
+    /// Also end the stream with [`ChippClientError::Cancelled`] once `cancel`,
+    /// if any, is cancelled.
+    pub(crate) fn with_cancel(self, cancel: Option<&CancellationToken>) -> Self {
+        match cancel {
+            Some(token) => self.with_shutdown(token.clone()),
+            None => self,
+        }
+    }
+
+    /// Keep every `data:` payload read for a [`RawChippStream`].
     ///
-    /// This is set when the API sends `message-metadata` with `persistedMessageId`.
-    pub async fn session_id(&self) -> Option<String> {
-        self.session_id.lock().await.clone()
+    /// Set before the first read so payloads read while prefetching aren't lost.
+    pub(crate) fn with_raw_lines(mut self, enabled: bool) -> Self {
+        self.raw_lines = enabled.then(VecDeque::new);
+        self
+    }
+
+    /// Record the usage this source reports on `span`.
+    pub(crate) fn with_gen_ai_span(mut self, span: tracing::Span) -> Self {
+        self.gen_ai_span = span;
+        self
+    }
+
+    /// Append every text delta this source produces to `buffer`.
+    pub(crate) fn with_tee(mut self, buffer: SharedBuffer) -> Self {
+        self.tee = Some(buffer);
+        self
+    }
+
+    /// Create an event source that replays an already-received response.
+    ///
+    /// Used when the server answers a streaming request with a plain JSON body.
+    pub(crate) fn from_complete(content: String, session_id: String) -> Self {
+        let mut source = Self::new(Box::pin(futures::stream::empty()));
+        source.queue(StreamEvent::TextDelta(content));
+        if !session_id.is_empty() {
+            source.session_id = Some(session_id.clone());
+            source.queue(StreamEvent::SessionId(session_id));
+        }
+        source.queue(StreamEvent::Done);
+        source
+    }
+
+    /// Queue an event read ahead of the consumer.
+    fn queue(&mut self, event: StreamEvent) {
+        self.queued.push_back(QueuedEvent {
+            event,
+            arrived_at: self.received_at,
+            retry_hint: self.retry_hint,
+        });
+    }
+
+    /// When the bytes of the latest yielded event arrived.
+    ///
+    /// Read-ahead events keep the time they were received, not the time the
+    /// consumer got them.
+    pub(crate) fn arrived_at(&self) -> Option<Instant> {
+        self.arrived_at
+    }
+
+    /// Whether every remaining poll will return `None`.
+    fn is_terminated(&self) -> bool {
+        self.finished && self.queued.is_empty() && self.queued_error.is_none()
     }
 
-    /// Process buffered data and extract next text chunk.
-    fn process_buffer(&mut self) -> Option<Result<String, ChippClientError>> {
+    /// Get the session ID captured so far (if available).
+    fn session_id(&self) -> Option<String> {
+        self.session_id.clone()
+    }
+
+    /// Whether `event` repeats the latest session ID, queued or captured.
+    ///
+    /// The server may repeat the same persistedMessageId in several metadata
+    /// events; only the first one is worth reporting.
+    fn is_repeated_session_id(&self, event: &StreamEvent) -> bool {
+        let StreamEvent::SessionId(id) = event else {
+            return false;
+        };
+        let latest = self
+            .queued
+            .iter()
+            .rev()
+            .find_map(|queued| match &queued.event {
+                StreamEvent::SessionId(id) => Some(id),
+                _ => None,
+            })
+            .or(self.session_id.as_ref());
+        latest == Some(id)
+    }
+
+    /// Parse the next complete line in the buffer into an event.
+    ///
+    /// An `error` event from the server is returned as a `StreamError`.
+    fn next_buffered_event(&mut self) -> Option<Result<StreamEvent, ChippClientError>> {
+        if let Some(event) = self.line_events.pop_front() {
+            return Some(Ok(event));
+        }
+
         // Process complete lines from buffer
         while let Some(newline_pos) = self.buffer.find('\n') {
             let line = self.buffer[..newline_pos].trim().to_string();
@@ -155,74 +429,228 @@ impl ChippStream {
                 continue;
             }
 
-            if let Some(event) = parse_sse_line(&line) {
-                match event {
-                    StreamEvent::TextDelta(text) => {
-                        return Some(Ok(text));
-                    }
-                    StreamEvent::SessionId(id) => {
-                        // Update session ID asynchronously
-                        // We can't await here, so we use try_lock
-                        if let Ok(mut guard) = self.session_id.try_lock() {
-                            *guard = Some(id);
-                        }
-                    }
-                    StreamEvent::Done => {
-                        self.finished = true;
-                        return None;
-                    }
-                }
+            if let Some(delay) = parse_retry_line(&line) {
+                self.retry_hint = Some(delay);
+                continue;
+            }
+
+            if let (Some(raw_lines), Some(payload)) =
+                (&mut self.raw_lines, line.strip_prefix("data: "))
+            {
+                raw_lines.push_back(payload.to_string());
+            }
+
+            let event = match parse_sse_payload(&line) {
+                Some(SsePayload::Done) => return Some(Ok(StreamEvent::Done)),
+                Some(SsePayload::Event(event)) => event,
+                None => continue,
+            };
+            if let Some(e) = event.error() {
+                return Some(Err(e));
+            }
+            if let Some(meta) = &event.message_metadata {
+                self.record_annotations(&meta.annotations);
+            }
+            self.line_events.extend(event.into_stream_events());
+            if let Some(event) = self.line_events.pop_front() {
+                return Some(Ok(event));
             }
         }
         None
     }
-}
 
-impl Stream for ChippStream {
-    type Item = Result<String, ChippClientError>;
+    /// Keep each `message-metadata` annotation not seen before.
+    ///
+    /// Servers repeat metadata events, so identical annotations are kept once.
+    fn record_annotations(&mut self, annotations: &[serde_json::Value]) {
+        for annotation in annotations {
+            if self.annotation_keys.insert(annotation.to_string()) {
+                self.annotations.push(annotation.clone());
+            }
+        }
+    }
+
+    /// Append newly received bytes to the line buffer.
+    fn push_bytes(&mut self, bytes: &Bytes) -> Result<(), ChippClientError> {
+        let text = String::from_utf8(bytes.to_vec()).map_err(|e| {
+            ChippClientError::StreamError(format!("Invalid UTF-8 in stream: {}", e))
+        })?;
+        self.buffer.push_str(&text);
+        self.received_at = Instant::now();
+        Ok(())
+    }
+
+    /// Read ahead until the first text chunk, or the end of the stream, is buffered.
+    ///
+    /// Events read on the way are queued rather than recorded, so consumers
+    /// and observers still see each of them once, in order. A transport error
+    /// or `error` event before that point is returned instead, letting the
+    /// caller retry.
+    pub(crate) async fn prefetch_first_text(&mut self) -> Result<(), ChippClientError> {
+        self.prefetch_until(|event| matches!(event, StreamEvent::TextDelta(_) | StreamEvent::Done))
+            .await
+    }
+
+    /// Read ahead until the first event, or the end of the stream, is buffered.
+    ///
+    /// Like [`prefetch_first_text()`](Self::prefetch_first_text), so an
+    /// immediate `error` event fails the request that opened the stream. A
+    /// transport error is kept for the first poll instead, since without
+    /// `retry_stream_start` nothing would retry it.
+    pub(crate) async fn prefetch_first_event(&mut self) -> Result<(), ChippClientError> {
+        match self.prefetch_until(|_| true).await {
+            Err(e @ ChippClientError::HttpError(_)) => {
+                self.queued_error = Some(e);
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    /// Queue events until one matches `done`, or the stream ends.
+    ///
+    /// A `retry:` hint read on the way only becomes visible once the event
+    /// after it is yielded.
+    async fn prefetch_until(
+        &mut self,
+        done: impl Fn(&StreamEvent) -> bool,
+    ) -> Result<(), ChippClientError> {
+        let retry_hint = self.retry_hint;
+        let result = self.read_ahead_until(done).await;
+        self.retry_hint = retry_hint;
+        result
+    }
+
+    /// Queue events until one matches `done`, or the stream ends.
+    async fn read_ahead_until(
+        &mut self,
+        done: impl Fn(&StreamEvent) -> bool,
+    ) -> Result<(), ChippClientError> {
+        if let Some(e) = self.queued_error.take() {
+            return Err(e);
+        }
+        loop {
+            while let Some(event) = self.next_buffered_event() {
+                let event = event?;
+                if self.is_repeated_session_id(&event) {
+                    continue;
+                }
+                let reached = done(&event);
+                self.queue(event);
+                if reached {
+                    return Ok(());
+                }
+            }
+
+            match self.inner.next().await {
+                Some(Ok(bytes)) => self.push_bytes(&bytes)?,
+                Some(Err(e)) => return Err(ChippClientError::HttpError(e)),
+                None => {
+                    // Don't poll the finished body again once the queue drains
+                    self.inner = Box::pin(futures::stream::empty());
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// Apply the text transformation, if any, to a text delta.
+    fn transform(&self, event: StreamEvent) -> StreamEvent {
+        match (event, &self.text_map) {
+            (StreamEvent::TextDelta(text), Some(map)) => StreamEvent::TextDelta(map(text)),
+            (event, _) => event,
+        }
+    }
+
+    /// Apply an event's side effects on stream state.
+    fn record(&mut self, event: &StreamEvent) {
+        if let Some(observer) = &self.observer {
+            observer(event);
+        }
+
+        match event {
+            StreamEvent::SessionId(id) => {
+                self.session_id = Some(id.clone());
+            }
+            StreamEvent::Usage(usage) => {
+                self.usage_delta = Some(usage.saturating_sub(&self.usage.unwrap_or_default()));
+                self.usage = Some(*usage);
+                record_gen_ai_usage(&self.gen_ai_span, usage);
+            }
+            StreamEvent::Done => {
+                self.finished = true;
+            }
+            StreamEvent::TextDelta(text) => {
+                if let Some(tee) = &self.tee {
+                    tee.push_str(text);
+                }
+            }
+            StreamEvent::ReasoningDelta(_) => {}
+        }
+    }
+
+    /// Poll for the next parsed event.
+    fn poll_event(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<StreamEvent, ChippClientError>>> {
+        if let Some(queued) = self.queued.pop_front() {
+            self.arrived_at = Some(queued.arrived_at);
+            self.retry_hint = queued.retry_hint;
+            let event = self.transform(queued.event);
+            self.record(&event);
+            return Poll::Ready(Some(Ok(event)));
+        }
+
+        if let Some(e) = self.queued_error.take() {
+            return Poll::Ready(Some(Err(e)));
+        }
 
-    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         if self.finished {
             return Poll::Ready(None);
         }
 
-        // First, try to get content from existing buffer
-        if let Some(result) = self.process_buffer() {
-            return Poll::Ready(Some(result));
+        if self
+            .cancelled
+            .iter_mut()
+            .any(|cancelled| cancelled.as_mut().poll(cx).is_ready())
+        {
+            tracing::info!("Stream cancelled, ending stream");
+            self.finished = true;
+            return Poll::Ready(Some(Err(ChippClientError::Cancelled)));
         }
 
-        // Poll for more data from the inner stream
         loop {
+            // First, try to get an event from existing buffer
+            if let Some(event) = self.next_buffered_event() {
+                let event = match event {
+                    Ok(event) => event,
+                    Err(e) => {
+                        self.finished = true;
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                };
+                if self.is_repeated_session_id(&event) {
+                    continue;
+                }
+                self.arrived_at = Some(self.received_at);
+                let event = self.transform(event);
+                self.record(&event);
+                return Poll::Ready(Some(Ok(event)));
+            }
+
+            // Poll for more data from the inner stream
             match self.inner.as_mut().poll_next(cx) {
                 Poll::Ready(Some(Ok(bytes))) => {
-                    // Append new data to buffer
-                    match String::from_utf8(bytes.to_vec()) {
-                        Ok(text) => {
-                            self.buffer.push_str(&text);
-                            // Try to extract content from buffer
-                            if let Some(result) = self.process_buffer() {
-                                return Poll::Ready(Some(result));
-                            }
-                            // No complete line yet, continue polling
-                        }
-                        Err(e) => {
-                            return Poll::Ready(Some(Err(ChippClientError::StreamError(format!(
-                                "Invalid UTF-8 in stream: {}",
-                                e
-                            )))));
-                        }
+                    if let Err(e) = self.push_bytes(&bytes) {
+                        return Poll::Ready(Some(Err(e)));
                     }
                 }
                 Poll::Ready(Some(Err(e))) => {
                     return Poll::Ready(Some(Err(ChippClientError::HttpError(e))));
                 }
                 Poll::Ready(None) => {
-                    // Stream ended, process any remaining buffer
-                    if !self.buffer.is_empty() {
-                        if let Some(result) = self.process_buffer() {
-                            return Poll::Ready(Some(result));
-                        }
-                    }
+                    // Stream ended; any remaining buffer is an incomplete line
                     self.finished = true;
                     return Poll::Ready(None);
                 }
@@ -233,3 +661,984 @@ impl Stream for ChippStream {
         }
     }
 }
+
+/// Stream of text chunks from Chipp API.
+///
+/// Implements `Stream<Item = Result<String, ChippClientError>>`.
+///
+/// Only `text-delta` content is yielded; use
+/// [`ChippClient::chat_stream_events`](crate::ChippClient::chat_stream_events)
+/// to also receive reasoning deltas and metadata.
+///
+/// The stream is fused: once it has returned `None`, polling again keeps
+/// returning `None`, and [`FusedStream::is_terminated()`] reports `true`.
+///
+/// Use with `futures::StreamExt` to iterate over chunks:
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// # use chipp::{ChippClient, ChippConfig, ChippSession, ChippMessage};
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let config = ChippConfig::default();
+/// # let client = ChippClient::new(config)?;
+/// # let mut session = ChippSession::new();
+/// let mut stream = client.chat_stream(&mut session, &[ChippMessage::user("Hi")]).await?;
+///
+/// while let Some(chunk) = stream.next().await {
+///     print!("{}", chunk?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ChippStream {
+    /// Parsed SSE events
+    events: EventSource,
+}
+
+impl std::fmt::Debug for ChippStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChippStream")
+            .field("finished", &self.events.finished)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ChippStream {
+    /// Create a text stream from an event source.
+    pub(crate) fn new(events: EventSource) -> Self {
+        Self { events }
+    }
+
+    /// Get the token usage reported when the stream finished (if available).
+    ///
+    /// Populated once the server's `finish` event arrives (or an earlier
+    /// `message-metadata` event reports usage); `None` before that or if the
+    /// server didn't report usage.
+    #[must_use]
+    pub fn final_usage(&self) -> Option<Usage> {
+        self.events.usage
+    }
+
+    /// Get the reconnection delay suggested by the server (if any).
+    ///
+    /// Set from the most recent SSE `retry: <ms>` line seen so far.
+    #[must_use]
+    pub fn retry_hint(&self) -> Option<Duration> {
+        self.events.retry_hint
+    }
+
+    /// Get the `message-metadata` annotations received so far.
+    ///
+    /// Each annotation is the server's JSON object as is, e.g. the
+    /// `persistedMessageId` used for [`session_id()`](Self::session_id), or
+    /// citation sources and feedback IDs. Populated as metadata events are
+    /// read; identical annotations repeated by the server appear once.
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// # use chipp::{ChippClient, ChippConfig, ChippSession, ChippMessage};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = ChippConfig::default();
+    /// # let client = ChippClient::new(config)?;
+    /// # let mut session = ChippSession::new();
+    /// let mut stream = client.chat_stream(&mut session, &[ChippMessage::user("Hi")]).await?;
+    /// while let Some(chunk) = stream.next().await {
+    ///     print!("{}", chunk?);
+    /// }
+    ///
+    /// for annotation in stream.metadata_annotations() {
+    ///     if let Some(source) = annotation.get("source") {
+    ///         println!("\nSource: {}", source);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn metadata_annotations(&self) -> &[serde_json::Value] {
+        &self.events.annotations
+    }
+
+    /// Switch to usage mode, which yields a final item carrying token usage.
+    ///
+    /// See [`ChippUsageStream`] for the item contract. The plain `ChippStream`
+    /// never yields anything but text.
+    #[must_use]
+    pub fn with_usage(self) -> ChippUsageStream {
+        ChippUsageStream {
+            events: self.events,
+            done: false,
+        }
+    }
+
+    /// Transform every text chunk, keeping the stream's session tracking.
+    ///
+    /// Unlike `futures::StreamExt::map`, the result is still a `ChippStream`,
+    /// so [`session_id()`](Self::session_id), [`final_usage()`](Self::final_usage)
+    /// and the other adapters keep working. `f` runs on each `text-delta` as
+    /// it is parsed, so a tee or `on_stream_event` observer sees the
+    /// transformed text too. Calling this again chains the transformations.
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// # use chipp::{ChippClient, ChippConfig, ChippSession, ChippMessage};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = ChippConfig::default();
+    /// # let client = ChippClient::new(config)?;
+    /// # let mut session = ChippSession::new();
+    /// let mut stream = client
+    ///     .chat_stream(&mut session, &[ChippMessage::user("Hi")])
+    ///     .await?
+    ///     .map_text(|text| text.replace("darn", "****"));
+    ///
+    /// while let Some(chunk) = stream.next().await {
+    ///     print!("{}", chunk?);
+    /// }
+    /// if let Some(id) = stream.session_id().await {
+    ///     session.set_id(id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn map_text<F>(mut self, f: F) -> ChippStream
+    where
+        F: Fn(String) -> String + Send + 'static,
+    {
+        self.events.text_map = Some(match self.events.text_map.take() {
+            Some(previous) => Box::new(move |text| f(previous(text))),
+            None => Box::new(f),
+        });
+        self
+    }
+
+    /// Re-chunk the text into whole sentences, e.g. for text-to-speech.
+    ///
+    /// See [`SentenceStream`] for how boundaries are detected.
+    #[must_use]
+    pub fn sentences(self) -> SentenceStream {
+        SentenceStream {
+            inner: self,
+            buffer: String::new(),
+            ready: VecDeque::new(),
+            finished: false,
+        }
+    }
+
+    /// Erase the stream's type into a boxed `Send` stream.
+    ///
+    /// `ChippStream` is `Send + 'static`, so it can be moved into
+    /// `tokio::spawn` directly; boxing is useful when streams from different
+    /// sources need a single type.
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// # use chipp::{ChippClient, ChippConfig, ChippSession, ChippMessage};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let config = ChippConfig::default();
+    /// # let client = ChippClient::new(config)?;
+    /// # let mut session = ChippSession::new();
+    /// let mut stream = client
+    ///     .chat_stream(&mut session, &[ChippMessage::user("Hi")])
+    ///     .await?
+    ///     .into_boxed();
+    ///
+    /// tokio::spawn(async move {
+    ///     while let Some(chunk) = stream.next().await {
+    ///         // forward chunk...
+    ///         # let _ = chunk;
+    ///     }
+    /// });
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn into_boxed(
+        self,
+    ) -> Pin<Box<dyn Stream<Item = Result<String, ChippClientError>> + Send>> {
+        Box::pin(self)
+    }
+
+    /// Get the session ID captured during streaming (if available).
+    ///
+    /// This is set when the API sends `message-metadata` with `persistedMessageId`.
+    pub async fn session_id(&self) -> Option<String> {
+        self.events.session_id()
+    }
+
+    /// Get the captured session ID without awaiting.
+    ///
+    /// Holding `&self` across an await would require the stream to be `Sync`,
+    /// which would make futures reading it non-`Send`.
+    pub(crate) fn captured_session_id(&self) -> Option<String> {
+        self.events.session_id()
+    }
+
+    /// When the latest yielded chunk arrived from the server.
+    pub(crate) fn arrived_at(&self) -> Option<Instant> {
+        self.events.arrived_at()
+    }
+}
+
+impl Stream for ChippStream {
+    type Item = Result<String, ChippClientError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match self.events.poll_event(cx) {
+                Poll::Ready(Some(Ok(StreamEvent::TextDelta(text)))) => {
+                    return Poll::Ready(Some(Ok(text)));
+                }
+                Poll::Ready(Some(Ok(StreamEvent::Done))) | Poll::Ready(None) => {
+                    return Poll::Ready(None);
+                }
+                // Reasoning and metadata events are not part of the text stream
+                Poll::Ready(Some(Ok(_))) => continue,
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl FusedStream for ChippStream {
+    fn is_terminated(&self) -> bool {
+        self.events.is_terminated()
+    }
+}
+
+/// Text buffer shared between a stream and its readers.
+///
+/// Returned by [`ChippClient::chat_stream_tee()`](crate::ChippClient::chat_stream_tee).
+/// Every text chunk the stream yields is appended before it reaches the
+/// consumer, so the buffer can be read from another task while the stream is
+/// in progress, and holds the full response once the stream ends.
+///
+/// Cloning the buffer is cheap and all clones share the same text.
+#[derive(Debug, Clone, Default)]
+pub struct SharedBuffer {
+    inner: Arc<Mutex<String>>,
+}
+
+impl SharedBuffer {
+    /// Get a copy of the text accumulated so far.
+    #[must_use]
+    pub fn text(&self) -> String {
+        self.lock().clone()
+    }
+
+    /// Get the length of the accumulated text in bytes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns `true` if no text has been accumulated yet.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    fn push_str(&self, text: &str) {
+        self.lock().push_str(text);
+    }
+
+    /// Lock the text, recovering it if a reader panicked while holding the lock.
+    fn lock(&self) -> std::sync::MutexGuard<'_, String> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Stream of text chunks followed by a final usage item.
+///
+/// Created with [`ChippStream::with_usage()`]. Implements
+/// `Stream<Item = Result<(String, Option<Usage>), ChippClientError>>`:
+///
+/// - Every text chunk is yielded as `(text, None)`.
+/// - When the stream completes normally, one final `(String::new(), usage)`
+///   item is yielded, where `usage` is the token usage from the server's
+///   `finish` event (or `None` if it wasn't reported), followed by `None`.
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// # use chipp::{ChippClient, ChippConfig, ChippSession, ChippMessage};
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let config = ChippConfig::default();
+/// # let client = ChippClient::new(config)?;
+/// # let mut session = ChippSession::new();
+/// let mut stream = client
+///     .chat_stream(&mut session, &[ChippMessage::user("Hi")])
+///     .await?
+///     .with_usage();
+///
+/// while let Some(item) = stream.next().await {
+///     match item? {
+///         (_, Some(usage)) => println!("\nTokens used: {}", usage.total_tokens),
+///         (text, None) => print!("{}", text),
+///     }
+/// }
+/// if let Some(id) = stream.session_id() {
+///     session.set_id(id);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ChippUsageStream {
+    /// Parsed SSE events
+    events: EventSource,
+    /// Whether the final usage item has been yielded
+    done: bool,
+}
+
+impl std::fmt::Debug for ChippUsageStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChippUsageStream")
+            .field("finished", &self.events.finished)
+            .field("usage", &self.events.usage)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ChippUsageStream {
+    /// Get the session ID captured during streaming (if available).
+    #[must_use]
+    pub fn session_id(&self) -> Option<String> {
+        self.events.session_id()
+    }
+
+    /// Get the token usage reported when the stream finished (if available).
+    #[must_use]
+    pub fn final_usage(&self) -> Option<Usage> {
+        self.events.usage
+    }
+}
+
+impl Stream for ChippUsageStream {
+    type Item = Result<(String, Option<Usage>), ChippClientError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+
+        loop {
+            match self.events.poll_event(cx) {
+                Poll::Ready(Some(Ok(StreamEvent::TextDelta(text)))) => {
+                    return Poll::Ready(Some(Ok((text, None))));
+                }
+                Poll::Ready(Some(Ok(StreamEvent::Done))) | Poll::Ready(None) => {
+                    self.done = true;
+                    let usage = self.events.usage;
+                    return Poll::Ready(Some(Ok((String::new(), usage))));
+                }
+                // Reasoning and metadata events are not part of the text stream
+                Poll::Ready(Some(Ok(_))) => continue,
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// Words that end in a period without ending a sentence.
+const ABBREVIATIONS: &[&str] = &[
+    "mr", "mrs", "ms", "dr", "prof", "sr", "jr", "st", "vs", "etc", "approx",
+];
+
+/// Stream of whole sentences re-chunked from a [`ChippStream`].
+///
+/// Created with [`ChippStream::sentences()`]. Text deltas are buffered and a
+/// sentence is yielded once a `.`, `!` or `?` followed by whitespace arrives;
+/// whatever remains when the stream ends is yielded as the last sentence.
+/// Sentences are trimmed of surrounding whitespace.
+///
+/// Abbreviations are handled conservatively: a period after a common
+/// abbreviation ("Dr.", "etc."), a single letter ("J."), or a word that
+/// already contains a period ("e.g.", "U.S.") never ends a sentence. "No."
+/// is only an abbreviation when a number follows ("No. 5"), so a sentence
+/// ending in it waits for the next word to arrive.
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// # use chipp::{ChippClient, ChippConfig, ChippSession, ChippMessage};
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let config = ChippConfig::default();
+/// # let client = ChippClient::new(config)?;
+/// # let mut session = ChippSession::new();
+/// let mut sentences = client
+///     .chat_stream(&mut session, &[ChippMessage::user("Tell me a story")])
+///     .await?
+///     .sentences();
+///
+/// while let Some(sentence) = sentences.next().await {
+///     println!("speak: {}", sentence?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SentenceStream {
+    /// Underlying text stream
+    inner: ChippStream,
+    /// Text received but not yet part of a complete sentence
+    buffer: String,
+    /// Complete sentences waiting to be yielded
+    ready: VecDeque<String>,
+    /// Whether the inner stream has ended
+    finished: bool,
+}
+
+impl SentenceStream {
+    /// Get the session ID captured during streaming (if available).
+    #[must_use]
+    pub fn session_id(&self) -> Option<String> {
+        self.inner.captured_session_id()
+    }
+
+    /// Get the token usage reported when the stream finished (if available).
+    #[must_use]
+    pub fn final_usage(&self) -> Option<Usage> {
+        self.inner.final_usage()
+    }
+
+    /// Move every complete sentence from the buffer into `ready`.
+    fn split_sentences(&mut self) {
+        let mut start = 0;
+        let mut chars = self.buffer.char_indices().peekable();
+
+        while let Some((i, c)) = chars.next() {
+            let Some(&(next_i, next)) = chars.peek() else {
+                break;
+            };
+            if !matches!(c, '.' | '!' | '?') || !next.is_whitespace() {
+                continue;
+            }
+            if c == '.' {
+                match Self::period_ends_sentence(&self.buffer[start..i], &self.buffer[next_i..]) {
+                    Some(true) => {}
+                    Some(false) => continue,
+                    // Wait for the word after the period
+                    None => break,
+                }
+            }
+
+            let sentence = self.buffer[start..next_i].trim();
+            if !sentence.is_empty() {
+                self.ready.push_back(sentence.to_string());
+            }
+            start = next_i;
+        }
+
+        self.buffer.drain(..start);
+    }
+
+    /// Check whether a period ends a sentence, given the text around it.
+    ///
+    /// Returns `None` if that depends on text that hasn't arrived yet.
+    fn period_ends_sentence(before: &str, after: &str) -> Option<bool> {
+        let word = before
+            .rsplit(char::is_whitespace)
+            .next()
+            .unwrap_or_default()
+            .trim_start_matches(|c: char| !c.is_alphanumeric());
+
+        if word.eq_ignore_ascii_case("no") {
+            // "No. 5" is an abbreviation, a bare "No." is an answer
+            let next = after.trim_start().chars().next()?;
+            return Some(!next.is_ascii_digit());
+        }
+
+        let abbreviation = word.contains('.')
+            || (word.chars().count() == 1 && word.chars().all(char::is_alphabetic))
+            || ABBREVIATIONS.contains(&word.to_lowercase().as_str());
+        Some(!abbreviation)
+    }
+}
+
+impl Stream for SentenceStream {
+    type Item = Result<String, ChippClientError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(sentence) = self.ready.pop_front() {
+                return Poll::Ready(Some(Ok(sentence)));
+            }
+
+            if self.finished {
+                return Poll::Ready(None);
+            }
+
+            match Pin::new(&mut self.inner).poll_next(cx) {
+                Poll::Ready(Some(Ok(text))) => {
+                    self.buffer.push_str(&text);
+                    self.split_sentences();
+                }
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => {
+                    self.finished = true;
+                    let rest = std::mem::take(&mut self.buffer);
+                    let rest = rest.trim();
+                    if !rest.is_empty() {
+                        self.ready.push_back(rest.to_string());
+                    }
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// Stream of text chunks paired with the time each took to arrive.
+///
+/// Returned by [`ChippClient::chat_stream_timed()`](crate::ChippClient::chat_stream_timed).
+/// Implements `Stream<Item = Result<(String, Duration), ChippClientError>>`,
+/// where the `Duration` is the gap since the previous chunk, or since the
+/// request was sent for the first one. Gaps are measured between the times
+/// chunks were read from the connection, so a chunk read ahead while opening
+/// the stream is still timed correctly. Useful for token cadence histograms
+/// and spotting mid-stream stalls.
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// # use chipp::{ChippClient, ChippConfig, ChippSession, ChippMessage};
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let config = ChippConfig::default();
+/// # let client = ChippClient::new(config)?;
+/// # let mut session = ChippSession::new();
+/// let mut stream = client
+///     .chat_stream_timed(&mut session, &[ChippMessage::user("Tell me a story")])
+///     .await?;
+///
+/// while let Some(chunk) = stream.next().await {
+///     let (text, gap) = chunk?;
+///     println!("+{:?}: {}", gap, text);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TimedChippStream {
+    /// Underlying text stream
+    inner: ChippStream,
+    /// When the previous chunk (or the request) was seen
+    last: Instant,
+}
+
+impl TimedChippStream {
+    /// Time chunks of `inner`, measuring the first gap from `start`.
+    pub(crate) fn new(inner: ChippStream, start: Instant) -> Self {
+        Self { inner, last: start }
+    }
+
+    /// Get the session ID captured during streaming (if available).
+    #[must_use]
+    pub fn session_id(&self) -> Option<String> {
+        self.inner.captured_session_id()
+    }
+
+    /// Get the token usage reported when the stream finished (if available).
+    #[must_use]
+    pub fn final_usage(&self) -> Option<Usage> {
+        self.inner.final_usage()
+    }
+
+    /// Stop timing and return the underlying text stream.
+    #[must_use]
+    pub fn into_inner(self) -> ChippStream {
+        self.inner
+    }
+}
+
+impl Stream for TimedChippStream {
+    type Item = Result<(String, Duration), ChippClientError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match Pin::new(&mut self.inner).poll_next(cx) {
+            Poll::Ready(Some(Ok(text))) => {
+                // Measure from arrival, since the first chunk is read while opening
+                let arrived = self.inner.arrived_at().unwrap_or_else(Instant::now);
+                let gap = arrived.saturating_duration_since(self.last);
+                self.last = arrived;
+                Poll::Ready(Some(Ok((text, gap))))
+            }
+            Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(e))),
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl FusedStream for TimedChippStream {
+    fn is_terminated(&self) -> bool {
+        self.inner.is_terminated()
+    }
+}
+
+/// Stream of structured events from Chipp API.
+///
+/// Implements `Stream<Item = Result<StreamEvent, ChippClientError>>`, yielding
+/// text and reasoning deltas separately along with session metadata and the
+/// final [`StreamEvent::Done`].
+///
+/// ```no_run
+/// use chipp::StreamEvent;
+/// use futures::StreamExt;
+/// # use chipp::{ChippClient, ChippConfig, ChippSession, ChippMessage};
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let config = ChippConfig::default();
+/// # let client = ChippClient::new(config)?;
+/// # let mut session = ChippSession::new();
+/// let mut events = client
+///     .chat_stream_events(&mut session, &[ChippMessage::user("Hi")])
+///     .await?;
+///
+/// while let Some(event) = events.next().await {
+///     match event? {
+///         StreamEvent::ReasoningDelta(thought) => eprint!("{}", thought),
+///         StreamEvent::TextDelta(text) => print!("{}", text),
+///         _ => {}
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ChippEventStream {
+    /// Parsed SSE events
+    events: EventSource,
+}
+
+impl std::fmt::Debug for ChippEventStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChippEventStream")
+            .field("finished", &self.events.finished)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ChippEventStream {
+    /// Create an event stream from an event source.
+    pub(crate) fn new(events: EventSource) -> Self {
+        Self { events }
+    }
+
+    /// Get the session ID captured during streaming (if available).
+    ///
+    /// This is set when the API sends `message-metadata` with `persistedMessageId`.
+    pub async fn session_id(&self) -> Option<String> {
+        self.events.session_id()
+    }
+
+    /// Get the latest cumulative token usage reported so far (if any).
+    #[must_use]
+    pub fn usage(&self) -> Option<Usage> {
+        self.events.usage
+    }
+
+    /// Get the tokens added by the most recent [`StreamEvent::Usage`] event.
+    ///
+    /// The first report counts from zero. Updated as each usage event is
+    /// yielded, so a live token meter can add this after every one; a report
+    /// smaller than the previous one counts as zero rather than underflowing
+    /// (see [`Usage::saturating_sub()`]).
+    #[must_use]
+    pub fn usage_delta(&self) -> Option<Usage> {
+        self.events.usage_delta
+    }
+}
+
+impl Stream for ChippEventStream {
+    type Item = Result<StreamEvent, ChippClientError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.events.poll_event(cx)
+    }
+}
+
+/// Stream of the raw `data:` payloads of a streaming response.
+///
+/// Created with
+/// [`ChippClient::chat_stream_raw()`](crate::ChippClient::chat_stream_raw).
+/// Implements `Stream<Item = Result<String, ChippClientError>>`, yielding each
+/// payload verbatim (the JSON text, or `[DONE]`) in arrival order, before any
+/// parsing. Feed a capture to [`replay_sse_payloads()`] to get back the events,
+/// including `error` events, that a [`ChippEventStream`] yields for it.
+///
+/// Session IDs are still captured. A server that answers with a plain JSON
+/// body instead of SSE yields no payloads.
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// # use chipp::{ChippClient, ChippConfig, ChippSession, ChippMessage};
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = ChippClient::new(ChippConfig::default())?;
+/// # let mut session = ChippSession::new();
+/// let mut raw = client
+///     .chat_stream_raw(&mut session, &[ChippMessage::user("Hi")])
+///     .await?;
+///
+/// while let Some(payload) = raw.next().await {
+///     eprintln!("{}", payload?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct RawChippStream {
+    /// Parsed SSE events, collecting raw payloads as lines are read
+    events: EventSource,
+    /// Error to yield once the payloads read before it are drained
+    pending_error: Option<ChippClientError>,
+    /// Whether the underlying event source has ended
+    ended: bool,
+}
+
+impl std::fmt::Debug for RawChippStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RawChippStream")
+            .field("ended", &self.ended)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RawChippStream {
+    /// Create a raw stream from an event source.
+    pub(crate) fn new(events: EventSource) -> Self {
+        Self {
+            events,
+            pending_error: None,
+            ended: false,
+        }
+    }
+
+    /// Get the session ID captured during streaming (if available).
+    pub async fn session_id(&self) -> Option<String> {
+        self.events.session_id()
+    }
+}
+
+impl Stream for RawChippStream {
+    type Item = Result<String, ChippClientError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(payload) = self.events.raw_lines.as_mut().and_then(VecDeque::pop_front) {
+                return Poll::Ready(Some(Ok(payload)));
+            }
+            if let Some(e) = self.pending_error.take() {
+                return Poll::Ready(Some(Err(e)));
+            }
+            if self.ended {
+                return Poll::Ready(None);
+            }
+
+            match self.events.poll_event(cx) {
+                Poll::Ready(Some(Ok(_))) => continue,
+                // The line that caused the error is yielded first
+                Poll::Ready(Some(Err(e))) => self.pending_error = Some(e),
+                Poll::Ready(None) => self.ended = true,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// A text stream together with the headers of the response that carries it.
+///
+/// Returned by [`ChippClient::chat_stream_with_headers`](crate::ChippClient::chat_stream_with_headers).
+/// Implements `Stream<Item = Result<String, ChippClientError>>` exactly like
+/// [`ChippStream`].
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// # use chipp::{ChippClient, ChippConfig, ChippSession, ChippMessage};
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let config = ChippConfig::default();
+/// # let client = ChippClient::new(config)?;
+/// # let mut session = ChippSession::new();
+/// let mut handle = client
+///     .chat_stream_with_headers(&mut session, &[ChippMessage::user("Hi")])
+///     .await?;
+///
+/// if let Some(remaining) = handle.headers().rate_limit_remaining() {
+///     eprintln!("{} requests left in this window", remaining);
+/// }
+///
+/// while let Some(chunk) = handle.next().await {
+///     print!("{}", chunk?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct StreamHandle {
+    /// Headers of the streaming response
+    headers: ResponseHeaders,
+    /// Text stream over the response body
+    stream: ChippStream,
+}
+
+impl StreamHandle {
+    /// Pair a stream with its response headers.
+    pub(crate) fn new(headers: ResponseHeaders, stream: ChippStream) -> Self {
+        Self { headers, stream }
+    }
+
+    /// Get the response headers.
+    #[must_use]
+    pub fn headers(&self) -> &ResponseHeaders {
+        &self.headers
+    }
+
+    /// Get the session ID captured during streaming (if available).
+    pub async fn session_id(&self) -> Option<String> {
+        self.stream.session_id().await
+    }
+
+    /// Split into the response headers and the text stream.
+    #[must_use]
+    pub fn into_parts(self) -> (ResponseHeaders, ChippStream) {
+        (self.headers, self.stream)
+    }
+}
+
+impl Stream for StreamHandle {
+    type Item = Result<String, ChippClientError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.stream).poll_next(cx)
+    }
+}
+
+/// Text stream read ahead by a background task into a bounded channel.
+///
+/// Created with
+/// [`ChippClient::chat_stream_buffered()`](crate::ChippClient::chat_stream_buffered).
+/// A spawned task reads and parses the response while up to `capacity` chunks
+/// are waiting, then stops reading from the network until the consumer takes
+/// one. Network reads are decoupled from the consumer's pace, yet memory stays
+/// bounded however slow the consumer is.
+///
+/// Dropping the stream stops the task and closes the connection.
+pub struct BufferedChippStream {
+    /// Chunks produced by the reader task
+    receiver: tokio::sync::mpsc::Receiver<Result<String, ChippClientError>>,
+    /// Metadata the reader task has captured so far
+    state: Arc<Mutex<BufferedState>>,
+    /// Reader task, aborted on drop
+    task: tokio::task::JoinHandle<()>,
+}
+
+/// Stream metadata shared between the reader task and the consumer.
+#[derive(Default)]
+struct BufferedState {
+    session_id: Option<String>,
+    usage: Option<Usage>,
+}
+
+impl std::fmt::Debug for BufferedChippStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BufferedChippStream")
+            .field("pending", &self.receiver.len())
+            .field("capacity", &self.receiver.max_capacity())
+            .finish_non_exhaustive()
+    }
+}
+
+impl BufferedChippStream {
+    /// Spawn a task that feeds `stream` into a channel holding `capacity` chunks.
+    ///
+    /// `capacity` must be non-zero.
+    pub(crate) fn spawn(mut stream: ChippStream, capacity: usize) -> Self {
+        let (sender, receiver) = tokio::sync::mpsc::channel(capacity);
+        let state = Arc::new(Mutex::new(BufferedState::default()));
+
+        let task_state = Arc::clone(&state);
+        let task = tokio::spawn(async move {
+            while let Some(item) = stream.next().await {
+                // Recorded before sending so metadata is never behind the text
+                Self::record(&task_state, &stream);
+                // Waits while the channel is full; fails once the consumer is gone
+                if sender.send(item).await.is_err() {
+                    return;
+                }
+            }
+            Self::record(&task_state, &stream);
+        });
+
+        Self {
+            receiver,
+            state,
+            task,
+        }
+    }
+
+    /// Copy the stream's captured metadata into the shared state.
+    fn record(state: &Mutex<BufferedState>, stream: &ChippStream) {
+        let mut state = state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.session_id = stream.captured_session_id();
+        state.usage = stream.final_usage();
+    }
+
+    /// Get the session ID captured during streaming (if available).
+    pub async fn session_id(&self) -> Option<String> {
+        self.lock_state().session_id.clone()
+    }
+
+    /// Get the token usage reported when the stream finished (if available).
+    #[must_use]
+    pub fn final_usage(&self) -> Option<Usage> {
+        self.lock_state().usage
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, BufferedState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Stream for BufferedChippStream {
+    type Item = Result<String, ChippClientError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+impl Drop for BufferedChippStream {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Text collected from a stream that may have failed partway through.
+///
+/// Returned by [`ChippClient::chat_stream_collect_lossy()`]. When the stream
+/// breaks after some chunks arrived, `text` holds everything received before
+/// the failure and `error` holds the failure itself.
+///
+/// [`ChippClient::chat_stream_collect_lossy()`]: crate::ChippClient::chat_stream_collect_lossy
+#[derive(Debug)]
+pub struct PartialResponse {
+    /// Text received before the stream finished or failed
+    pub text: String,
+    /// Error that ended the stream early, or `None` if it completed
+    pub error: Option<ChippClientError>,
+}
+
+impl PartialResponse {
+    /// Returns `true` if the stream finished without error.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Convert into the full text, or the error if the stream failed.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error that ended the stream early, discarding the partial text.
+    pub fn into_result(self) -> Result<String, ChippClientError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.text),
+        }
+    }
+}
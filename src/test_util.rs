@@ -0,0 +1,92 @@
+//! Helpers for testing code that uses the Chipp client against a mock server.
+//!
+//! Available with the `test-util` feature. Point these at your own mock server
+//! (e.g. `wiremock`) instead of copying config plumbing into every test suite.
+//!
+//! # Example
+//!
+//! ```
+//! use chipp::{ChippMessage, ChippSession};
+//! use wiremock::matchers::{method, path};
+//! use wiremock::{Mock, MockServer, ResponseTemplate};
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let server = MockServer::start().await;
+//! Mock::given(method("POST"))
+//!     .and(path("/chat/completions"))
+//!     .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+//!         "chatSessionId": "session-1",
+//!         "id": "chatcmpl-1",
+//!         "object": "chat.completion",
+//!         "created": 0,
+//!         "model": "test-model",
+//!         "choices": [{
+//!             "index": 0,
+//!             "message": { "role": "assistant", "content": "Hi!" },
+//!             "finish_reason": "stop"
+//!         }],
+//!         "usage": { "prompt_tokens": 1, "completion_tokens": 1, "total_tokens": 2 }
+//!     })))
+//!     .mount(&server)
+//!     .await;
+//!
+//! let client = chipp::test_util::client_for(&server.uri());
+//! let mut session = ChippSession::new();
+//! let response = client.chat(&mut session, &[ChippMessage::user("Hello")]).await.unwrap();
+//!
+//! assert_eq!(response, "Hi!");
+//! # }
+//! ```
+
+use crate::client::ChippClient;
+use crate::config::ChippConfig;
+use std::time::Duration;
+
+/// Create a test configuration pointed at `base_url`.
+///
+/// Uses a dummy API key and model, a 5 second timeout, and fast retries
+/// (3 retries, 10ms initial delay, 100ms max delay) so failure paths run quickly.
+#[must_use]
+pub fn config_for(base_url: &str) -> ChippConfig {
+    ChippConfig {
+        api_key: "test-api-key".into(),
+        base_url: base_url.to_string(),
+        model: "test-model".into(),
+        timeout: Duration::from_secs(5),
+        max_retries: 3,
+        initial_retry_delay: Duration::from_millis(10),
+        max_retry_delay: Duration::from_millis(100),
+        ..Default::default()
+    }
+}
+
+/// Create a client pointed at `base_url` using [`config_for`] defaults.
+///
+/// # Panics
+///
+/// Panics if the underlying HTTP client fails to build.
+#[must_use]
+pub fn client_for(base_url: &str) -> ChippClient {
+    client_with(base_url, |_| {})
+}
+
+/// Create a client pointed at `base_url`, letting `configure` adjust the
+/// [`config_for`] defaults first.
+///
+/// ```
+/// let client = chipp::test_util::client_with("http://127.0.0.1:9", |config| {
+///     config.max_retries = 0;
+/// });
+/// # drop(client);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the underlying HTTP client fails to build.
+#[must_use]
+pub fn client_with(base_url: &str, configure: impl FnOnce(&mut ChippConfig)) -> ChippClient {
+    let mut config = config_for(base_url);
+    configure(&mut config);
+    ChippClient::new(config).expect("Failed to create test client")
+}
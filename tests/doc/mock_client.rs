@@ -0,0 +1,37 @@
+// Fixture for the runnable doctests in `src/client.rs`, pulled in with
+// `include!` so the examples stay short.
+//
+// An async block that starts a local mock server answering plain and streaming
+// chat requests with "Hi there!", and resolves to a client pointed at it plus
+// the server, which must be kept alive for as long as the client is used.
+async {
+    use wiremock::matchers::{body_partial_json, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    let body = serde_json::json!({
+        "chatSessionId": "session-1", "id": "chatcmpl-1", "object": "chat.completion",
+        "created": 0, "model": "myapp-123",
+        "choices": [{"index": 0, "finish_reason": "stop",
+                     "message": {"role": "assistant", "content": "Hi there!"}}],
+        "usage": {"prompt_tokens": 3, "completion_tokens": 2, "total_tokens": 5}
+    });
+    Mock::given(method("POST"))
+        .and(body_partial_json(serde_json::json!({ "stream": false })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .mount(&server)
+        .await;
+    let events = concat!(
+        "data: {\"type\":\"text-delta\",\"id\":\"m\",\"delta\":\"Hi \"}\n\n",
+        "data: {\"type\":\"text-delta\",\"id\":\"m\",\"delta\":\"there!\"}\n\n",
+        "data: [DONE]\n",
+    );
+    Mock::given(method("POST"))
+        .and(body_partial_json(serde_json::json!({ "stream": true })))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(events, "text/event-stream"))
+        .mount(&server)
+        .await;
+
+    let config = chipp::ChippConfig { base_url: server.uri(), ..Default::default() };
+    (chipp::ChippClient::new(config).unwrap(), server)
+}
@@ -16,8 +16,8 @@ fn get_test_config() -> Option<ChippConfig> {
         .unwrap_or_else(|| "newapplication-10032142".to_string());
 
     Some(ChippConfig {
-        api_key,
-        model,
+        api_key: api_key.into(),
+        model: model.into(),
         ..Default::default()
     })
 }
@@ -6,32 +6,32 @@
 //! - Error handling for various failure modes
 //! - Session management
 //! - Token usage tracking (chat_detailed)
+//! - Session IDs kept in a SessionStore
+//! - Summing token usage across responses
+//! - OpenTelemetry GenAI span attributes
+//! - Detecting replies blocked by the content filter
+//! - Per-call configuration with clone_with()
+//! - Positional results from chat_batch()
+//! - Raw requests with raw_post()
 
+use super::raw_server::{start_raw_server, Step};
+use super::span_capture::CapturedSpans;
+use super::test_client::{setup_test_client, setup_test_client_with};
+use chipp::test_util::{client_for, client_with};
 use chipp::{
-    ChatResponse, ChippClient, ChippClientError, ChippConfig, ChippMessage, ChippSession,
-    MessageRole, Usage,
+    AdaptiveTimeout, CancellationToken, ChatRequest, ChatResponse, ChippClient, ChippClientError,
+    ChippConfig, ChippMessage, ChippSession, InMemorySessionStore, MessageRole, RedirectPolicy,
+    RequestOptions, SessionStore, Usage,
 };
+use futures::future::BoxFuture;
 use serde_json::json;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use wiremock::matchers::{header, method, path};
+use tracing_test::traced_test;
+use wiremock::matchers::{body_partial_json, header, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
-/// Helper to create test client with mock server
-async fn setup_test_client() -> (ChippClient, MockServer) {
-    let mock_server = MockServer::start().await;
-    let config = ChippConfig {
-        api_key: "test-api-key".to_string(),
-        base_url: mock_server.uri(),
-        model: "test-model".to_string(),
-        timeout: Duration::from_secs(5),
-        max_retries: 3,
-        initial_retry_delay: Duration::from_millis(10), // Fast retries for tests
-        max_retry_delay: Duration::from_millis(100),
-    };
-    let client = ChippClient::new(config).expect("Failed to create test client");
-    (client, mock_server)
-}
-
 /// Helper to create test messages
 fn create_test_messages() -> Vec<ChippMessage> {
     vec![ChippMessage {
@@ -221,11 +221,72 @@ async fn test_chat_max_retries_exceeded() {
     // Assert
     assert!(result.is_err(), "Expected Err, got: {:?}", result);
     match result.unwrap_err() {
-        ChippClientError::MaxRetriesExceeded(max_retries) => {
-            assert_eq!(max_retries, 3); // max_retries config value
+        ChippClientError::MaxRetriesExceeded { attempts, errors } => {
+            assert_eq!(attempts, 4); // first attempt + max_retries
+            assert_eq!(errors.len(), 4);
+        }
+        other => panic!("Expected MaxRetriesExceeded, got: {:?}", other),
+    }
+}
+
+/// Tests that MaxRetriesExceeded carries the error from every attempt
+///
+/// Arrange: Short timeout, max_retries = 2; server returns 500, then times out, then 500
+/// Act: Call chat() with test message
+/// Assert: Error lists all three failures in order, and Display summarizes them
+#[tokio::test]
+async fn test_chat_max_retries_exceeded_records_attempt_history() {
+    // Arrange
+    let (client, mock_server) = setup_test_client_with(|config| {
+        config.timeout = Duration::from_millis(200);
+        config.max_retries = 2;
+    })
+    .await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("Internal Server Error"))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(create_success_response("Too late", "session-1"))
+                .set_delay(Duration::from_secs(2)),
+        )
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("Still broken"))
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let result = client
+        .chat(&mut ChippSession::new(), &create_test_messages())
+        .await;
+
+    // Assert
+    let error = result.unwrap_err();
+    let message = error.to_string();
+    match error {
+        ChippClientError::MaxRetriesExceeded { attempts, errors } => {
+            assert_eq!(attempts, 3);
+            assert_eq!(errors.len(), 3);
+            assert!(errors[0].starts_with("attempt 1: "), "{:?}", errors);
+            assert!(errors[0].contains("500"), "{:?}", errors);
+            assert!(errors[1].starts_with("attempt 2: "), "{:?}", errors);
+            assert!(errors[1].contains("HTTP request failed"), "{:?}", errors);
+            assert!(errors[2].contains("Still broken"), "{:?}", errors);
         }
         other => panic!("Expected MaxRetriesExceeded, got: {:?}", other),
     }
+    assert!(message.contains("after 3 attempts"), "{}", message);
+    assert!(message.contains("Still broken"), "{}", message);
 }
 
 /// Tests that chat() returns immediately on non-retryable 4xx error
@@ -611,3 +672,3436 @@ async fn test_chat_backward_compatibility() {
     // Session should still be updated
     assert_eq!(session.chat_session_id, Some("session-compat".to_string()));
 }
+
+// =============================================================================
+// Accept-Language Tests
+// =============================================================================
+
+/// Tests that a configured locale is sent as the Accept-Language header
+///
+/// Arrange: Client configured with locale "fr-FR"
+/// Act: Call chat()
+/// Assert: Request carries Accept-Language: fr-FR
+#[tokio::test]
+async fn test_chat_sends_accept_language_when_locale_configured() {
+    // Arrange
+    let (client, mock_server) =
+        setup_test_client_with(|config| config.locale = Some("fr-FR".to_string())).await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(header("Accept-Language", "fr-FR"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(create_success_response("Bonjour!", "s-1")),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let result = client.chat(&mut session, &create_test_messages()).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), "Bonjour!");
+}
+
+/// Tests that no Accept-Language header is sent when no locale is configured
+///
+/// Arrange: Default test client (no locale)
+/// Act: Call chat()
+/// Assert: Received request has no Accept-Language header
+#[tokio::test]
+async fn test_chat_omits_accept_language_by_default() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(create_success_response("Hello", "s-1")),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    client
+        .chat(&mut session, &create_test_messages())
+        .await
+        .unwrap();
+
+    // Assert
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 1);
+    assert!(!requests[0].headers.contains_key("accept-language"));
+}
+
+/// Tests that each retry attempt carries its own attempt number and request ID
+///
+/// Arrange: Mock server fails twice, then succeeds
+/// Act: Call chat()
+/// Assert: Three requests with X-Attempt 1, 2, 3, one shared X-Correlation-ID,
+///         and X-Request-ID `{correlation_id}-{attempt}`
+#[tokio::test]
+async fn test_retry_attempts_carry_attempt_headers() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(2)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(create_success_response("Third time", "s-1")),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let result = client.chat(&mut session, &create_test_messages()).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), "Third time");
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 3);
+    let header_value = |request: &wiremock::Request, name: &str| {
+        request.headers[name].to_str().unwrap().to_string()
+    };
+    let correlation_id = header_value(&requests[0], "x-correlation-id");
+    for (index, request) in requests.iter().enumerate() {
+        let attempt = index + 1;
+        assert_eq!(header_value(request, "x-correlation-id"), correlation_id);
+        assert_eq!(header_value(request, "x-attempt"), attempt.to_string());
+        assert_eq!(
+            header_value(request, "x-request-id"),
+            format!("{}-{}", correlation_id, attempt)
+        );
+    }
+}
+
+/// Tests that a per-request locale overrides the configured one
+///
+/// Arrange: Client configured with locale "en-US"
+/// Act: Call chat_detailed_with() with locale "ja-JP"
+/// Assert: Request carries Accept-Language: ja-JP
+#[tokio::test]
+async fn test_chat_detailed_with_locale_override() {
+    // Arrange
+    let (client, mock_server) =
+        setup_test_client_with(|config| config.locale = Some("en-US".to_string())).await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(header("Accept-Language", "ja-JP"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(create_success_response("こんにちは", "s-1")),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+    let options = RequestOptions {
+        locale: Some("ja-JP".to_string()),
+        ..Default::default()
+    };
+
+    // Act
+    let response = client
+        .chat_detailed_with(&mut session, &create_test_messages(), options)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(response.content(), "こんにちは");
+}
+
+/// Tests that an invalid per-request locale is rejected before sending
+///
+/// Arrange: Mock server that must not be called
+/// Act: Call chat_detailed_with() with an empty locale
+/// Assert: Returns ConfigError
+#[tokio::test]
+async fn test_chat_detailed_with_invalid_locale_returns_config_error() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+    let options = RequestOptions {
+        locale: Some(String::new()),
+        ..Default::default()
+    };
+
+    // Act
+    let result = client
+        .chat_detailed_with(&mut session, &create_test_messages(), options)
+        .await;
+
+    // Assert
+    assert!(matches!(result, Err(ChippClientError::ConfigError(_))));
+}
+
+/// Tests that timeout and model overrides apply together on one call
+///
+/// Arrange: Client with a 100ms timeout and no retries; server answers after
+///          300ms and only matches the overridden model
+/// Act: Call chat_detailed_with() with a 5s timeout and model "other-model"
+/// Assert: Response succeeds (both overrides were used)
+#[tokio::test]
+async fn test_chat_detailed_with_timeout_and_model_overrides() {
+    // Arrange
+    let (client, mock_server) = setup_test_client_with(|config| {
+        config.timeout = Duration::from_millis(100);
+        config.max_retries = 0;
+    })
+    .await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(body_partial_json(json!({"model": "other-model"})))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(create_success_response("Slow but fine", "s-1"))
+                .set_delay(Duration::from_millis(300)),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+    let options = RequestOptions {
+        timeout: Some(Duration::from_secs(5)),
+        model: Some("other-model".into()),
+        ..Default::default()
+    };
+
+    // Act
+    let response = client
+        .chat_detailed_with(&mut session, &create_test_messages(), options)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(response.content(), "Slow but fine");
+}
+
+/// Tests that an all-default options call matches chat_detailed()
+///
+/// Arrange: Mock server returning a fixed response
+/// Act: Call chat_detailed() and chat_detailed_with(RequestOptions::default())
+/// Assert: Same response content and identical request bodies
+#[tokio::test]
+async fn test_chat_detailed_with_default_options_matches_chat_detailed() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(create_success_response("Same", "s-1")),
+        )
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let detailed = client
+        .chat_detailed(&mut ChippSession::new(), &create_test_messages())
+        .await
+        .unwrap();
+    let with_defaults = client
+        .chat_detailed_with(
+            &mut ChippSession::new(),
+            &create_test_messages(),
+            RequestOptions::default(),
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(detailed.content(), with_defaults.content());
+    let requests = mock_server.received_requests().await.unwrap();
+    let body = |request: &wiremock::Request| -> serde_json::Value {
+        serde_json::from_slice(&request.body).unwrap()
+    };
+    assert_eq!(body(&requests[0]), body(&requests[1]));
+}
+
+/// Tests that headers, sampling and correlation ID overrides reach the wire
+///
+/// Arrange: Mock server matching the extra header, correlation ID and body fields
+/// Act: Call chat_detailed_with() with those overrides set
+/// Assert: Request matched
+#[tokio::test]
+async fn test_chat_detailed_with_sends_headers_sampling_and_correlation_id() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(header("X-Tenant", "acme"))
+        .and(header("X-Correlation-ID", "trace-42"))
+        .and(body_partial_json(
+            json!({"temperature": 0.5, "max_tokens": 64}),
+        ))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(create_success_response("Tuned", "s-1")),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+    let options = RequestOptions {
+        headers: Some(vec![("X-Tenant".to_string(), "acme".to_string())]),
+        temperature: Some(0.5),
+        max_tokens: Some(64),
+        correlation_id: Some("trace-42".to_string()),
+        ..Default::default()
+    };
+
+    // Act
+    let response = client
+        .chat_detailed_with(&mut session, &create_test_messages(), options)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(response.content(), "Tuned");
+}
+
+/// Tests that a shared correlation ID doesn't make separate calls share an idempotency key
+///
+/// Arrange: Mock server returning success
+/// Act: Call chat_detailed_with() twice with the same correlation_id
+/// Assert: Both carry the correlation ID, but the idempotency keys differ
+#[tokio::test]
+async fn test_chat_detailed_with_correlation_id_is_not_idempotency_key() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(header("X-Correlation-ID", "trace-42"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(create_success_response("Hi!", "s-1")),
+        )
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let options = RequestOptions {
+        correlation_id: Some("trace-42".to_string()),
+        ..Default::default()
+    };
+
+    // Act
+    for _ in 0..2 {
+        client
+            .chat_detailed_with(
+                &mut ChippSession::new(),
+                &create_test_messages(),
+                options.clone(),
+            )
+            .await
+            .unwrap();
+    }
+
+    // Assert
+    let keys: Vec<String> = mock_server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|request| {
+            request.headers["Idempotency-Key"]
+                .to_str()
+                .unwrap()
+                .to_string()
+        })
+        .collect();
+    assert_ne!(keys[0], "trace-42");
+    assert_ne!(keys[0], keys[1]);
+}
+
+/// Tests that a user header replaces the built-in header of the same name
+///
+/// Arrange: Mock server returning success
+/// Act: Call chat_detailed_with() with an Authorization header in headers
+/// Assert: Exactly one Authorization header is sent, with the user's value
+#[tokio::test]
+async fn test_chat_detailed_with_header_replaces_authorization() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(create_success_response("Hi!", "s-1")),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let options = RequestOptions {
+        headers: Some(vec![(
+            "Authorization".to_string(),
+            "Bearer other-key".to_string(),
+        )]),
+        ..Default::default()
+    };
+
+    // Act
+    client
+        .chat_detailed_with(&mut ChippSession::new(), &create_test_messages(), options)
+        .await
+        .unwrap();
+
+    // Assert
+    let requests = mock_server.received_requests().await.unwrap();
+    let values: Vec<_> = requests[0]
+        .headers
+        .get_all("authorization")
+        .iter()
+        .map(|value| value.to_str().unwrap().to_string())
+        .collect();
+    assert_eq!(values, vec!["Bearer other-key".to_string()]);
+}
+
+/// Tests that a per-request max_retries overrides the configured one
+///
+/// Arrange: Client configured with 3 retries; server always returns 500
+/// Act: Call chat_detailed_with() with max_retries Some(0)
+/// Assert: Only one attempt is made
+#[tokio::test]
+async fn test_chat_detailed_with_max_retries_override() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+    let options = RequestOptions {
+        max_retries: Some(0),
+        ..Default::default()
+    };
+
+    // Act
+    let result = client
+        .chat_detailed_with(&mut session, &create_test_messages(), options)
+        .await;
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(ChippClientError::MaxRetriesExceeded { attempts: 1, .. })
+    ));
+}
+
+/// Tests that invalid header and temperature overrides are rejected before sending
+///
+/// Arrange: Mock server that must not be called
+/// Act: Call chat_detailed_with() with a bad header name, then a negative temperature
+/// Assert: Both return ConfigError
+#[tokio::test]
+async fn test_chat_detailed_with_invalid_overrides_return_config_error() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let bad_header = RequestOptions {
+        headers: Some(vec![("bad header".to_string(), "x".to_string())]),
+        ..Default::default()
+    };
+    let bad_temperature = RequestOptions {
+        temperature: Some(-1.0),
+        ..Default::default()
+    };
+
+    for options in [bad_header, bad_temperature] {
+        // Act
+        let result = client
+            .chat_detailed_with(&mut ChippSession::new(), &create_test_messages(), options)
+            .await;
+
+        // Assert
+        assert!(matches!(result, Err(ChippClientError::ConfigError(_))));
+    }
+}
+
+/// Tests that chat() requests a non-streaming response in the body
+///
+/// Arrange: Mock server only matches bodies with `"stream": false`
+/// Act: Call chat()
+/// Assert: Request matched (stream flag was unset)
+#[tokio::test]
+async fn test_chat_sends_stream_false() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(body_partial_json(json!({ "stream": false })))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(create_success_response("Hi", "s-1")),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let result = client.chat(&mut session, &create_test_messages()).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), "Hi");
+}
+
+// =============================================================================
+// Message Prefix Tests
+// =============================================================================
+
+/// Tests that the configured message prefix is sent before the caller's messages
+///
+/// Arrange: Client with a two-turn few-shot prefix
+/// Act: Call chat() with a system message and a user message
+/// Assert: Body order is system, prefix turns, then the user message
+#[tokio::test]
+async fn test_chat_inserts_message_prefix_after_system_messages() {
+    // Arrange
+    let (client, mock_server) = setup_test_client_with(|config| {
+        config.message_prefix = vec![
+            ChippMessage::user("Example question"),
+            ChippMessage::assistant("Example answer"),
+        ];
+    })
+    .await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(create_success_response("Answer", "s-1")),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+    let messages = vec![
+        ChippMessage::system("Be concise."),
+        ChippMessage::user("Real question"),
+    ];
+
+    // Act
+    client.chat(&mut session, &messages).await.unwrap();
+
+    // Assert
+    let requests = mock_server.received_requests().await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(
+        body["messages"],
+        json!([
+            { "role": "system", "content": "Be concise." },
+            { "role": "user", "content": "Example question" },
+            { "role": "assistant", "content": "Example answer" },
+            { "role": "user", "content": "Real question" }
+        ])
+    );
+}
+
+/// Tests that chat_detailed() tolerates a response without a usage object
+///
+/// Arrange: Mock server returns a valid response with no `usage` field
+/// Act: Call chat_detailed()
+/// Assert: Content is returned and usage is zeroed
+#[tokio::test]
+async fn test_chat_detailed_missing_usage_defaults_to_zero() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "chatSessionId": "session-no-usage",
+            "id": "chatcmpl-no-usage",
+            "object": "chat.completion",
+            "created": 1234567890,
+            "model": "test-model",
+            "choices": [{
+                "index": 0,
+                "message": { "role": "assistant", "content": "Still here" },
+                "finish_reason": "stop"
+            }]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let response = client
+        .chat_detailed(&mut session, &create_test_messages())
+        .await
+        .expect("Missing usage should not fail the request");
+
+    // Assert
+    assert_eq!(response.content(), "Still here");
+    assert_eq!(response.usage(), &Usage::default());
+    assert_eq!(response.usage().total_tokens, 0);
+}
+
+// =============================================================================
+// Strict Response Parsing Tests
+// =============================================================================
+
+/// Helper to create a response with an extra, unknown top-level field
+fn create_response_with_extra_field() -> serde_json::Value {
+    let mut body = create_success_response("Hello", "session-strict");
+    body["system_fingerprint"] = json!("fp_123");
+    body
+}
+
+/// Tests that unknown response fields are ignored by default
+///
+/// Arrange: Mock server returns a response with an extra field
+/// Act: Call chat() with default (lenient) parsing
+/// Assert: Request succeeds
+#[tokio::test]
+async fn test_chat_lenient_parsing_ignores_unknown_fields() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(create_response_with_extra_field()))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let result = client.chat(&mut session, &create_test_messages()).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), "Hello");
+}
+
+/// Tests that unknown response fields are rejected in strict mode
+///
+/// Arrange: Strict client, mock server returns a response with an extra field
+/// Act: Call chat()
+/// Assert: Returns InvalidResponse naming the unknown field
+#[tokio::test]
+async fn test_chat_strict_parsing_rejects_unknown_fields() {
+    // Arrange
+    let (client, mock_server) =
+        setup_test_client_with(|config| config.strict_response_parsing = true).await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(create_response_with_extra_field()))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let result = client.chat(&mut session, &create_test_messages()).await;
+
+    // Assert
+    match result.unwrap_err() {
+        ChippClientError::InvalidResponse(msg) => {
+            assert!(msg.contains("system_fingerprint"), "Got: {}", msg);
+        }
+        other => panic!("Expected InvalidResponse, got: {:?}", other),
+    }
+}
+
+// =============================================================================
+// Retry Override Tests
+// =============================================================================
+
+/// Tests that no_retries() makes exactly one attempt
+///
+/// Arrange: Mock server always returns 500
+/// Act: Call chat() on a no_retries() client
+/// Assert: Exactly one request is made
+#[tokio::test]
+async fn test_no_retries_makes_single_attempt() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("Internal Server Error"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let result = client
+        .no_retries()
+        .chat(&mut session, &create_test_messages())
+        .await;
+
+    // Assert
+    assert!(result.is_err(), "Expected Err, got: {:?}", result);
+}
+
+/// Tests that with_max_retries() changes the attempt count without affecting the original
+///
+/// Arrange: Mock server always returns 500; original client has 3 retries
+/// Act: Call chat() on a with_max_retries(1) client
+/// Assert: Exactly two requests are made
+#[tokio::test]
+async fn test_with_max_retries_overrides_retry_count() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("Internal Server Error"))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let result = client
+        .with_max_retries(1)
+        .chat(&mut session, &create_test_messages())
+        .await;
+
+    // Assert
+    match result.unwrap_err() {
+        ChippClientError::MaxRetriesExceeded { attempts, .. } => assert_eq!(attempts, 2),
+        other => panic!("Expected MaxRetriesExceeded, got: {:?}", other),
+    }
+}
+
+/// Tests that attempts(1) makes exactly one request
+///
+/// Arrange: Mock server always returns 500; client built with attempts(1)
+/// Act: Call chat()
+/// Assert: Exactly one request is made and reported as one attempt
+#[tokio::test]
+async fn test_attempts_one_makes_single_request() {
+    // Arrange
+    let mock_server = MockServer::start().await;
+    let config = ChippConfig::builder()
+        .api_key("test-api-key")
+        .model("test-model")
+        .base_url(mock_server.uri())
+        .attempts(1)
+        .build()
+        .unwrap();
+    let client = ChippClient::new(config).unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("Internal Server Error"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let result = client
+        .chat(&mut ChippSession::new(), &create_test_messages())
+        .await;
+
+    // Assert
+    match result.unwrap_err() {
+        ChippClientError::MaxRetriesExceeded { attempts, .. } => assert_eq!(attempts, 1),
+        other => panic!("Expected MaxRetriesExceeded, got: {:?}", other),
+    }
+}
+
+// =============================================================================
+// Cancellation Tests
+// =============================================================================
+
+/// Tests that cancelling during retry backoff returns Cancelled promptly
+///
+/// Arrange: Client with a long retry delay, mock server always returns 500
+/// Act: Cancel the token shortly after the first attempt fails
+/// Assert: Returns Cancelled well before the backoff would have elapsed
+#[tokio::test]
+async fn test_chat_detailed_cancellable_cancels_during_backoff() {
+    // Arrange
+    let (client, mock_server) = setup_test_client_with(|config| {
+        config.initial_retry_delay = Duration::from_secs(30);
+        config.max_retry_delay = Duration::from_secs(30);
+    })
+    .await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("Internal Server Error"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let token = CancellationToken::new();
+    let canceller = token.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        canceller.cancel();
+    });
+
+    let mut session = ChippSession::new();
+    let start = std::time::Instant::now();
+
+    // Act
+    let result = client
+        .chat_detailed_cancellable(&mut session, &create_test_messages(), &token)
+        .await;
+
+    // Assert
+    assert!(
+        matches!(result, Err(ChippClientError::Cancelled)),
+        "Expected Cancelled, got: {:?}",
+        result
+    );
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+/// Tests that an already-cancelled token stops the request before it is sent
+///
+/// Arrange: Cancelled token, mock server that must not be called
+/// Act: Call chat_detailed_cancellable()
+/// Assert: Returns Cancelled
+#[tokio::test]
+async fn test_chat_detailed_cancellable_with_cancelled_token() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let token = CancellationToken::new();
+    token.cancel();
+    let mut session = ChippSession::new();
+
+    // Act
+    let result = client
+        .chat_detailed_cancellable(&mut session, &create_test_messages(), &token)
+        .await;
+
+    // Assert
+    assert!(matches!(result, Err(ChippClientError::Cancelled)));
+}
+
+/// Tests that shutting the client down mid-retry returns Cancelled promptly
+///
+/// Arrange: Client with a long retry delay, mock server always returns 500
+/// Act: Call shutdown() on a clone shortly after the first attempt fails
+/// Assert: chat() returns Cancelled well before the backoff would have elapsed
+#[tokio::test]
+async fn test_shutdown_cancels_chat_during_backoff() {
+    // Arrange
+    let (client, mock_server) = setup_test_client_with(|config| {
+        config.initial_retry_delay = Duration::from_secs(30);
+        config.max_retry_delay = Duration::from_secs(30);
+    })
+    .await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("Internal Server Error"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let owner = client.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        owner.shutdown();
+    });
+
+    let mut session = ChippSession::new();
+    let start = std::time::Instant::now();
+
+    // Act
+    let result = client.chat(&mut session, &create_test_messages()).await;
+
+    // Assert
+    assert!(
+        matches!(result, Err(ChippClientError::Cancelled)),
+        "Expected Cancelled, got: {:?}",
+        result
+    );
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert!(client.is_shut_down());
+}
+
+/// Tests that requests made after shutdown fail without being sent
+///
+/// Arrange: Shut-down client, mock server that must not be called
+/// Act: Call chat() on the client and on a client derived from it
+/// Assert: Both return Cancelled
+#[tokio::test]
+async fn test_shutdown_rejects_new_requests() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let derived = client.no_retries();
+    client.shutdown();
+    let mut session = ChippSession::new();
+
+    // Act
+    let result = client.chat(&mut session, &create_test_messages()).await;
+    let derived_result = derived.chat(&mut session, &create_test_messages()).await;
+
+    // Assert
+    assert!(matches!(result, Err(ChippClientError::Cancelled)));
+    assert!(matches!(derived_result, Err(ChippClientError::Cancelled)));
+}
+
+/// Tests that every network entry point is rejected after shutdown
+///
+/// Arrange: Shut-down client, mock server that must not be called
+/// Act: Call ping() and raw_post()
+/// Assert: Each returns Cancelled
+#[tokio::test]
+async fn test_shutdown_rejects_other_entry_points() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(wiremock::matchers::any())
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    client.shutdown();
+
+    // Act
+    let pinged = client.ping().await;
+    let posted = client.raw_post("chat/completions", json!({})).await;
+
+    // Assert
+    assert!(matches!(pinged, Err(ChippClientError::Cancelled)));
+    assert!(matches!(posted, Err(ChippClientError::Cancelled)));
+}
+
+// =============================================================================
+// Message Content Logging Tests
+// =============================================================================
+
+/// Message body that must never appear in logs unless explicitly enabled
+const SECRET_MESSAGE: &str = "my-secret-account-number-4242";
+
+/// Response body that must never appear in logs unless explicitly enabled
+const SECRET_REPLY: &str = "your-secret-balance-is-1337";
+
+/// Helper to create a client with message content logging set explicitly,
+/// where the first request fails so the retry path is also exercised
+async fn setup_logging_client(log_message_content: bool) -> (ChippClient, MockServer) {
+    let (client, mock_server) =
+        setup_test_client_with(|config| config.log_message_content = log_message_content).await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(create_success_response(SECRET_REPLY, "session-log")),
+        )
+        .mount(&mock_server)
+        .await;
+
+    (client, mock_server)
+}
+
+/// Tests that message content is never logged when log_message_content is off
+///
+/// Arrange: Default client (logging off), server fails once then succeeds
+/// Act: Call chat() with a secret message
+/// Assert: Logs were captured, but neither the request nor response content appears
+#[tokio::test]
+#[traced_test]
+async fn test_chat_does_not_log_message_content_by_default() {
+    // Arrange
+    let (client, _mock_server) = setup_logging_client(false).await;
+    let mut session = ChippSession::new();
+
+    // Act
+    let result = client
+        .chat(&mut session, &[ChippMessage::user(SECRET_MESSAGE)])
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), SECRET_REPLY);
+    assert!(
+        logs_contain("Retrying"),
+        "Expected retry logs to be captured"
+    );
+    assert!(!logs_contain(SECRET_MESSAGE));
+    assert!(!logs_contain(SECRET_REPLY));
+}
+
+/// Tests that message content is logged at debug level when enabled
+///
+/// Arrange: Client with log_message_content = true
+/// Act: Call chat() with a secret message
+/// Assert: Both the request and response content appear in the logs
+#[tokio::test]
+#[traced_test]
+async fn test_chat_logs_message_content_when_enabled() {
+    // Arrange
+    let (client, _mock_server) = setup_logging_client(true).await;
+    let mut session = ChippSession::new();
+
+    // Act
+    let result = client
+        .chat(&mut session, &[ChippMessage::user(SECRET_MESSAGE)])
+        .await;
+
+    // Assert
+    assert!(result.is_ok(), "Expected Ok, got: {:?}", result);
+    assert!(logs_contain(SECRET_MESSAGE));
+    assert!(logs_contain(SECRET_REPLY));
+}
+
+// =============================================================================
+// Reusable Request Tests
+// =============================================================================
+
+/// Tests that a ChatRequest can be sent twice and honours its model override
+///
+/// Arrange: ChatRequest with a model override, mock server expecting that model twice
+/// Act: Send the same request twice
+/// Assert: Both sends succeed and the request is unchanged
+#[tokio::test]
+async fn test_send_reuses_chat_request() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(body_partial_json(json!({
+            "model": "other-app",
+            "messages": [{"role": "user", "content": "Hello"}],
+        })))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(create_success_response("Hi!", "session-1")),
+        )
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let request = ChatRequest::new(create_test_messages()).with_model("other-app");
+
+    // Act
+    let first = client.send(&request, &mut ChippSession::new()).await;
+    let second = client.send(&request, &mut ChippSession::new()).await;
+
+    // Assert
+    assert_eq!(first.unwrap().content(), "Hi!");
+    assert_eq!(second.unwrap().content(), "Hi!");
+    assert_eq!(request.messages.len(), 1);
+}
+
+/// Tests that an empty model override is rejected before sending
+///
+/// Arrange: ChatRequest with an empty model override
+/// Act: Send the request
+/// Assert: Returns ConfigError
+#[tokio::test]
+async fn test_send_rejects_empty_model_override() {
+    // Arrange
+    let (client, _mock_server) = setup_test_client().await;
+    let request = ChatRequest::new(create_test_messages()).with_model("");
+
+    // Act
+    let result = client.send(&request, &mut ChippSession::new()).await;
+
+    // Assert
+    assert!(matches!(result, Err(ChippClientError::ConfigError(_))));
+}
+
+/// Tests that a request that timed out can be re-sent with a longer timeout
+///
+/// Arrange: Mock server that replies after 300ms, ChatRequest with a 100ms timeout
+/// Act: Send the request, then retry_with() a 2s timeout
+/// Assert: The first send fails; the retry succeeds with the same messages
+#[tokio::test]
+async fn test_retry_with_resends_request_with_longer_timeout() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+    let client = client.no_retries();
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(body_partial_json(json!({
+            "messages": [{"role": "user", "content": "Hello"}],
+        })))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(create_success_response("Worth the wait", "session-1"))
+                .set_delay(Duration::from_millis(300)),
+        )
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let request = ChatRequest::new(create_test_messages()).with_timeout(Duration::from_millis(100));
+    let mut session = ChippSession::new();
+
+    // Act
+    let first = client.send(&request, &mut session).await;
+    let retried = client
+        .retry_with(&request, &mut session, Duration::from_secs(2))
+        .await;
+
+    // Assert
+    assert!(
+        matches!(
+            first,
+            Err(ChippClientError::MaxRetriesExceeded { attempts: 1, .. })
+        ),
+        "Expected the short timeout to fail, got: {:?}",
+        first
+    );
+    assert_eq!(retried.unwrap().content(), "Worth the wait");
+    assert_eq!(session.id(), Some("session-1"));
+    assert_eq!(request.options.timeout, Some(Duration::from_millis(100)));
+}
+
+/// Tests that a zero per-request timeout is rejected before sending
+///
+/// Arrange: ChatRequest with a zero timeout
+/// Act: Send the request
+/// Assert: Returns ConfigError
+#[tokio::test]
+async fn test_send_rejects_zero_timeout_override() {
+    // Arrange
+    let (client, _mock_server) = setup_test_client().await;
+    let request = ChatRequest::new(create_test_messages()).with_timeout(Duration::ZERO);
+
+    // Act
+    let result = client.send(&request, &mut ChippSession::new()).await;
+
+    // Assert
+    assert!(matches!(result, Err(ChippClientError::ConfigError(_))));
+}
+
+// =============================================================================
+// Idempotency Key Tests
+// =============================================================================
+
+/// Tests that retries reuse one idempotency key and a new call gets a fresh one
+///
+/// Arrange: Mock server fails twice, then succeeds
+/// Act: Call chat() (retried) and then chat() again
+/// Assert: The retried attempts share a key; the second call's key differs
+#[tokio::test]
+async fn test_idempotency_key_stable_across_retries() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(2)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(create_success_response("Hi!", "session-1")),
+        )
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    client
+        .chat(&mut ChippSession::new(), &create_test_messages())
+        .await
+        .expect("Retried call should succeed");
+    client
+        .chat(&mut ChippSession::new(), &create_test_messages())
+        .await
+        .expect("Second call should succeed");
+
+    // Assert
+    let keys: Vec<String> = mock_server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|request| {
+            request.headers["Idempotency-Key"]
+                .to_str()
+                .unwrap()
+                .to_string()
+        })
+        .collect();
+
+    assert_eq!(keys.len(), 4);
+    assert_eq!(keys[0], keys[1]);
+    assert_eq!(keys[1], keys[2]);
+    assert_ne!(keys[2], keys[3]);
+}
+
+/// Tests that no idempotency key is sent when disabled
+///
+/// Arrange: Client with idempotency_key_header = None
+/// Act: Call chat()
+/// Assert: Request has no Idempotency-Key header
+#[tokio::test]
+async fn test_idempotency_key_not_sent_when_disabled() {
+    // Arrange
+    let (client, mock_server) =
+        setup_test_client_with(|config| config.idempotency_key_header = None).await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(create_success_response("Hi!", "session-1")),
+        )
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    client
+        .chat(&mut ChippSession::new(), &create_test_messages())
+        .await
+        .expect("Call should succeed");
+
+    // Assert
+    let requests = mock_server.received_requests().await.unwrap();
+    assert!(!requests[0].headers.contains_key("Idempotency-Key"));
+}
+
+// =============================================================================
+// Rate Limit Retry Tests
+// =============================================================================
+
+/// Helper to create a client with a small general retry budget and a larger 429 budget
+async fn setup_rate_limit_client(rate_limit_max_retries: usize) -> (ChippClient, MockServer) {
+    setup_test_client_with(|config| {
+        config.max_retries = 1;
+        config.rate_limit_max_retries = Some(rate_limit_max_retries);
+    })
+    .await
+}
+
+/// Tests that 429s use the rate limit budget instead of max_retries
+///
+/// Arrange: max_retries = 1, rate_limit_max_retries = 5; server returns five 429s then 200
+/// Act: Call chat()
+/// Assert: Succeeds after six attempts
+#[tokio::test]
+async fn test_rate_limit_retries_are_counted_separately() {
+    // Arrange
+    let (client, mock_server) = setup_rate_limit_client(5).await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+        .up_to_n_times(5)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(create_success_response("Hi!", "session-1")),
+        )
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let result = client
+        .chat(&mut ChippSession::new(), &create_test_messages())
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), "Hi!");
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 6);
+}
+
+/// Tests that a huge Retry-After is capped at max_retry_delay
+///
+/// Arrange: max_retry_delay = 100ms; server returns one 429 with Retry-After: 86400, then 200
+/// Act: Call chat() with a 5s test deadline
+/// Assert: Succeeds well within the deadline
+#[tokio::test]
+async fn test_rate_limit_retry_after_capped_at_max_retry_delay() {
+    // Arrange
+    let (client, mock_server) = setup_rate_limit_client(1).await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "86400"))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(create_success_response("Hi!", "session-1")),
+        )
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let result = tokio::time::timeout(
+        Duration::from_secs(5),
+        client.chat(&mut ChippSession::new(), &create_test_messages()),
+    )
+    .await
+    .expect("Retry-After should be capped, not waited out");
+
+    // Assert
+    assert_eq!(result.unwrap(), "Hi!");
+}
+
+/// Tests that exhausting the rate limit budget returns MaxRetriesExceeded
+///
+/// Arrange: rate_limit_max_retries = 2; server always returns 429 without Retry-After
+/// Act: Call chat()
+/// Assert: Returns MaxRetriesExceeded after three attempts
+#[tokio::test]
+async fn test_rate_limit_retries_exhausted() {
+    // Arrange
+    let (client, mock_server) = setup_rate_limit_client(2).await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(429))
+        .expect(3)
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let result = client
+        .chat(&mut ChippSession::new(), &create_test_messages())
+        .await;
+
+    // Assert
+    assert!(
+        matches!(
+            result,
+            Err(ChippClientError::MaxRetriesExceeded { attempts: 3, .. })
+        ),
+        "Expected MaxRetriesExceeded after 3 attempts, got: {:?}",
+        result
+    );
+}
+
+// =============================================================================
+// Malformed Usage Tests
+// =============================================================================
+
+/// Tests that out-of-range token counts are clamped instead of failing the response
+///
+/// Arrange: Mock server returns negative completion_tokens and oversized total_tokens
+/// Act: Call chat_detailed()
+/// Assert: Content comes through; counts are clamped to 0 and u32::MAX
+#[tokio::test]
+async fn test_chat_detailed_clamps_out_of_range_usage() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    let mut body = create_success_response("Still here", "session-1");
+    body["usage"] = json!({
+        "prompt_tokens": 10,
+        "completion_tokens": -1,
+        "total_tokens": 99_999_999_999u64,
+    });
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let result = client
+        .chat_detailed(&mut ChippSession::new(), &create_test_messages())
+        .await;
+
+    // Assert
+    let response = result.expect("Malformed usage should not fail the response");
+    assert_eq!(response.content(), "Still here");
+    assert_eq!(
+        response.usage(),
+        &Usage {
+            prompt_tokens: 10,
+            completion_tokens: 0,
+            total_tokens: u32::MAX,
+        }
+    );
+}
+
+// =============================================================================
+// Empty Body Tests
+// =============================================================================
+
+/// Tests that a success status with an empty JSON body yields EmptyResponse
+///
+/// Arrange: Mock server answers a streaming request with 200, JSON content type, no body
+/// Act: Call chat_stream() (the JSON fallback is not retried)
+/// Assert: Returns EmptyResponse rather than a JSON parse error
+#[tokio::test]
+async fn test_empty_success_body_returns_empty_response() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw("", "application/json"))
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let result = client
+        .chat_stream(&mut ChippSession::new(), &create_test_messages())
+        .await;
+
+    // Assert
+    assert!(
+        matches!(result, Err(ChippClientError::EmptyResponse)),
+        "Expected EmptyResponse, got: {:?}",
+        result
+    );
+}
+
+/// Tests that an empty 204 response is retried like other transient failures
+///
+/// Arrange: Mock server returns 204 once, then a valid completion
+/// Act: Call chat()
+/// Assert: Succeeds after retrying
+#[tokio::test]
+async fn test_chat_retries_empty_response() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(204))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(create_success_response("Back!", "session-1")),
+        )
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let result = client
+        .chat(&mut ChippSession::new(), &create_test_messages())
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), "Back!");
+}
+
+// =============================================================================
+// Truncated Body Tests
+// =============================================================================
+
+/// Tests that a body cut off mid-document yields TruncatedResponse
+///
+/// Arrange: Mock server answers a streaming request with half a JSON document
+/// Act: Call chat_stream() (the JSON fallback is not retried)
+/// Assert: Returns TruncatedResponse rather than InvalidResponse
+#[tokio::test]
+async fn test_truncated_body_returns_truncated_response() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+    let full = create_success_response("Hello", "session-1").to_string();
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_raw(&full[..full.len() / 2], "application/json"),
+        )
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let result = client
+        .chat_stream(&mut ChippSession::new(), &create_test_messages())
+        .await;
+
+    // Assert
+    assert!(
+        matches!(result, Err(ChippClientError::TruncatedResponse(_))),
+        "Expected TruncatedResponse, got: {:?}",
+        result.map(|_| ())
+    );
+}
+
+/// Tests that a truncated body is retried like other transient failures
+///
+/// Arrange: Mock server returns half a JSON document once, then the full one
+/// Act: Call chat()
+/// Assert: Succeeds after retrying
+#[tokio::test]
+async fn test_chat_retries_truncated_body() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+    let full = create_success_response("Complete", "session-1").to_string();
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_raw(&full[..full.len() / 2], "application/json"),
+        )
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(full, "application/json"))
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let result = client
+        .chat(&mut ChippSession::new(), &create_test_messages())
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), "Complete");
+}
+
+// =============================================================================
+// Session Reset Tests
+// =============================================================================
+
+/// Tests that an expired session is reset and the request retried without its ID
+///
+/// Arrange: auto_reset_invalid_session enabled; server 404s for the stale session
+///          and succeeds for a request without chatSessionId
+/// Act: Call chat_detailed() with a stale session
+/// Assert: Response is returned and the session holds the new ID
+#[tokio::test]
+async fn test_auto_reset_invalid_session_retries_without_id() {
+    // Arrange
+    let (client, mock_server) =
+        setup_test_client_with(|config| config.auto_reset_invalid_session = true).await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(body_partial_json(json!({"chatSessionId": "stale-session"})))
+        .respond_with(ResponseTemplate::new(404).set_body_string("Session not found"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(create_success_response("Fresh start", "new-session")),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::with_id("stale-session");
+
+    // Act
+    let result = client
+        .chat_detailed(&mut session, &create_test_messages())
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap().content(), "Fresh start");
+    assert_eq!(session.chat_session_id.as_deref(), Some("new-session"));
+    let requests = mock_server.received_requests().await.unwrap();
+    let retry: serde_json::Value = serde_json::from_slice(&requests[1].body).unwrap();
+    assert!(retry.get("chatSessionId").is_none());
+}
+
+/// Tests that a session-not-found error is returned unchanged when auto reset is off
+///
+/// Arrange: Default client (auto reset disabled, no retries); server 404s
+/// Act: Call chat_detailed() with a stale session
+/// Assert: Request fails and the session ID is kept
+#[tokio::test]
+async fn test_invalid_session_not_reset_by_default() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+    let client = client.no_retries();
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("Session not found"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::with_id("stale-session");
+
+    // Act
+    let result = client
+        .chat_detailed(&mut session, &create_test_messages())
+        .await;
+
+    // Assert
+    assert!(result.is_err());
+    assert_eq!(session.chat_session_id.as_deref(), Some("stale-session"));
+}
+
+/// Tests that a 404 that doesn't name an unknown session leaves the session alone
+///
+/// Arrange: auto_reset_invalid_session enabled; server 404s with a generic body
+/// Act: Call chat_detailed() with a session ID
+/// Assert: Request fails after one attempt and the session ID is kept
+#[tokio::test]
+async fn test_auto_reset_ignores_unrelated_not_found() {
+    // Arrange
+    let (client, mock_server) =
+        setup_test_client_with(|config| config.auto_reset_invalid_session = true).await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("Not Found"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::with_id("live-session");
+
+    // Act
+    let result = client
+        .chat_detailed(&mut session, &create_test_messages())
+        .await;
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(ChippClientError::ApiError { status: 404, .. })
+    ));
+    assert_eq!(session.chat_session_id.as_deref(), Some("live-session"));
+}
+
+// =============================================================================
+// Session ID Continuity Tests
+// =============================================================================
+
+/// Tests that the session ID survives responses that echo or omit it
+///
+/// Arrange: Server returns session-1, then session-1 again, then no session ID
+/// Act: Call chat_detailed() three times with the same session
+/// Assert: The first call sets the ID and later calls keep it, with
+///         ChatResponse::session_id() matching the session each time
+#[tokio::test]
+async fn test_chat_session_id_continuity() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(create_success_response("First", "session-1")),
+        )
+        .up_to_n_times(2)
+        .mount(&mock_server)
+        .await;
+
+    let mut without_id = create_success_response("Third", "unused");
+    without_id.as_object_mut().unwrap().remove("chatSessionId");
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(without_id))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+    let messages = create_test_messages();
+
+    // Act & Assert - First call sets the ID
+    let first = client.chat_detailed(&mut session, &messages).await.unwrap();
+    assert_eq!(session.chat_session_id.as_deref(), Some("session-1"));
+    assert_eq!(first.session_id(), "session-1");
+
+    // Act & Assert - Second call echoes the same ID
+    let second = client.chat_detailed(&mut session, &messages).await.unwrap();
+    assert_eq!(session.chat_session_id.as_deref(), Some("session-1"));
+    assert_eq!(second.session_id(), "session-1");
+
+    // Act & Assert - Third call has no ID, so the prior one is kept
+    let third = client.chat_detailed(&mut session, &messages).await.unwrap();
+    assert_eq!(third.content(), "Third");
+    assert_eq!(session.chat_session_id.as_deref(), Some("session-1"));
+    assert_eq!(third.session_id(), "session-1");
+}
+
+/// Tests that an empty session ID in the response doesn't clear the session
+///
+/// Arrange: Session already has an ID; server returns an empty chatSessionId
+/// Act: Call chat_detailed()
+/// Assert: Session keeps its ID and the response reports it
+#[tokio::test]
+async fn test_chat_empty_session_id_keeps_existing() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(create_success_response("Hi", "")))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::with_id("existing-session");
+
+    // Act
+    let response = client
+        .chat_detailed(&mut session, &create_test_messages())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(session.chat_session_id.as_deref(), Some("existing-session"));
+    assert_eq!(response.session_id(), "existing-session");
+}
+
+// =============================================================================
+// Retry Predicate Tests
+// =============================================================================
+
+/// Helper to create a client that won't retry 503s reporting a disabled app
+async fn setup_should_retry_client() -> (ChippClient, MockServer) {
+    setup_test_client_with(|config| {
+        config.should_retry = Some(Arc::new(|error: &ChippClientError| match error {
+            ChippClientError::ApiError { status, message } => {
+                *status >= 500 && !message.contains("disabled")
+            }
+            _ => false,
+        }));
+    })
+    .await
+}
+
+/// Tests that should_retry can decline a failure the built-in rule would retry
+///
+/// Arrange: Predicate rejects 503s mentioning "disabled"; server returns one
+/// Act: Call chat()
+/// Assert: The 503 is returned after a single attempt
+#[tokio::test]
+async fn test_should_retry_declines_disabled_app() {
+    // Arrange
+    let (client, mock_server) = setup_should_retry_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(503).set_body_string("App disabled by owner"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let result = client
+        .chat(&mut ChippSession::new(), &create_test_messages())
+        .await;
+
+    // Assert
+    assert!(
+        matches!(result, Err(ChippClientError::ApiError { status: 503, .. })),
+        "Expected 503 ApiError, got: {:?}",
+        result
+    );
+}
+
+/// Tests that failures the predicate accepts are still retried
+///
+/// Arrange: Same predicate; server returns a deploy-time 503 once, then succeeds
+/// Act: Call chat()
+/// Assert: Succeeds after retrying
+#[tokio::test]
+async fn test_should_retry_allows_transient_503() {
+    // Arrange
+    let (client, mock_server) = setup_should_retry_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(503).set_body_string("Deploy in progress"))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(create_success_response("Up!", "session-1")),
+        )
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let result = client
+        .chat(&mut ChippSession::new(), &create_test_messages())
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), "Up!");
+}
+
+// =============================================================================
+// Transcript Tests
+// =============================================================================
+
+/// Tests that chat_in_session() records every turn in the session transcript
+///
+/// Arrange: Mock server answers two requests
+/// Act: Call chat_in_session() twice with the same session
+/// Assert: Transcript holds 2 user and 2 assistant turns, with usage on the assistant turns
+#[tokio::test]
+async fn test_chat_in_session_records_transcript() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(create_success_response("First", "session-1")),
+        )
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(create_success_response("Second", "session-1")),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    client
+        .chat_in_session(&mut session, &[ChippMessage::user("One")])
+        .await
+        .unwrap();
+    client
+        .chat_in_session(&mut session, &[ChippMessage::user("Two")])
+        .await
+        .unwrap();
+
+    // Assert
+    let turns = &session.transcript().turns;
+    assert_eq!(turns.len(), 4);
+
+    let summary: Vec<(MessageRole, &str)> = turns
+        .iter()
+        .map(|turn| (turn.role.clone(), turn.content.as_str()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (MessageRole::User, "One"),
+            (MessageRole::Assistant, "First"),
+            (MessageRole::User, "Two"),
+            (MessageRole::Assistant, "Second"),
+        ]
+    );
+
+    for turn in turns {
+        match turn.role {
+            MessageRole::Assistant => assert_eq!(turn.usage.as_ref().unwrap().total_tokens, 15),
+            _ => assert!(turn.usage.is_none()),
+        }
+        assert!(turn.timestamp > 0);
+    }
+
+    let json: serde_json::Value = serde_json::from_str(&session.transcript().to_json()).unwrap();
+    assert_eq!(json["turns"].as_array().unwrap().len(), 4);
+}
+
+// =============================================================================
+// Redirect Tests
+// =============================================================================
+
+/// Helper to start a server that redirects to `target` with a 307, and a client pointed at it
+async fn setup_redirect_client(
+    target: &MockServer,
+    redirect_policy: RedirectPolicy,
+) -> (ChippClient, MockServer) {
+    let (client, redirecting_server) = setup_test_client_with(|config| {
+        config.max_retries = 0;
+        config.redirect_policy = redirect_policy;
+    })
+    .await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(307)
+                .insert_header("Location", format!("{}/chat/completions", target.uri())),
+        )
+        .mount(&redirecting_server)
+        .await;
+
+    (client, redirecting_server)
+}
+
+/// Tests that LimitedWithAuth follows a same-origin 307 with the API key
+///
+/// Arrange: Server 307-redirects to another path on itself that requires the Authorization header
+/// Act: Call chat() with RedirectPolicy::LimitedWithAuth
+/// Assert: The redirect is followed with auth and body intact
+#[tokio::test]
+async fn test_redirect_with_auth_preserves_authorization_same_origin() {
+    // Arrange
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(307).insert_header(
+            "Location",
+            format!("{}/v2/chat/completions", mock_server.uri()),
+        ))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v2/chat/completions"))
+        .and(header("Authorization", "Bearer test-api-key"))
+        .and(body_partial_json(json!({"model": "test-model"})))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(create_success_response("Moved hello", "session-1")),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = client_with(&mock_server.uri(), |config| {
+        config.max_retries = 0;
+        config.redirect_policy = RedirectPolicy::LimitedWithAuth(3);
+    });
+
+    // Act
+    let result = client
+        .chat(&mut ChippSession::new(), &create_test_messages())
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), "Moved hello");
+}
+
+/// Tests that LimitedWithAuth doesn't send the API key to another origin
+///
+/// Arrange: One server 307-redirects to a second server on another port
+/// Act: Call chat() with RedirectPolicy::LimitedWithAuth
+/// Assert: The redirect isn't followed; the 307 is the attempt's error
+#[tokio::test]
+async fn test_redirect_with_auth_refuses_cross_origin() {
+    // Arrange
+    let regional_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(create_success_response("Regional hello", "session-1")),
+        )
+        .expect(0)
+        .mount(&regional_server)
+        .await;
+
+    let (client, _redirecting_server) =
+        setup_redirect_client(&regional_server, RedirectPolicy::LimitedWithAuth(3)).await;
+
+    // Act
+    let result = client
+        .chat(&mut ChippSession::new(), &create_test_messages())
+        .await;
+
+    // Assert
+    match result {
+        Err(ChippClientError::MaxRetriesExceeded {
+            attempts: 1,
+            errors,
+        }) => {
+            assert!(errors[0].contains("307"), "Unexpected error: {:?}", errors);
+        }
+        other => panic!(
+            "Expected the redirect as the only failure, got: {:?}",
+            other
+        ),
+    }
+}
+
+/// Tests that the default policy does not forward the API key to another host
+///
+/// Arrange: One server 307-redirects to a second that only answers authorized requests
+/// Act: Call chat() with the default RedirectPolicy
+/// Assert: The redirect is followed but without the Authorization header
+#[tokio::test]
+async fn test_default_redirect_drops_authorization_across_hosts() {
+    // Arrange
+    let regional_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(header("Authorization", "Bearer test-api-key"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(create_success_response("Regional hello", "session-1")),
+        )
+        .expect(0)
+        .mount(&regional_server)
+        .await;
+
+    let (client, _redirecting_server) =
+        setup_redirect_client(&regional_server, RedirectPolicy::default()).await;
+
+    // Act
+    let result = client
+        .chat(&mut ChippSession::new(), &create_test_messages())
+        .await;
+
+    // Assert
+    assert!(result.is_err(), "Expected Err, got: {:?}", result);
+    let requests = regional_server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 1);
+    assert!(!requests[0].headers.contains_key("authorization"));
+}
+
+// =============================================================================
+// HTML Interstitial Tests
+// =============================================================================
+
+/// Tests that an HTML page on a 200 yields an actionable error
+///
+/// Arrange: Mock server returns a proxy login page with status 200
+/// Act: Call chat()
+/// Assert: Returns InvalidResponse naming HTML, proxy/auth and the page's first line
+#[tokio::test]
+async fn test_chat_html_body_returns_clear_error() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(
+            "\n<!DOCTYPE html>\n<html><body>Corporate SSO login</body></html>",
+            "text/html; charset=utf-8",
+        ))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let result = client
+        .chat(&mut ChippSession::new(), &create_test_messages())
+        .await;
+
+    // Assert
+    match result.unwrap_err() {
+        ChippClientError::InvalidResponse(msg) => {
+            assert!(msg.contains("received HTML instead of JSON"), "{}", msg);
+            assert!(msg.contains("check proxy/auth"), "{}", msg);
+            assert!(msg.contains("<!DOCTYPE html>"), "{}", msg);
+        }
+        other => panic!("Expected InvalidResponse, got: {:?}", other),
+    }
+}
+
+/// Tests that HTML is detected from the body even with a JSON content type
+///
+/// Arrange: Mock server returns an HTML page labelled application/json
+/// Act: Call chat()
+/// Assert: Returns the HTML InvalidResponse rather than a JSON parse error
+#[tokio::test]
+async fn test_chat_html_body_detected_without_html_content_type() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(
+            "<html><head><title>Sign in</title></head></html>",
+            "application/json",
+        ))
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let result = client
+        .chat(&mut ChippSession::new(), &create_test_messages())
+        .await;
+
+    // Assert
+    match result.unwrap_err() {
+        ChippClientError::InvalidResponse(msg) => {
+            assert!(msg.contains("received HTML instead of JSON"), "{}", msg);
+            assert!(msg.contains("<title>Sign in</title>"), "{}", msg);
+        }
+        other => panic!("Expected InvalidResponse, got: {:?}", other),
+    }
+}
+
+/// Tests that a streaming request answered with HTML fails instead of yielding nothing
+///
+/// Arrange: Mock server returns an HTML page with status 200
+/// Act: Call chat_stream()
+/// Assert: Returns the HTML InvalidResponse
+#[tokio::test]
+async fn test_chat_stream_html_body_returns_clear_error() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_raw("<html>Please log in</html>", "text/html"),
+        )
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let result = client
+        .chat_stream(&mut ChippSession::new(), &create_test_messages())
+        .await;
+
+    // Assert
+    assert!(
+        matches!(&result, Err(ChippClientError::InvalidResponse(msg)) if msg.contains("HTML")),
+        "Expected HTML InvalidResponse, got: {:?}",
+        result.map(|_| ())
+    );
+}
+
+// =============================================================================
+// Read-Only Session Tests
+// =============================================================================
+
+/// Tests that chat_detailed_readonly() sends the session ID but never writes it back
+///
+/// Arrange: Session has an ID; server expects it and answers with a new one
+/// Act: Call chat_detailed_readonly()
+/// Assert: Session is unchanged and the response carries the server's ID
+#[tokio::test]
+async fn test_chat_detailed_readonly_leaves_session_unchanged() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(body_partial_json(
+            json!({"chatSessionId": "canonical-session"}),
+        ))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(create_success_response(
+                "Maybe this?",
+                "speculative-session",
+            )),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let session = ChippSession::with_id("canonical-session");
+
+    // Act
+    let response = client
+        .chat_detailed_readonly(&session, &create_test_messages())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(response.content(), "Maybe this?");
+    assert_eq!(response.session_id(), "speculative-session");
+    assert_eq!(session.id(), Some("canonical-session"));
+}
+
+// =============================================================================
+// Adaptive Timeout Tests
+// =============================================================================
+
+/// Tests that the adaptive timeout replaces the fixed timeout once it has samples
+///
+/// Arrange: Fixed timeout of 5s, adaptive timeout floored at 150ms with factor 1;
+///          the first response is fast, the second takes 1s
+/// Act: Send two requests without retries
+/// Assert: The first succeeds; the second times out under the learned timeout
+#[tokio::test]
+async fn test_adaptive_timeout_applies_learned_timeout() {
+    // Arrange
+    let (client, mock_server) = setup_test_client_with(|config| {
+        config.timeout = Duration::from_secs(5);
+        config.max_retries = 0;
+        config.adaptive_timeout = Some(AdaptiveTimeout {
+            min: Duration::from_millis(150),
+            factor: 1.0,
+            window: 10,
+        });
+    })
+    .await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(create_success_response("Fast", "session-1")),
+        )
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(create_success_response("Slow", "session-1"))
+                .set_delay(Duration::from_secs(1)),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let first = client.chat(&mut session, &create_test_messages()).await;
+    let second = client.chat(&mut session, &create_test_messages()).await;
+
+    // Assert
+    assert_eq!(first.unwrap(), "Fast");
+    match second {
+        Err(ChippClientError::MaxRetriesExceeded { errors, .. }) => {
+            assert!(errors[0].contains("HTTP request failed"), "{:?}", errors);
+        }
+        other => panic!("Expected a timeout, got {:?}", other),
+    }
+}
+
+// =============================================================================
+// Request Size Guard Tests
+// =============================================================================
+
+/// Tests that an oversized request body is rejected before anything is sent
+///
+/// Arrange: max_request_bytes of 256, mock server expecting no requests
+/// Act: Call chat() and chat_stream() with a 10 KB message
+/// Assert: Both fail with RequestTooLarge carrying the actual size and the limit
+#[tokio::test]
+async fn test_oversized_request_rejected_before_sending() {
+    // Arrange
+    let (client, mock_server) =
+        setup_test_client_with(|config| config.max_request_bytes = Some(256)).await;
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let messages = vec![ChippMessage::user("x".repeat(10_000))];
+    let mut session = ChippSession::new();
+
+    // Act
+    let chat_result = client.chat(&mut session, &messages).await;
+    let stream_result = client.chat_stream(&mut session, &messages).await;
+
+    // Assert
+    match chat_result {
+        Err(ChippClientError::RequestTooLarge { size, limit }) => {
+            assert!(size > 10_000, "size {} should include the message", size);
+            assert_eq!(limit, 256);
+        }
+        other => panic!("Expected RequestTooLarge, got {:?}", other),
+    }
+    assert!(matches!(
+        stream_result,
+        Err(ChippClientError::RequestTooLarge { limit: 256, .. })
+    ));
+}
+
+/// Tests that a request within max_request_bytes is sent normally
+///
+/// Arrange: max_request_bytes of 64 KB, mock server returning success
+/// Act: Call chat() with a short message
+/// Assert: The response is returned
+#[tokio::test]
+async fn test_request_within_limit_is_sent() {
+    // Arrange
+    let (client, mock_server) =
+        setup_test_client_with(|config| config.max_request_bytes = Some(64 * 1024)).await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(header("Content-Type", "application/json"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(create_success_response("Hi", "session-1")),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let result = client.chat(&mut session, &create_test_messages()).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), "Hi");
+}
+
+// =============================================================================
+// JSON Reply Tests
+// =============================================================================
+
+/// Structured reply used by the chat_json() tests
+#[derive(Debug, PartialEq, serde::Deserialize)]
+struct Sentiment {
+    label: String,
+    score: f64,
+}
+
+/// Helper to mount a mock replying with `content`
+async fn mount_reply(mock_server: &MockServer, content: &str) {
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(create_success_response(content, "session-json")),
+        )
+        .mount(mock_server)
+        .await;
+}
+
+/// Tests that chat_json() parses a bare JSON reply
+///
+/// Arrange: Mock server replying with unfenced JSON
+/// Act: Call chat_json::<Sentiment>()
+/// Assert: The struct is parsed and the session ID is updated
+#[tokio::test]
+async fn test_chat_json_parses_bare_reply() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+    mount_reply(&mock_server, r#"{"label": "positive", "score": 0.9}"#).await;
+    let mut session = ChippSession::new();
+
+    // Act
+    let result: Result<Sentiment, _> = client
+        .chat_json(&mut session, &create_test_messages())
+        .await;
+
+    // Assert
+    assert_eq!(
+        result.unwrap(),
+        Sentiment {
+            label: "positive".to_string(),
+            score: 0.9,
+        }
+    );
+    assert_eq!(session.chat_session_id, Some("session-json".to_string()));
+}
+
+/// Tests that chat_json() unwraps markdown code fences before parsing
+///
+/// Arrange: Mock servers replying with tagged, untagged and single-line fences
+/// Act: Call chat_json::<Sentiment>() against each
+/// Assert: Every reply parses to the same struct
+#[tokio::test]
+async fn test_chat_json_strips_code_fences() {
+    for reply in [
+        "```json\n{\"label\": \"negative\", \"score\": 0.2}\n```",
+        "```\n{\"label\": \"negative\", \"score\": 0.2}\n```\n",
+        "  ```JSON\n{\"label\": \"negative\", \"score\": 0.2}```  ",
+    ] {
+        // Arrange
+        let (client, mock_server) = setup_test_client().await;
+        mount_reply(&mock_server, reply).await;
+        let mut session = ChippSession::new();
+
+        // Act
+        let result: Result<Sentiment, _> = client
+            .chat_json(&mut session, &create_test_messages())
+            .await;
+
+        // Assert
+        assert_eq!(
+            result.unwrap(),
+            Sentiment {
+                label: "negative".to_string(),
+                score: 0.2,
+            },
+            "reply: {:?}",
+            reply
+        );
+    }
+}
+
+/// Tests that chat_json() reports malformed JSON with the raw reply
+///
+/// Arrange: Mock server replying with text that isn't JSON
+/// Act: Call chat_json::<Sentiment>()
+/// Assert: InvalidResponse is returned and its message includes the raw reply
+#[tokio::test]
+async fn test_chat_json_rejects_malformed_reply() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+    mount_reply(&mock_server, "I think it's positive!").await;
+    let mut session = ChippSession::new();
+
+    // Act
+    let result: Result<Sentiment, _> = client
+        .chat_json(&mut session, &create_test_messages())
+        .await;
+
+    // Assert
+    match result {
+        Err(ChippClientError::InvalidResponse(message)) => {
+            assert!(message.contains("I think it's positive!"), "{}", message);
+        }
+        other => panic!("Expected InvalidResponse, got {:?}", other),
+    }
+}
+
+// =============================================================================
+// Empty Completion Tests
+// =============================================================================
+
+/// Tests that blank content is reported by ChatResponse::is_empty()
+///
+/// Arrange: Mock servers replying with "", whitespace only, and real text
+/// Act: Call chat_detailed() against each with the default config
+/// Assert: The blank replies are returned as is and report is_empty()
+#[tokio::test]
+async fn test_chat_response_is_empty_for_blank_content() {
+    for (content, expected_empty) in [("", true), (" \n\t ", true), (" Hi ", false)] {
+        // Arrange
+        let (client, mock_server) = setup_test_client().await;
+        mount_reply(&mock_server, content).await;
+        let mut session = ChippSession::new();
+
+        // Act
+        let response = client
+            .chat_detailed(&mut session, &create_test_messages())
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.content(), content);
+        assert_eq!(
+            response.is_empty(),
+            expected_empty,
+            "content: {:?}",
+            content
+        );
+    }
+}
+
+/// Tests that reject_empty_completions turns blank "stop" replies into errors
+///
+/// Arrange: Client with reject_empty_completions, replies of "" and whitespace only
+/// Act: Call chat() against each
+/// Assert: InvalidResponse("empty completion") is returned, without retrying
+#[tokio::test]
+async fn test_reject_empty_completions_fails_blank_stop_reply() {
+    for content in ["", "   \n"] {
+        // Arrange
+        let (client, mock_server) =
+            setup_test_client_with(|config| config.reject_empty_completions = true).await;
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(create_success_response(content, "session-empty")),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let mut session = ChippSession::new();
+
+        // Act
+        let result = client.chat(&mut session, &create_test_messages()).await;
+
+        // Assert
+        match result {
+            Err(ChippClientError::InvalidResponse(message)) => {
+                assert_eq!(message, "empty completion");
+            }
+            other => panic!(
+                "Expected InvalidResponse for {:?}, got {:?}",
+                content, other
+            ),
+        }
+    }
+}
+
+/// Tests that a rejected empty completion leaves the session untouched
+///
+/// Arrange: Client with reject_empty_completions, sessions with and without an ID,
+///          blank reply carrying a new session ID
+/// Act: Call chat() with each session
+/// Assert: The call fails and each session keeps its original ID
+#[tokio::test]
+async fn test_reject_empty_completions_leaves_session_unchanged() {
+    for mut session in [ChippSession::new(), ChippSession::with_id("session-kept")] {
+        // Arrange
+        let (client, mock_server) =
+            setup_test_client_with(|config| config.reject_empty_completions = true).await;
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(create_success_response("", "session-rejected")),
+            )
+            .mount(&mock_server)
+            .await;
+        let original_id = session.id().map(str::to_string);
+
+        // Act
+        let result = client.chat(&mut session, &create_test_messages()).await;
+
+        // Assert
+        assert!(matches!(result, Err(ChippClientError::InvalidResponse(_))));
+        assert_eq!(session.id(), original_id.as_deref());
+    }
+}
+
+/// Tests that reject_empty_completions leaves blank non-"stop" replies alone
+///
+/// Arrange: Client with reject_empty_completions, blank reply with finish_reason "length"
+/// Act: Call chat_detailed()
+/// Assert: The blank response is returned
+#[tokio::test]
+async fn test_reject_empty_completions_ignores_other_finish_reasons() {
+    // Arrange
+    let (client, mock_server) =
+        setup_test_client_with(|config| config.reject_empty_completions = true).await;
+
+    let mut body = create_success_response("", "session-length");
+    body["choices"][0]["finish_reason"] = json!("length");
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .mount(&mock_server)
+        .await;
+    let mut session = ChippSession::new();
+
+    // Act
+    let response = client
+        .chat_detailed(&mut session, &create_test_messages())
+        .await
+        .unwrap();
+
+    // Assert
+    assert!(response.is_empty());
+    assert_eq!(response.finish_reason(), "length");
+}
+
+// =============================================================================
+// Empty Message Tests
+// =============================================================================
+
+/// Tests that a blank user message is rejected before sending
+///
+/// Arrange: Mock server that must not be called; user messages "" and whitespace only
+/// Act: Call chat() and chat_stream() with each
+/// Assert: ConfigError("empty user message") is returned without a request
+#[tokio::test]
+async fn test_empty_user_message_rejected_before_sending() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(400))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    for content in ["", "  \n\t"] {
+        let messages = [
+            ChippMessage::system("Be brief."),
+            ChippMessage::user(content),
+        ];
+        let mut session = ChippSession::new();
+
+        // Act
+        let chat = client.chat(&mut session, &messages).await;
+        let stream = client.chat_stream(&mut session, &messages).await;
+
+        // Assert
+        match chat {
+            Err(ChippClientError::ConfigError(msg)) => assert_eq!(msg, "empty user message"),
+            other => panic!("Expected ConfigError, got: {:?}", other),
+        }
+        assert!(matches!(stream, Err(ChippClientError::ConfigError(_))));
+    }
+}
+
+/// Tests that an empty system message is allowed
+///
+/// Arrange: Mock server replying normally; an empty system message and a user message
+/// Act: Call chat()
+/// Assert: The request is sent and succeeds
+#[tokio::test]
+async fn test_empty_system_message_allowed() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+    mount_reply(&mock_server, "Hi!").await;
+    let messages = [ChippMessage::system(""), ChippMessage::user("Hello")];
+
+    // Act
+    let result = client.chat(&mut ChippSession::new(), &messages).await;
+
+    // Assert
+    assert_eq!(result.unwrap(), "Hi!");
+}
+
+/// Tests that reject_empty_messages(false) lets a blank user message through
+///
+/// Arrange: Client with reject_empty_messages disabled; mock server returning 400
+/// Act: Call chat() with an empty user message
+/// Assert: The request is sent and the server's 400 is returned
+#[tokio::test]
+async fn test_reject_empty_messages_disabled_sends_request() {
+    // Arrange
+    let (client, mock_server) =
+        setup_test_client_with(|config| config.reject_empty_messages = false).await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(400).set_body_string("content must not be empty"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let result = client
+        .chat(&mut ChippSession::new(), &[ChippMessage::user("")])
+        .await;
+
+    // Assert
+    assert!(
+        matches!(result, Err(ChippClientError::ApiError { status: 400, .. })),
+        "Expected ApiError 400, got: {:?}",
+        result
+    );
+}
+
+// =============================================================================
+// Deadline Tests
+// =============================================================================
+
+/// Tests that an already-expired deadline fails without sending a request
+///
+/// Arrange: Mock server that must not be called; a deadline in the past
+/// Act: Call chat_detailed_until()
+/// Assert: Returns DeadlineExceeded carrying the deadline immediately
+#[tokio::test]
+async fn test_chat_detailed_until_expired_deadline_returns_deadline_exceeded() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let deadline = std::time::Instant::now() - Duration::from_millis(1);
+
+    // Act
+    let result = client
+        .chat_detailed_until(&mut ChippSession::new(), &create_test_messages(), deadline)
+        .await;
+
+    // Assert
+    match result {
+        Err(ChippClientError::DeadlineExceeded {
+            deadline: reported,
+            partial,
+        }) => {
+            assert_eq!(reported, deadline);
+            assert!(partial.is_empty());
+        }
+        other => panic!("Expected DeadlineExceeded, got: {:?}", other),
+    }
+}
+
+/// Tests that a generous deadline lets the request complete normally
+///
+/// Arrange: Mock server replying at once; a deadline 5 seconds away
+/// Act: Call chat_detailed_until()
+/// Assert: The response and session ID are returned
+#[tokio::test]
+async fn test_chat_detailed_until_generous_deadline_succeeds() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+    mount_reply(&mock_server, "In time").await;
+    let deadline = std::time::Instant::now() + Duration::from_secs(5);
+    let mut session = ChippSession::new();
+
+    // Act
+    let result = client
+        .chat_detailed_until(&mut session, &create_test_messages(), deadline)
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap().content(), "In time");
+    assert!(session.id().is_some());
+}
+
+/// Tests that the deadline bounds the whole call, not just one attempt
+///
+/// Arrange: Mock server replying after 1s; a deadline 200ms away
+/// Act: Call chat_detailed_until()
+/// Assert: Returns DeadlineExceeded well before the server would have replied
+#[tokio::test]
+async fn test_chat_detailed_until_times_out_slow_reply() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(create_success_response("Too late", "session-1"))
+                .set_delay(Duration::from_secs(1)),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let start = std::time::Instant::now();
+    let deadline = start + Duration::from_millis(200);
+
+    // Act
+    let result = client
+        .chat_detailed_until(&mut ChippSession::new(), &create_test_messages(), deadline)
+        .await;
+
+    // Assert
+    assert!(
+        matches!(result, Err(ChippClientError::DeadlineExceeded { .. })),
+        "Expected DeadlineExceeded, got: {:?}",
+        result
+    );
+    assert!(start.elapsed() < Duration::from_millis(800));
+}
+
+// =============================================================================
+// Session Store Tests
+// =============================================================================
+
+/// Map-backed SessionStore that counts writes
+#[derive(Default)]
+struct MapSessionStore {
+    ids: Mutex<HashMap<String, String>>,
+    writes: Mutex<usize>,
+}
+
+impl SessionStore for MapSessionStore {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<String>> {
+        Box::pin(async move { self.ids.lock().unwrap().get(key).cloned() })
+    }
+
+    fn set<'a>(&'a self, key: &'a str, session_id: String) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            self.ids.lock().unwrap().insert(key.to_string(), session_id);
+            *self.writes.lock().unwrap() += 1;
+        })
+    }
+}
+
+/// Tests that chat_with_session_store() persists the session ID across calls
+///
+/// Arrange: Map-backed store; server assigns an ID, then expects it on the next call
+/// Act: Call chat_with_session_store() twice for one key, once for another
+/// Assert: The stored ID is sent back; each key has its own ID; unchanged IDs aren't rewritten
+#[tokio::test]
+async fn test_chat_with_session_store_persists_session_id() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+    let store = MapSessionStore::default();
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(body_partial_json(json!({"chatSessionId": "session-alice"})))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(create_success_response("Welcome back", "session-alice")),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(create_success_response("Hello", "session-alice")),
+        )
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(create_success_response("Hi", "session-bob")),
+        )
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let first = client
+        .chat_with_session_store(&store, "alice", &create_test_messages())
+        .await
+        .unwrap();
+    let second = client
+        .chat_with_session_store(&store, "alice", &create_test_messages())
+        .await
+        .unwrap();
+    let other = client
+        .chat_with_session_store(&store, "bob", &create_test_messages())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(first, "Hello");
+    assert_eq!(second, "Welcome back");
+    assert_eq!(other, "Hi");
+    assert_eq!(store.get("alice").await, Some("session-alice".to_string()));
+    assert_eq!(store.get("bob").await, Some("session-bob".to_string()));
+    assert_eq!(*store.writes.lock().unwrap(), 2);
+}
+
+/// Tests that a failed request leaves the store untouched
+///
+/// Arrange: InMemorySessionStore, server returning 400
+/// Act: Call chat_with_session_store()
+/// Assert: The error is returned and nothing was stored
+#[tokio::test]
+async fn test_chat_with_session_store_does_not_write_on_error() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+    let store = InMemorySessionStore::new();
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(400).set_body_string("Bad Request"))
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let result = client
+        .chat_with_session_store(&store, "alice", &create_test_messages())
+        .await;
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(ChippClientError::ApiError { status: 400, .. })
+    ));
+    assert!(store.is_empty());
+}
+
+// =============================================================================
+// Backoff Reset Tests
+// =============================================================================
+
+/// Tests that each chat() call starts its backoff from initial_retry_delay
+///
+/// Arrange: initial_retry_delay of 20ms without jitter; server fails twice, succeeds,
+///          fails once, succeeds
+/// Act: Call chat() twice in sequence
+/// Assert: The first call waits 20ms then 40ms; the second call's only retry waits
+///         20ms again, not the doubled delay the first call had reached
+#[tokio::test]
+#[traced_test]
+async fn test_backoff_starts_fresh_for_each_call() {
+    // Arrange
+    let (client, mock_server) = setup_test_client_with(|config| {
+        config.initial_retry_delay = Duration::from_millis(20);
+        config.max_retry_delay = Duration::from_secs(1);
+        config.retry_jitter = 0.0;
+    })
+    .await;
+
+    for (status, times) in [(503, 2), (200, 1), (503, 1), (200, 1)] {
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(
+                ResponseTemplate::new(status)
+                    .set_body_json(create_success_response("Recovered", "session-backoff")),
+            )
+            .up_to_n_times(times)
+            .expect(times)
+            .mount(&mock_server)
+            .await;
+    }
+
+    // Act
+    let mut session = ChippSession::new();
+    let first = client.chat(&mut session, &create_test_messages()).await;
+    let second = client.chat(&mut session, &create_test_messages()).await;
+
+    // Assert
+    assert_eq!(first.unwrap(), "Recovered");
+    assert_eq!(second.unwrap(), "Recovered");
+    logs_assert(|lines: &[&str]| {
+        let delays: Vec<u64> = lines
+            .iter()
+            .filter(|line| line.contains("Retrying"))
+            .filter_map(|line| line.split("delay_ms=").nth(1))
+            .filter_map(|rest| rest.split_whitespace().next()?.parse().ok())
+            .collect();
+        if delays == [20, 40, 20] {
+            Ok(())
+        } else {
+            Err(format!(
+                "expected retry delays [20, 40, 20], got {:?}",
+                delays
+            ))
+        }
+    });
+}
+
+// =============================================================================
+// Usage Aggregation Tests
+// =============================================================================
+
+/// Tests that token usage can be summed across responses without cloning
+///
+/// Arrange: Mock server returning two responses with different usage
+/// Act: Call chat_detailed() twice and sum usage() through an iterator chain
+/// Assert: Each count is the sum of both responses
+#[tokio::test]
+async fn test_usage_sums_across_responses() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    for (completion_id, prompt_tokens, completion_tokens) in [("c-1", 10, 5), ("c-2", 20, 8)] {
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(create_full_response(
+                    "Counted",
+                    "session-usage",
+                    completion_id,
+                    prompt_tokens,
+                    completion_tokens,
+                )),
+            )
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+    }
+
+    let mut session = ChippSession::new();
+    let mut responses: Vec<ChatResponse> = Vec::new();
+    for _ in 0..2 {
+        let response = client
+            .chat_detailed(&mut session, &create_test_messages())
+            .await
+            .unwrap();
+        responses.push(response);
+    }
+
+    // Act
+    let total = responses
+        .iter()
+        .map(ChatResponse::usage)
+        .copied()
+        .sum::<Usage>();
+
+    // Assert
+    assert_eq!(total.prompt_tokens, 30);
+    assert_eq!(total.completion_tokens, 13);
+    assert_eq!(total.total_tokens, 43);
+}
+
+// =============================================================================
+// Base URL Normalization Tests
+// =============================================================================
+
+/// Tests that a trailing slash on base_url doesn't change the request URL
+///
+/// Arrange: Two directly-constructed clients, with and without a trailing slash
+/// Act: Call chat() through each
+/// Assert: Both requests hit exactly /chat/completions
+#[tokio::test]
+async fn test_trailing_slash_base_url_builds_same_url() {
+    // Arrange
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(create_success_response("Hi!", "session-1")),
+        )
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let clients = [format!("{}/", mock_server.uri()), mock_server.uri()]
+        .map(|base_url| client_with(&base_url, |config| config.max_retries = 0));
+
+    // Act
+    for client in &clients {
+        let result = client
+            .chat(&mut ChippSession::new(), &create_test_messages())
+            .await;
+        assert_eq!(result.unwrap(), "Hi!");
+    }
+
+    // Assert
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].url, requests[1].url);
+    assert_eq!(requests[0].url.path(), "/chat/completions");
+}
+
+// =============================================================================
+// OpenTelemetry Semantic Convention Tests
+// =============================================================================
+
+/// Helper to mount a completion reporting 12 prompt and 7 completion tokens
+async fn mount_otel_reply(mock_server: &MockServer) {
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(create_full_response(
+                "Hi",
+                "session-otel",
+                "chatcmpl-otel",
+                12,
+                7,
+            )),
+        )
+        .mount(mock_server)
+        .await;
+}
+
+/// Tests that GenAI convention fields are recorded when enabled
+///
+/// Arrange: Client with otel_semantic_conventions = true
+/// Act: Call chat_detailed()
+/// Assert: One gen_ai.chat span carries the request and response values
+#[tokio::test]
+async fn test_otel_semantic_conventions_recorded_when_enabled() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+    let client = client
+        .clone_with(|config| config.otel_semantic_conventions = true)
+        .unwrap();
+    mount_otel_reply(&mock_server).await;
+    let mut session = ChippSession::new();
+    let (spans, _guard) = CapturedSpans::named("gen_ai.chat");
+
+    // Act
+    let response = client
+        .chat_detailed(&mut session, &create_test_messages())
+        .await
+        .expect("chat should succeed");
+
+    // Assert
+    assert_eq!(response.content(), "Hi");
+    let spans = spans.fields();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+    assert_eq!(span["gen_ai.operation.name"], "chat");
+    assert_eq!(span["gen_ai.system"], "chipp");
+    assert_eq!(span["gen_ai.request.model"], "test-model");
+    assert_eq!(span["gen_ai.response.model"], "test-model");
+    assert_eq!(span["gen_ai.usage.input_tokens"], "12");
+    assert_eq!(span["gen_ai.usage.output_tokens"], "7");
+    assert_eq!(span["gen_ai.response.finish_reasons"], r#"["stop"]"#);
+}
+
+/// Tests that GenAI convention fields are left out by default
+///
+/// Arrange: Client with the default config
+/// Act: Call chat_detailed()
+/// Assert: No gen_ai.chat span is opened
+#[tokio::test]
+async fn test_otel_semantic_conventions_absent_by_default() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+    mount_otel_reply(&mock_server).await;
+    let mut session = ChippSession::new();
+    let (spans, _guard) = CapturedSpans::named("gen_ai.chat");
+
+    // Act
+    client
+        .chat_detailed(&mut session, &create_test_messages())
+        .await
+        .expect("chat should succeed");
+
+    // Assert
+    assert!(spans.fields().is_empty());
+}
+
+/// Tests that streaming calls get a GenAI span updated with the streamed usage
+///
+/// Arrange: Client with otel_semantic_conventions = true; server streams a finish event
+/// Act: Drain chat_stream()
+/// Assert: One gen_ai.chat span carries the request model and the reported usage
+#[tokio::test]
+async fn test_otel_semantic_conventions_recorded_for_streams() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+    let client = client
+        .clone_with(|config| config.otel_semantic_conventions = true)
+        .unwrap();
+    let body = concat!(
+        "data: {\"type\":\"text-delta\",\"id\":\"m\",\"delta\":\"Hi\"}\n\n",
+        "data: {\"type\":\"finish\",\"usage\":{\"promptTokens\":12,\"completionTokens\":7}}\n\n",
+        "data: [DONE]\n",
+    );
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(&mock_server)
+        .await;
+    let mut session = ChippSession::new();
+    let (spans, _guard) = CapturedSpans::named("gen_ai.chat");
+
+    // Act
+    use futures::StreamExt;
+    let mut stream = client
+        .chat_stream(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start");
+    while let Some(chunk) = stream.next().await {
+        chunk.unwrap();
+    }
+
+    // Assert
+    let spans = spans.fields();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+    assert_eq!(span["gen_ai.operation.name"], "chat");
+    assert_eq!(span["gen_ai.request.model"], "test-model");
+    assert_eq!(span["gen_ai.usage.input_tokens"], "12");
+    assert_eq!(span["gen_ai.usage.output_tokens"], "7");
+}
+
+// =============================================================================
+// Content Filter Tests
+// =============================================================================
+
+/// Helper to mount a reply blocked by the content filter
+async fn mount_filtered_reply(mock_server: &MockServer) {
+    let mut body = create_success_response("I can't help with that.", "session-filtered");
+    body["choices"][0]["finish_reason"] = json!("content_filter");
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .expect(1)
+        .mount(mock_server)
+        .await;
+}
+
+/// Tests that a filtered reply is returned and detectable by default
+///
+/// Arrange: Server replies with finish_reason "content_filter"
+/// Act: Call chat_detailed()
+/// Assert: The response is Ok and was_filtered() is true
+#[tokio::test]
+async fn test_filtered_reply_is_detected() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+    mount_filtered_reply(&mock_server).await;
+    let mut session = ChippSession::new();
+
+    // Act
+    let response = client
+        .chat_detailed(&mut session, &create_test_messages())
+        .await
+        .expect("filtered reply should be returned by default");
+
+    // Assert
+    assert!(response.was_filtered());
+    assert_eq!(response.content(), "I can't help with that.");
+}
+
+/// Tests that an ordinary reply is not reported as filtered
+///
+/// Arrange: Server replies with finish_reason "stop"
+/// Act: Call chat_detailed()
+/// Assert: was_filtered() is false
+#[tokio::test]
+async fn test_stop_reply_is_not_filtered() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+    mount_reply(&mock_server, "Short.").await;
+    let mut session = ChippSession::new();
+
+    // Act
+    let response = client
+        .chat_detailed(&mut session, &create_test_messages())
+        .await
+        .unwrap();
+
+    // Assert
+    assert!(!response.was_filtered());
+}
+
+/// Tests that reject_filtered_completions turns a filtered reply into an error
+///
+/// Arrange: Client with reject_filtered_completions, filtered reply
+/// Act: Call chat()
+/// Assert: ContentFiltered with the canned text is returned, without retrying
+#[tokio::test]
+async fn test_reject_filtered_completions_returns_content_filtered() {
+    // Arrange
+    let (client, mock_server) =
+        setup_test_client_with(|config| config.reject_filtered_completions = true).await;
+    mount_filtered_reply(&mock_server).await;
+    let mut session = ChippSession::new();
+
+    // Act
+    let result = client.chat(&mut session, &create_test_messages()).await;
+
+    // Assert
+    match result {
+        Err(ChippClientError::ContentFiltered { message }) => {
+            assert_eq!(message, "I can't help with that.");
+        }
+        other => panic!("Expected ContentFiltered, got {:?}", other),
+    }
+}
+
+// =============================================================================
+// Clone With Config Tests
+// =============================================================================
+
+/// Tests that clone_with() applies its own timeout without changing the original
+///
+/// Arrange: Server replies after 300ms; original client has a 5s timeout
+/// Act: Call chat() on a clone with a 50ms timeout that never retries, then on the original
+/// Assert: The clone's request times out while the original succeeds
+#[tokio::test]
+async fn test_clone_with_overrides_timeout() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(create_success_response("Slow", "session-slow"))
+                .set_delay(Duration::from_millis(300)),
+        )
+        .mount(&mock_server)
+        .await;
+    // Not retrying hands back the attempt's own error rather than MaxRetriesExceeded
+    let quick = client
+        .clone_with(|config| {
+            config.timeout = Duration::from_millis(50);
+            config.should_retry = Some(Arc::new(|_: &ChippClientError| false));
+        })
+        .unwrap();
+
+    // Act
+    let quick_result = quick
+        .chat(&mut ChippSession::new(), &create_test_messages())
+        .await;
+    let original_result = client
+        .chat(&mut ChippSession::new(), &create_test_messages())
+        .await;
+
+    // Assert
+    assert!(
+        matches!(&quick_result, Err(e) if e.is_timeout()),
+        "Expected timeout, got {:?}",
+        quick_result
+    );
+    assert_eq!(original_result.unwrap(), "Slow");
+}
+
+/// Tests that clone_with() rejects a configuration new() would reject
+///
+/// Arrange: Valid test client
+/// Act: Call clone_with() setting a zero timeout
+/// Assert: Returns ConfigError
+#[tokio::test]
+async fn test_clone_with_validates_config() {
+    // Arrange
+    let (client, _mock_server) = setup_test_client().await;
+
+    // Act
+    let result = client.clone_with(|config| config.timeout = Duration::ZERO);
+
+    // Assert
+    assert!(matches!(result, Err(ChippClientError::ConfigError(_))));
+}
+
+/// Tests that clone_with() shares the original client's connection pool
+///
+/// Arrange: Keep-alive raw server counting connections; client and a clone_with() copy
+/// Act: Call chat() on the original, then on the clone
+/// Assert: Both requests were served over a single connection
+#[tokio::test]
+async fn test_clone_with_shares_connection_pool() {
+    // Arrange
+    let body = create_success_response("Pooled", "session-pool").to_string();
+    let (base_url, connections) = start_raw_server(move |_| {
+        vec![Step::Write(format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\n\
+             content-length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        ))]
+    })
+    .await;
+    let client = client_for(&base_url);
+    let clone = client
+        .clone_with(|config| config.timeout = Duration::from_secs(7))
+        .unwrap();
+
+    // Act
+    let first = client
+        .chat(&mut ChippSession::new(), &create_test_messages())
+        .await;
+    let second = clone
+        .chat(&mut ChippSession::new(), &create_test_messages())
+        .await;
+
+    // Assert
+    assert_eq!(first.unwrap(), "Pooled");
+    assert_eq!(second.unwrap(), "Pooled");
+    assert_eq!(connections.load(std::sync::atomic::Ordering::SeqCst), 1);
+}
+
+// =============================================================================
+// Batch Tests
+// =============================================================================
+
+/// Tests that chat_batch() results line up with the prompts despite failures and timing
+///
+/// Arrange: First prompt answers slowly, second always returns 500, third answers at once
+/// Act: Call chat_batch() with all three in flight together
+/// Assert: results[0] and results[2] are Ok with their own replies; results[1] is Err
+#[tokio::test]
+async fn test_chat_batch_results_keep_input_order() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(
+            json!({"messages": [{"content": "first"}]}),
+        ))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(create_success_response("reply-first", "session-1"))
+                .set_delay(Duration::from_millis(300)),
+        )
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(
+            json!({"messages": [{"content": "second"}]}),
+        ))
+        .respond_with(ResponseTemplate::new(500).set_body_string("Internal Server Error"))
+        .expect(4)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(
+            json!({"messages": [{"content": "third"}]}),
+        ))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(create_success_response("reply-third", "session-3")),
+        )
+        .mount(&mock_server)
+        .await;
+    let prompts: Vec<Vec<ChippMessage>> = ["first", "second", "third"]
+        .into_iter()
+        .map(|content| vec![ChippMessage::user(content)])
+        .collect();
+
+    // Act
+    let results = client.chat_batch(&prompts, 3).await;
+
+    // Assert
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap(), "reply-first");
+    assert!(matches!(
+        results[1],
+        Err(ChippClientError::MaxRetriesExceeded { attempts: 4, .. })
+    ));
+    assert_eq!(results[2].as_ref().unwrap(), "reply-third");
+}
+
+// =============================================================================
+// Raw Request Tests
+// =============================================================================
+
+/// Tests that raw_post() applies auth and returns the response untouched
+///
+/// Arrange: Mock endpoint that requires the bearer token and answers 418 with a JSON body
+/// Act: Call raw_post() on an unmodeled path
+/// Assert: The error status and body are handed back as is rather than as an Err
+#[tokio::test]
+async fn test_raw_post_applies_auth_and_returns_raw_response() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+    Mock::given(method("POST"))
+        .and(path("/custom/endpoint"))
+        .and(header("Authorization", "Bearer test-api-key"))
+        .and(header("Content-Type", "application/json"))
+        .and(body_partial_json(json!({"input": "ping"})))
+        .respond_with(ResponseTemplate::new(418).set_body_json(json!({"pong": true})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let response = client
+        .raw_post("/custom/endpoint", json!({"input": "ping"}))
+        .await
+        .expect("raw_post should return the response");
+
+    // Assert
+    assert_eq!(response.status().as_u16(), 418);
+    assert!(response.headers().contains_key("content-type"));
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body, json!({"pong": true}));
+
+    let requests = mock_server.received_requests().await.unwrap();
+    assert!(requests[0].headers.contains_key("x-correlation-id"));
+}
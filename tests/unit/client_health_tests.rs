@@ -1,4 +1,4 @@
-//! Tests for ChippClient ping method.
+//! Tests for ChippClient ping and warm_up methods.
 
 use chipp::{ChippClient, ChippClientError, ChippConfig};
 use std::time::Duration;
@@ -60,3 +60,54 @@ async fn test_ping_returns_err_for_network_failure() {
         ChippClientError::HttpError(_)
     ));
 }
+
+// ============================================================================
+// warm_up() Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_warm_up_succeeds_against_reachable_server() {
+    // ARRANGE
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("HEAD", "/chat/completions")
+        .with_status(200)
+        .create_async()
+        .await;
+
+    let config = ChippConfig::builder()
+        .api_key("test-key")
+        .model("test-model")
+        .base_url(server.url())
+        .build()
+        .unwrap();
+
+    let client = ChippClient::new(config).unwrap();
+
+    // ACT
+    let result = client.warm_up().await;
+
+    // ASSERT
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_warm_up_returns_err_for_network_failure() {
+    // ARRANGE - use invalid URL to simulate network failure
+    let config = ChippConfig::builder()
+        .api_key("test-key")
+        .model("test-model")
+        .base_url("http://invalid-domain-that-does-not-exist-12345.com")
+        .timeout(Duration::from_millis(100))
+        .build()
+        .unwrap();
+
+    let client = ChippClient::new(config).unwrap();
+
+    // ACT
+    let result = client.warm_up().await;
+
+    // ASSERT
+    assert!(matches!(result, Err(ChippClientError::HttpError(_))));
+}
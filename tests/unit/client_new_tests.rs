@@ -3,7 +3,7 @@
 //! These tests verify that the ChippClient can be properly instantiated
 //! with various configurations.
 
-use chipp::{ChippClient, ChippConfig};
+use chipp::{ChippClient, ChippClientError, ChippConfig};
 use std::time::Duration;
 
 /// Tests that ChippClient::new() successfully creates a client with valid configuration
@@ -15,8 +15,8 @@ use std::time::Duration;
 fn test_new_with_valid_config_creates_client() {
     // Arrange
     let config = ChippConfig {
-        api_key: "test-api-key".to_string(),
-        model: "test-model".to_string(),
+        api_key: "test-api-key".into(),
+        model: "test-model".into(),
         ..Default::default()
     };
 
@@ -39,8 +39,8 @@ fn test_new_with_valid_config_creates_client() {
 fn test_new_with_default_config() {
     // Arrange
     let config = ChippConfig {
-        api_key: "test-api-key".to_string(),
-        model: "gpt-4".to_string(),
+        api_key: "test-api-key".into(),
+        model: "gpt-4".into(),
         ..Default::default()
     };
 
@@ -63,8 +63,8 @@ fn test_new_with_default_config() {
 fn test_new_with_custom_timeout() {
     // Arrange
     let config = ChippConfig {
-        api_key: "test-api-key".to_string(),
-        model: "test-model".to_string(),
+        api_key: "test-api-key".into(),
+        model: "test-model".into(),
         timeout: Duration::from_millis(1),
         ..Default::default()
     };
@@ -79,6 +79,96 @@ fn test_new_with_custom_timeout() {
     );
 }
 
+/// Tests that ChippClient::new() rejects a zero timeout as a configuration error
+///
+/// Arrange: Create ChippConfig with a zero timeout
+/// Act: Call ChippClient::new()
+/// Assert: Returns ConfigError rather than HttpError
+#[test]
+fn test_new_with_zero_timeout_returns_config_error() {
+    // Arrange
+    let config = ChippConfig {
+        api_key: "test-api-key".into(),
+        model: "test-model".into(),
+        timeout: Duration::ZERO,
+        ..Default::default()
+    };
+
+    // Act
+    let result = ChippClient::new(config);
+
+    // Assert
+    match result {
+        Err(ChippClientError::ConfigError(msg)) => assert!(msg.contains("timeout")),
+        Err(other) => panic!("Expected ConfigError, got: {:?}", other),
+        Ok(_) => panic!("Expected ConfigError, got a client"),
+    }
+}
+
+/// Tests that ChippClient::new() rejects an initial retry delay above the maximum
+///
+/// Arrange: Create ChippConfig with initial_retry_delay greater than max_retry_delay
+/// Act: Call ChippClient::new()
+/// Assert: Returns ConfigError naming initial_retry_delay
+#[test]
+fn test_new_with_initial_retry_delay_above_max_returns_config_error() {
+    // Arrange
+    let config = ChippConfig {
+        api_key: "test-api-key".into(),
+        model: "test-model".into(),
+        initial_retry_delay: Duration::from_secs(5),
+        max_retry_delay: Duration::from_secs(1),
+        ..Default::default()
+    };
+
+    // Act
+    let result = ChippClient::new(config);
+
+    // Assert
+    match result {
+        Err(ChippClientError::ConfigError(msg)) => assert!(msg.contains("initial_retry_delay")),
+        Err(other) => panic!("Expected ConfigError, got: {:?}", other),
+        Ok(_) => panic!("Expected ConfigError, got a client"),
+    }
+}
+
+/// Tests that ChippClient::new() checks settings a struct literal bypasses the builder for
+///
+/// Arrange: ChippConfig literals with an invalid locale and idempotency key header
+/// Act: Call ChippClient::new() on each
+/// Assert: Both return ConfigError naming the setting
+#[test]
+fn test_new_validates_locale_and_idempotency_key_header() {
+    // Arrange
+    let bad_locale = ChippConfig {
+        api_key: "test-api-key".into(),
+        model: "test-model".into(),
+        locale: Some("en US".to_string()),
+        ..Default::default()
+    };
+    let bad_header = ChippConfig {
+        api_key: "test-api-key".into(),
+        model: "test-model".into(),
+        idempotency_key_header: Some("Bad Header".to_string()),
+        ..Default::default()
+    };
+
+    for (config, setting) in [
+        (bad_locale, "locale"),
+        (bad_header, "idempotency_key_header"),
+    ] {
+        // Act
+        let result = ChippClient::new(config);
+
+        // Assert
+        match result {
+            Err(ChippClientError::ConfigError(msg)) => assert!(msg.contains(setting)),
+            Err(other) => panic!("Expected ConfigError, got: {:?}", other),
+            Ok(_) => panic!("Expected ConfigError for {}, got a client", setting),
+        }
+    }
+}
+
 /// Tests that ChippClient::new() accepts custom retry configuration
 ///
 /// Arrange: Create ChippConfig with zero retries
@@ -88,8 +178,8 @@ fn test_new_with_custom_timeout() {
 fn test_new_with_zero_retries() {
     // Arrange
     let config = ChippConfig {
-        api_key: "test-api-key".to_string(),
-        model: "test-model".to_string(),
+        api_key: "test-api-key".into(),
+        model: "test-model".into(),
         max_retries: 0,
         ..Default::default()
     };
@@ -104,6 +194,32 @@ fn test_new_with_zero_retries() {
     );
 }
 
+/// Tests that ChippClient::new() accepts HTTP/2 keep-alive configuration
+///
+/// Arrange: Create ChippConfig with keep-alive interval and idle pings enabled
+/// Act: Call ChippClient::new()
+/// Assert: Client is created successfully
+#[test]
+fn test_new_with_http2_keep_alive() {
+    // Arrange
+    let config = ChippConfig {
+        api_key: "test-api-key".into(),
+        model: "test-model".into(),
+        http2_keep_alive_interval: Some(Duration::from_secs(15)),
+        http2_keep_alive_while_idle: true,
+        ..Default::default()
+    };
+
+    // Act
+    let result = ChippClient::new(config);
+
+    // Assert
+    assert!(
+        result.is_ok(),
+        "ChippClient::new() should succeed with HTTP/2 keep-alive settings"
+    );
+}
+
 /// Tests that ChippSession::default() creates a new session with no ID
 ///
 /// Arrange: N/A
@@ -118,6 +234,17 @@ fn test_session_default_trait() {
     assert!(session.chat_session_id.is_none());
 }
 
+/// Compile-time check that client types can be shared across threads
+#[test]
+fn test_client_types_are_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<ChippClient>();
+    assert_send_sync::<ChippConfig>();
+    assert_send_sync::<chipp::ChippSession>();
+    assert_send_sync::<chipp::ChatResponse>();
+}
+
 /// Tests that ChippClient::new() returns Result for proper error handling
 ///
 /// Arrange: Create valid ChippConfig
@@ -127,8 +254,8 @@ fn test_session_default_trait() {
 fn test_new_returns_result_ok() {
     // Arrange
     let config = ChippConfig {
-        api_key: "test-api-key".to_string(),
-        model: "test-model".to_string(),
+        api_key: "test-api-key".into(),
+        model: "test-model".into(),
         ..Default::default()
     };
 
@@ -138,3 +265,148 @@ fn test_new_returns_result_ok() {
     // Assert - Should return Ok
     assert!(result.is_ok(), "ChippClient::new() should return Ok");
 }
+
+/// Tests that a client builds with TLS verification disabled, and warns about it
+///
+/// Arrange: Builder with danger_accept_invalid_certs(true)
+/// Act: Call ChippClient::new()
+/// Assert: Returns Ok and logs a warning
+#[cfg(feature = "dangerous-tls")]
+#[test]
+#[tracing_test::traced_test]
+fn test_new_with_danger_accept_invalid_certs() {
+    // Arrange
+    let config = ChippConfig::builder()
+        .api_key("test-api-key")
+        .model("test-model")
+        .base_url("https://localhost:8443/api/v1")
+        .danger_accept_invalid_certs(true)
+        .build()
+        .unwrap();
+    assert!(config.danger_accept_invalid_certs);
+
+    // Act
+    let result = ChippClient::new(config);
+
+    // Assert
+    assert!(result.is_ok(), "ChippClient::new() should return Ok");
+    assert!(logs_contain("TLS certificate verification is DISABLED"));
+}
+
+/// Self-signed root CA certificate used by the root_certificate tests
+const TEST_ROOT_CA_PEM: &str = "\
+-----BEGIN CERTIFICATE-----
+MIIBkDCCATegAwIBAgIUCBSPJRF+JhU9lpe6wbA0BlolEHMwCgYIKoZIzj0EAwIw
+HTEbMBkGA1UEAwwSQ2hpcHAgVGVzdCBSb290IENBMCAXDTI2MTAxODAwMzQyOFoY
+DzIxMjYwOTI0MDAzNDI4WjAdMRswGQYDVQQDDBJDaGlwcCBUZXN0IFJvb3QgQ0Ew
+WTATBgcqhkjOPQIBBggqhkjOPQMBBwNCAAQBCr1Lt3YIprBIHPb4yiFm47jwUf1h
+yOWLz3A/UKDinPF1NmiRs1tr9J0qPSqs5GwrQOV6KoqUNAOSVtBtUW2Vo1MwUTAd
+BgNVHQ4EFgQUCyQe2gHlVRl873x2EOrPhfYNhwUwHwYDVR0jBBgwFoAUCyQe2gHl
+VRl873x2EOrPhfYNhwUwDwYDVR0TAQH/BAUwAwEB/zAKBggqhkjOPQQDAgNHADBE
+AiBapbLFUtGNp3bV+tCoKcH3kiwV4YeElxj9/knPk91FJQIgRHT8Ymy9pHgDjVdq
+WpNaV8aXQldSp9eR4noMAw58iYE=
+-----END CERTIFICATE-----
+";
+
+/// Tests that ChippClient::new() accepts a valid PEM root certificate
+///
+/// Arrange: Builder with root_certificate set to a self-signed CA in PEM form
+/// Act: Call ChippClient::new()
+/// Assert: Client is created
+#[test]
+fn test_new_with_valid_root_certificate() {
+    // Arrange
+    let config = ChippConfig::builder()
+        .api_key("test-api-key")
+        .model("test-model")
+        .root_certificate(TEST_ROOT_CA_PEM)
+        .build()
+        .unwrap();
+
+    // Act
+    let result = ChippClient::new(config);
+
+    // Assert
+    assert!(result.is_ok(), "Expected Ok, got {:?}", result.err());
+}
+
+/// Tests that ChippClient::new() rejects a malformed root certificate
+///
+/// Arrange: Configs with a truncated PEM certificate and with garbage DER bytes
+/// Act: Call ChippClient::new() for each
+/// Assert: Returns ConfigError naming root_certificate
+#[test]
+fn test_new_with_malformed_root_certificate_returns_config_error() {
+    let truncated_pem = &TEST_ROOT_CA_PEM[..TEST_ROOT_CA_PEM.len() / 2];
+    for bytes in [truncated_pem.as_bytes(), b"not a certificate".as_slice()] {
+        // Arrange
+        let config = ChippConfig {
+            api_key: "test-api-key".into(),
+            model: "test-model".into(),
+            root_certificate: Some(bytes.to_vec()),
+            ..Default::default()
+        };
+
+        // Act
+        let result = ChippClient::new(config);
+
+        // Assert
+        match result {
+            Err(ChippClientError::ConfigError(message)) => {
+                assert!(message.contains("root_certificate"), "got: {}", message);
+            }
+            other => panic!("Expected ConfigError, got {:?}", other.err()),
+        }
+    }
+}
+
+/// Tests that ChippClient::try_from_env() reads the standard variables
+///
+/// Arrange: Set CHIPP_API_KEY and CHIPP_APP_NAME_ID, then empty and clear the app ID
+/// Act: Call ChippConfig::from_env() and ChippClient::try_from_env() each time
+/// Assert: Succeeds with the values when set; ConfigError naming the variable
+///         when it is empty or not set
+///
+/// The only test touching these variables, since the environment is shared
+/// by tests running in parallel.
+#[test]
+fn test_try_from_env_reads_standard_variables() {
+    // Arrange
+    std::env::set_var(ChippConfig::API_KEY_ENV, "env-api-key");
+    std::env::set_var(ChippConfig::APP_NAME_ID_ENV, "env-app-123");
+
+    // Act
+    let config = ChippConfig::from_env();
+    let client = ChippClient::try_from_env();
+
+    // Assert
+    let config = config.expect("from_env() should succeed with both variables set");
+    assert_eq!(config.api_key, "env-api-key");
+    assert_eq!(config.model, "env-app-123");
+    assert!(client.is_ok(), "try_from_env() should succeed");
+
+    for (app_name_id, reason) in [(Some(""), "is empty"), (None, "is not set")] {
+        // Arrange
+        match app_name_id {
+            Some(value) => std::env::set_var(ChippConfig::APP_NAME_ID_ENV, value),
+            None => std::env::remove_var(ChippConfig::APP_NAME_ID_ENV),
+        }
+
+        // Act
+        let result = ChippClient::try_from_env();
+
+        // Assert
+        match result {
+            Err(ChippClientError::ConfigError(msg)) => {
+                assert!(
+                    msg.contains(&format!("CHIPP_APP_NAME_ID {}", reason)),
+                    "{}",
+                    msg
+                );
+            }
+            Err(e) => panic!("Expected ConfigError, got {:?}", e),
+            Ok(_) => panic!("Expected ConfigError, got a client"),
+        }
+    }
+    std::env::remove_var(ChippConfig::API_KEY_ENV);
+}
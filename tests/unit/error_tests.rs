@@ -0,0 +1,189 @@
+//! Tests for ChippClientError classification and conversion helpers.
+
+use chipp::ChippClientError;
+use std::time::Duration;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+// ============================================================================
+// HttpError Classification Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_connect_error_is_classified_as_connect() {
+    // ARRANGE - nothing listens on port 1
+    let reqwest_error = reqwest::get("http://127.0.0.1:1").await.unwrap_err();
+
+    // ACT
+    let error = ChippClientError::from(reqwest_error);
+
+    // ASSERT
+    assert!(error.is_connect());
+    assert!(!error.is_timeout());
+    assert!(!error.is_decode());
+}
+
+#[tokio::test]
+async fn test_timeout_error_is_classified_as_timeout() {
+    // ARRANGE
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+        .mount(&mock_server)
+        .await;
+
+    let http = reqwest::Client::builder()
+        .timeout(Duration::from_millis(50))
+        .build()
+        .unwrap();
+    let reqwest_error = http.get(mock_server.uri()).send().await.unwrap_err();
+
+    // ACT
+    let error = ChippClientError::from(reqwest_error);
+
+    // ASSERT
+    assert!(error.is_timeout());
+    assert!(!error.is_decode());
+}
+
+#[tokio::test]
+async fn test_decode_error_is_classified_as_decode() {
+    // ARRANGE
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("not json"))
+        .mount(&mock_server)
+        .await;
+
+    let reqwest_error = reqwest::get(mock_server.uri())
+        .await
+        .unwrap()
+        .json::<serde_json::Value>()
+        .await
+        .unwrap_err();
+
+    // ACT
+    let error = ChippClientError::from(reqwest_error);
+
+    // ASSERT
+    assert!(error.is_decode());
+    assert!(!error.is_connect());
+    assert!(!error.is_timeout());
+}
+
+// ============================================================================
+// Non-HttpError Variant Tests
+// ============================================================================
+
+#[test]
+fn test_non_http_errors_are_never_classified() {
+    let errors = [
+        ChippClientError::ApiError {
+            status: 500,
+            message: "Internal Server Error".to_string(),
+        },
+        ChippClientError::InvalidResponse("bad".to_string()),
+        ChippClientError::StreamError("bad".to_string()),
+        ChippClientError::MaxRetriesExceeded {
+            attempts: 4,
+            errors: vec!["attempt 1: bad".to_string()],
+        },
+        ChippClientError::ConfigError("bad".to_string()),
+        ChippClientError::RequestTooLarge {
+            size: 2048,
+            limit: 1024,
+        },
+        ChippClientError::Timeout {
+            limit: std::time::Duration::from_secs(1),
+            partial: "partial".to_string(),
+        },
+        ChippClientError::DeadlineExceeded {
+            deadline: std::time::Instant::now(),
+            partial: String::new(),
+        },
+        ChippClientError::EmptyResponse,
+        ChippClientError::TruncatedResponse("EOF".to_string()),
+        ChippClientError::Cancelled,
+    ];
+
+    for error in &errors {
+        assert!(!error.is_connect(), "{:?}", error);
+        assert!(!error.is_timeout(), "{:?}", error);
+        assert!(!error.is_body(), "{:?}", error);
+        assert!(!error.is_decode(), "{:?}", error);
+    }
+}
+
+// ============================================================================
+// ApiError Display Tests
+// ============================================================================
+
+#[test]
+fn test_api_error_display_truncates_huge_body() {
+    // ARRANGE
+    let body = format!("<html>{}</html>", "é".repeat(5_000));
+    let error = ChippClientError::ApiError {
+        status: 400,
+        message: body.clone(),
+    };
+
+    // ACT
+    let displayed = error.to_string();
+
+    // ASSERT
+    assert!(displayed.chars().count() < 600, "{}", displayed);
+    assert!(displayed.starts_with("API returned error: 400 - <html>"));
+    assert!(displayed.ends_with(&format!("… ({} bytes total)", body.len())));
+    assert_eq!(error.full_message(), Some(body.as_str()));
+}
+
+#[test]
+fn test_api_error_display_keeps_short_body() {
+    let error = ChippClientError::ApiError {
+        status: 404,
+        message: "Not Found".to_string(),
+    };
+
+    assert_eq!(error.to_string(), "API returned error: 404 - Not Found");
+    assert_eq!(error.full_message(), Some("Not Found"));
+    assert_eq!(ChippClientError::Cancelled.full_message(), None);
+}
+
+// ============================================================================
+// SharedChippError Tests
+// ============================================================================
+
+#[test]
+fn test_to_shared_preserves_api_error_status_and_message() {
+    // ARRANGE
+    let error = ChippClientError::ApiError {
+        status: 429,
+        message: "Too Many Requests".to_string(),
+    };
+
+    // ACT
+    let shared = error.to_shared();
+    let copy = shared.clone();
+
+    // ASSERT
+    assert_eq!(shared.kind, "ApiError");
+    assert_eq!(shared.status, Some(429));
+    assert!(shared.message.contains("Too Many Requests"));
+    assert_eq!(shared.to_string(), error.to_string());
+    assert_eq!(copy, shared);
+}
+
+#[tokio::test]
+async fn test_to_shared_flattens_http_error() {
+    // ARRANGE - nothing listens on port 1
+    let reqwest_error = reqwest::get("http://127.0.0.1:1").await.unwrap_err();
+    let error = ChippClientError::from(reqwest_error);
+
+    // ACT
+    let shared = error.to_shared();
+
+    // ASSERT
+    assert_eq!(shared.kind, "HttpError");
+    assert_eq!(shared.status, None);
+    assert_eq!(shared.message, error.to_string());
+}
@@ -4,12 +4,21 @@
 //! - client_new_tests: ChippClient::new() constructor tests
 //! - chat_tests: ChippClient::chat() method tests
 //! - streaming_tests: ChippClient::chat_stream() method tests
+//! - error_tests: ChippClientError classification helpers
 //! - security_tests: Security-critical behavior tests (API key redaction, etc.)
+//!
+//! `raw_server` is a raw TCP server shared by tests that need control over
+//! the connection itself; `span_capture` records span fields for tests that
+//! inspect tracing spans; `test_client` builds clients against a mock server.
 
 mod chat_tests;
 mod client_health_tests;
 mod client_new_tests;
 mod config_tests;
+mod error_tests;
+mod raw_server;
 mod security_tests;
+mod span_capture;
 mod streaming_tests;
+mod test_client;
 mod types_tests;
@@ -0,0 +1,97 @@
+//! Raw TCP HTTP server for tests that need control over the connection itself
+//!
+//! wiremock always sends well-formed responses in one go; a raw server can
+//! stall, pace chunks, cut a body short or keep a connection alive for reuse.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// Response head for a streaming reply on a connection that closes afterwards
+pub(super) const SSE_HEAD: &str =
+    "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\nconnection: close\r\n\r\n";
+
+/// One step of a raw server's reply
+pub(super) enum Step {
+    /// Write the bytes and flush them
+    Write(String),
+    /// Wait before the next step
+    Sleep(Duration),
+    /// Close the connection
+    Close,
+}
+
+/// Helper to start a raw HTTP server scripted per request
+///
+/// Request `n` (counting from 0 across all connections) is answered with the
+/// steps `script(n)`. A connection stays open for further requests unless its
+/// reply ends with [`Step::Close`]. Returns the base URL and a count of
+/// accepted connections.
+pub(super) async fn start_raw_server<F>(script: F) -> (String, Arc<AtomicUsize>)
+where
+    F: Fn(usize) -> Vec<Step> + Send + Sync + 'static,
+{
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let connections = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&connections);
+    let requests = Arc::new(AtomicUsize::new(0));
+    let script = Arc::new(script);
+
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            counter.fetch_add(1, Ordering::SeqCst);
+            let requests = Arc::clone(&requests);
+            let script = Arc::clone(&script);
+            tokio::spawn(async move {
+                while read_request(&mut socket).await {
+                    for step in script(requests.fetch_add(1, Ordering::SeqCst)) {
+                        match step {
+                            Step::Write(bytes) => {
+                                if socket.write_all(bytes.as_bytes()).await.is_err() {
+                                    return;
+                                }
+                                let _ = socket.flush().await;
+                            }
+                            Step::Sleep(delay) => tokio::time::sleep(delay).await,
+                            Step::Close => {
+                                let _ = socket.shutdown().await;
+                                return;
+                            }
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    (format!("http://{}", addr), connections)
+}
+
+/// Helper to read a full HTTP request (head and body) before answering it
+///
+/// Returns `false` if the peer closed the connection first.
+async fn read_request(socket: &mut TcpStream) -> bool {
+    let mut request = Vec::new();
+    let mut buf = [0u8; 4096];
+    loop {
+        let n = socket.read(&mut buf).await.unwrap_or(0);
+        if n == 0 {
+            return false;
+        }
+        request.extend_from_slice(&buf[..n]);
+        let text = String::from_utf8_lossy(&request).to_lowercase();
+        if let Some(head_end) = text.find("\r\n\r\n") {
+            let content_length = text
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .and_then(|value| value.trim().parse::<usize>().ok())
+                .unwrap_or(0);
+            if request.len() >= head_end + 4 + content_length {
+                return true;
+            }
+        }
+    }
+}
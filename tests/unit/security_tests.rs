@@ -3,8 +3,15 @@
 //! These tests verify security-critical behaviors:
 //! - API key redaction in Debug output
 //! - No accidental credential exposure in logs/errors
+//! - `ApiKey` redaction independent of the config
+//! - No API key in `tracing` output across a full request
 
-use chipp::ChippConfig;
+use chipp::{ApiKey, AppId, ChippClient, ChippConfig, ChippMessage, ChippSession};
+use futures::StreamExt;
+use std::time::Duration;
+use tracing_test::traced_test;
+use wiremock::matchers::{body_partial_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
 
 /// Tests that ChippConfig's Debug implementation redacts the API key
 ///
@@ -19,8 +26,8 @@ fn test_config_debug_redacts_api_key() {
     // ARRANGE
     let secret_api_key = "live_super_secret_key_12345";
     let config = ChippConfig {
-        api_key: secret_api_key.to_string(),
-        model: "test-model".to_string(),
+        api_key: secret_api_key.into(),
+        model: "test-model".into(),
         ..Default::default()
     };
 
@@ -53,8 +60,8 @@ fn test_config_debug_redacts_api_key() {
 fn test_config_debug_shows_non_sensitive_fields() {
     // ARRANGE
     let config = ChippConfig {
-        api_key: "secret-key".to_string(),
-        model: "my-app-123".to_string(),
+        api_key: "secret-key".into(),
+        model: "my-app-123".into(),
         base_url: "https://custom.api.example.com".to_string(),
         ..Default::default()
     };
@@ -102,3 +109,130 @@ fn test_config_builder_debug_redacts_api_key() {
         debug_output
     );
 }
+
+/// Tests that ApiKey redacts itself in both Debug and Display output
+///
+/// SECURITY: the key type is the single place redaction happens, so any
+/// struct embedding it stays safe to log.
+///
+/// Arrange: Create an ApiKey with a known value
+/// Act: Format it with Debug and Display
+/// Assert: Neither output contains the key; expose() still returns it
+#[test]
+fn test_api_key_debug_and_display_are_redacted() {
+    // ARRANGE
+    let secret_api_key = "live_newtype_secret_key_24680";
+    let key = ApiKey::from(secret_api_key);
+
+    // ACT
+    let debug_output = format!("{:?}", key);
+    let display_output = format!("{}", key);
+
+    // ASSERT
+    assert_eq!(debug_output, "ApiKey([REDACTED])");
+    assert_eq!(display_output, "[REDACTED]");
+    assert_eq!(key.expose(), secret_api_key);
+}
+
+/// Tests that AppId is not redacted, unlike ApiKey
+///
+/// Arrange: Create an AppId from an owned String
+/// Act: Format it with Display
+/// Assert: The identifier is shown as is
+#[test]
+fn test_app_id_display_shows_identifier() {
+    // ARRANGE
+    let app = AppId::from("my-app-123".to_string());
+
+    // ACT
+    let display_output = app.to_string();
+
+    // ASSERT
+    assert_eq!(display_output, "my-app-123");
+    assert_eq!(app, "my-app-123");
+}
+
+/// Tests that the API key never appears in tracing output for a full request
+///
+/// SECURITY: the client logs on every request (retries, responses, message
+/// content when enabled). None of it may include the key or the
+/// `Authorization` header value, at any level.
+///
+/// Arrange: Client with a known key and content logging on; server fails once
+/// Act: Run chat() through a retry, then drain a chat_stream()
+/// Assert: Logs were captured, but contain neither the key nor the header value
+#[tokio::test]
+#[traced_test]
+async fn test_tracing_output_never_contains_api_key() {
+    // ARRANGE
+    let secret_api_key = "live_tracing_secret_key_97531";
+    let auth_header = format!("Bearer {}", secret_api_key);
+    let mock_server = MockServer::start().await;
+    let config = ChippConfig::builder()
+        .api_key(secret_api_key)
+        .model("test-model")
+        .base_url(mock_server.uri())
+        .initial_retry_delay(Duration::from_millis(10))
+        .log_message_content(true)
+        .build()
+        .unwrap();
+    let client = ChippClient::new(config).unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(header("Authorization", auth_header.as_str()))
+        .and(body_partial_json(serde_json::json!({ "stream": true })))
+        .respond_with(ResponseTemplate::new(200).set_body_string(concat!(
+            "data: {\"type\":\"text-delta\",\"id\":\"m\",\"delta\":\"Hello\"}\n\n",
+            "data: [DONE]\n",
+        )))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(header("Authorization", auth_header.as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "chatSessionId": "session-trace",
+            "id": "chatcmpl-trace",
+            "object": "chat.completion",
+            "created": 1234567890,
+            "model": "test-model",
+            "choices": [{
+                "index": 0,
+                "message": { "role": "assistant", "content": "Hello" },
+                "finish_reason": "stop"
+            }],
+            "usage": { "prompt_tokens": 1, "completion_tokens": 1, "total_tokens": 2 }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+    let messages = [ChippMessage::user("Hi")];
+
+    // ACT
+    let response = client.chat(&mut session, &messages).await;
+    let mut stream = client.chat_stream(&mut session, &messages).await.unwrap();
+    while stream.next().await.is_some() {}
+
+    // ASSERT
+    assert_eq!(response.unwrap(), "Hello");
+    assert!(
+        logs_contain("Retrying"),
+        "Expected retry logs to be captured"
+    );
+    assert!(
+        !logs_contain(secret_api_key),
+        "SECURITY VIOLATION: API key was emitted through tracing"
+    );
+    assert!(
+        !logs_contain(&auth_header),
+        "SECURITY VIOLATION: Authorization header was emitted through tracing"
+    );
+}
@@ -0,0 +1,91 @@
+//! Tracing subscriber that captures span fields for tests that inspect spans
+//!
+//! `tracing-test` only sees events, so a field recorded on a span after its
+//! last event never shows up in the captured logs.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// Fields of one span, values as their display text
+pub(super) type SpanFields = HashMap<String, String>;
+
+/// A captured span: its ID while still open, and its fields
+type CapturedSpan = (Option<Id>, SpanFields);
+
+/// Captured fields of every span with a given name, in creation order
+#[derive(Clone)]
+pub(super) struct CapturedSpans {
+    name: &'static str,
+    spans: Arc<Mutex<Vec<CapturedSpan>>>,
+}
+
+impl CapturedSpans {
+    /// Helper to capture spans named `name` while the returned guard is alive
+    pub(super) fn named(name: &'static str) -> (Self, tracing::subscriber::DefaultGuard) {
+        let captured = Self {
+            name,
+            spans: Arc::default(),
+        };
+        let subscriber = tracing_subscriber::registry().with(captured.clone());
+        (captured, tracing::subscriber::set_default(subscriber))
+    }
+
+    /// Fields of every captured span, with recorded values filled in
+    pub(super) fn fields(&self) -> Vec<SpanFields> {
+        let spans = self.spans.lock().unwrap();
+        spans.iter().map(|(_, fields)| fields.clone()).collect()
+    }
+}
+
+impl<S> Layer<S> for CapturedSpans
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, _ctx: Context<'_, S>) {
+        if attrs.metadata().name() != self.name {
+            return;
+        }
+        let mut fields = SpanFields::new();
+        attrs.record(&mut FieldVisitor(&mut fields));
+        self.spans.lock().unwrap().push((Some(id.clone()), fields));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+        let mut spans = self.spans.lock().unwrap();
+        if let Some((_, fields)) = spans.iter_mut().find(|(span, _)| span.as_ref() == Some(id)) {
+            values.record(&mut FieldVisitor(fields));
+        }
+    }
+
+    fn on_close(&self, id: Id, _ctx: Context<'_, S>) {
+        // Span IDs are reused once closed, so stop matching this one
+        let mut spans = self.spans.lock().unwrap();
+        if let Some((span, _)) = spans
+            .iter_mut()
+            .find(|(span, _)| span.as_ref() == Some(&id))
+        {
+            *span = None;
+        }
+    }
+}
+
+/// Stores each visited field in a [`SpanFields`] map
+struct FieldVisitor<'a>(&'a mut SpanFields);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
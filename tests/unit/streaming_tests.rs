@@ -4,29 +4,33 @@
 //! - Successful streaming with multiple chunks
 //! - Error handling for API failures
 //! - Chipp SSE streaming format parsing (data: JSON events)
-
-use chipp::{ChippClient, ChippClientError, ChippConfig, ChippMessage, ChippSession, MessageRole};
+//! - Bounded read-ahead with chat_stream_buffered()
+//! - Bounded total time with chat_stream_collect_timeout()
+//! - Message metadata annotations
+//! - Per-chunk timing with chat_stream_timed()
+//! - Streaming into a channel with chat_stream_to_sender()
+//! - Keeping partial text with chat_stream_collect_lossy()
+//! - Bounded error bodies and server error events
+//! - Incremental usage deltas from cumulative reports
+//! - Fused termination after the stream ends
+//! - Raw payload capture with chat_stream_raw()
+
+use super::raw_server::{start_raw_server, Step, SSE_HEAD};
+use super::test_client::{setup_test_client, setup_test_client_with};
+use chipp::test_util::{client_for, client_with};
+use chipp::{
+    replay_sse_payloads, BufferedChippStream, CancellationToken, ChippClient, ChippClientError,
+    ChippEventStream, ChippMessage, ChippSession, ChippStream, MessageRole, StreamEvent,
+    TimedChippStream, Usage,
+};
+use futures::stream::FusedStream;
 use futures::StreamExt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use wiremock::matchers::{header, method, path};
+use wiremock::matchers::{body_partial_json, header, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
-/// Helper to create test client with mock server
-async fn setup_test_client() -> (ChippClient, MockServer) {
-    let mock_server = MockServer::start().await;
-    let config = ChippConfig {
-        api_key: "test-api-key".to_string(),
-        base_url: mock_server.uri(),
-        model: "test-model".to_string(),
-        timeout: Duration::from_secs(5),
-        max_retries: 3,
-        initial_retry_delay: Duration::from_millis(10),
-        max_retry_delay: Duration::from_millis(100),
-    };
-    let client = ChippClient::new(config).expect("Failed to create test client");
-    (client, mock_server)
-}
-
 /// Helper to create test messages
 fn create_test_messages() -> Vec<ChippMessage> {
     vec![ChippMessage {
@@ -317,3 +321,2332 @@ data: [DONE]
     assert_eq!(chunks[0], "Valid chunk");
     assert_eq!(chunks[1], "Another valid");
 }
+
+/// Tests that chat_stream() requests streaming in the body
+///
+/// Arrange: Mock server only matches bodies with `"stream": true`
+/// Act: Call chat_stream()
+/// Assert: Request matched (stream flag was set)
+#[tokio::test]
+async fn test_chat_stream_sends_stream_true() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(body_partial_json(serde_json::json!({ "stream": true })))
+        .respond_with(ResponseTemplate::new(200).set_body_string("data: [DONE]\n"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let result = client
+        .chat_stream(&mut session, &create_test_messages())
+        .await;
+
+    // Assert
+    assert!(result.is_ok(), "Expected Ok, got: {:?}", result);
+}
+
+/// Tests that chat_stream() falls back to a single chunk for a JSON response
+///
+/// Arrange: Mock server ignores streaming and returns a plain JSON completion
+/// Act: Call chat_stream() and collect chunks
+/// Assert: Content arrives as one chunk and the session ID is updated
+#[tokio::test]
+async fn test_chat_stream_json_response_fallback() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "chatSessionId": "session-json",
+            "id": "chatcmpl-json",
+            "object": "chat.completion",
+            "created": 1234567890,
+            "model": "test-model",
+            "choices": [{
+                "index": 0,
+                "message": { "role": "assistant", "content": "Not streamed" },
+                "finish_reason": "stop"
+            }],
+            "usage": { "prompt_tokens": 1, "completion_tokens": 2, "total_tokens": 3 }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let mut stream = client
+        .chat_stream(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start");
+
+    let mut chunks = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        chunks.push(chunk.unwrap());
+    }
+
+    // Assert
+    assert_eq!(chunks, vec!["Not streamed".to_string()]);
+    assert_eq!(session.chat_session_id, Some("session-json".to_string()));
+    assert_eq!(stream.session_id().await, Some("session-json".to_string()));
+}
+
+// ============================================================================
+// Structured Event Stream Tests
+// ============================================================================
+
+/// SSE body with reasoning and text deltas interleaved
+const INTERLEAVED_REASONING_BODY: &str = r#"data: {"type":"reasoning-delta","id":"r1","delta":"Thinking about it. "}
+
+data: {"type":"text-delta","id":"msg","delta":"The answer "}
+
+data: {"type":"reasoning-delta","id":"r1","delta":"Double-checking."}
+
+data: {"type":"text-delta","id":"msg","delta":"is 42."}
+
+data: {"type":"message-metadata","messageMetadata":{"annotations":[{"persistedMessageId":"session-reasoning"}]}}
+
+data: [DONE]
+"#;
+
+/// Tests that chat_stream_events() separates reasoning deltas from text deltas
+///
+/// Arrange: Mock server returns interleaved reasoning-delta and text-delta events
+/// Act: Call chat_stream_events() and collect all events
+/// Assert: Events arrive in order with reasoning and text distinguishable
+#[tokio::test]
+async fn test_chat_stream_events_separates_reasoning_and_text() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(INTERLEAVED_REASONING_BODY))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let mut events = client
+        .chat_stream_events(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start");
+
+    let mut collected = Vec::new();
+    while let Some(event) = events.next().await {
+        collected.push(event.unwrap());
+    }
+
+    // Assert
+    assert_eq!(
+        collected,
+        vec![
+            StreamEvent::ReasoningDelta("Thinking about it. ".to_string()),
+            StreamEvent::TextDelta("The answer ".to_string()),
+            StreamEvent::ReasoningDelta("Double-checking.".to_string()),
+            StreamEvent::TextDelta("is 42.".to_string()),
+            StreamEvent::SessionId("session-reasoning".to_string()),
+            StreamEvent::Done,
+        ]
+    );
+    assert_eq!(
+        events.session_id().await,
+        Some("session-reasoning".to_string())
+    );
+}
+
+/// Tests that chat_stream() yields only text deltas when reasoning is present
+///
+/// Arrange: Mock server returns interleaved reasoning-delta and text-delta events
+/// Act: Call chat_stream() and collect chunks
+/// Assert: Only the text-delta content is returned
+#[tokio::test]
+async fn test_chat_stream_skips_reasoning_deltas() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(INTERLEAVED_REASONING_BODY))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let mut stream = client
+        .chat_stream(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start");
+
+    let mut chunks = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        chunks.push(chunk.unwrap());
+    }
+
+    // Assert
+    assert_eq!(chunks, vec!["The answer ", "is 42."]);
+}
+
+// ============================================================================
+// Content-Type Handling Tests
+// ============================================================================
+
+/// Minimal SSE body with a single text chunk
+const SINGLE_CHUNK_SSE_BODY: &str =
+    "data: {\"type\":\"text-delta\",\"id\":\"m\",\"delta\":\"Chunk\"}\n\ndata: [DONE]\n";
+
+/// Minimal non-streaming JSON completion body
+fn json_completion_body() -> String {
+    serde_json::json!({
+        "chatSessionId": "session-ct",
+        "id": "chatcmpl-ct",
+        "object": "chat.completion",
+        "created": 1234567890,
+        "model": "test-model",
+        "choices": [{
+            "index": 0,
+            "message": { "role": "assistant", "content": "Whole answer" },
+            "finish_reason": "stop"
+        }],
+        "usage": { "prompt_tokens": 1, "completion_tokens": 2, "total_tokens": 3 }
+    })
+    .to_string()
+}
+
+/// Helper to stream a body served with the given Content-Type and collect chunks
+async fn collect_with_content_type(body: String, content_type: &str) -> Vec<String> {
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, content_type))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+    let mut stream = client
+        .chat_stream(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start");
+
+    let mut chunks = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        chunks.push(chunk.unwrap());
+    }
+    chunks
+}
+
+#[tokio::test]
+async fn test_chat_stream_event_stream_content_type() {
+    let chunks =
+        collect_with_content_type(SINGLE_CHUNK_SSE_BODY.to_string(), "text/event-stream").await;
+    assert_eq!(chunks, vec!["Chunk"]);
+}
+
+#[tokio::test]
+async fn test_chat_stream_event_stream_with_charset() {
+    let chunks = collect_with_content_type(
+        SINGLE_CHUNK_SSE_BODY.to_string(),
+        "text/event-stream; charset=utf-8",
+    )
+    .await;
+    assert_eq!(chunks, vec!["Chunk"]);
+}
+
+#[tokio::test]
+async fn test_chat_stream_json_with_charset_and_mixed_case() {
+    let chunks =
+        collect_with_content_type(json_completion_body(), "Application/JSON; charset=UTF-8").await;
+    assert_eq!(chunks, vec!["Whole answer"]);
+}
+
+#[tokio::test]
+async fn test_chat_stream_unknown_content_type_attempts_sse() {
+    let chunks =
+        collect_with_content_type(SINGLE_CHUNK_SSE_BODY.to_string(), "application/x-ndjson").await;
+    assert_eq!(chunks, vec!["Chunk"]);
+}
+
+// ============================================================================
+// Session ID Capture Tests
+// ============================================================================
+
+/// Tests that a session ID sharing a chunk with the final delta is never lost
+///
+/// Arrange: Mock server sends the last text-delta and message-metadata in one chunk
+/// Act: Drain chat_stream() repeatedly
+/// Assert: Every run captures the session ID after the text
+#[tokio::test]
+async fn test_chat_stream_captures_session_id_in_same_chunk_as_delta() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    let stream_body = concat!(
+        r#"data: {"type":"text-delta","id":"msg","delta":"Last words"}"#,
+        "\n",
+        r#"data: {"type":"message-metadata","messageMetadata":{"annotations":[{"persistedMessageId":"session-same-chunk"}]}}"#,
+        "\n",
+    );
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(stream_body))
+        .mount(&mock_server)
+        .await;
+
+    for _ in 0..20 {
+        let mut session = ChippSession::new();
+
+        // Act
+        let mut stream = client
+            .chat_stream(&mut session, &create_test_messages())
+            .await
+            .expect("Stream should start");
+
+        let mut chunks = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            chunks.push(chunk.unwrap());
+        }
+
+        // Assert
+        assert_eq!(chunks, vec!["Last words"]);
+        assert_eq!(
+            stream.session_id().await,
+            Some("session-same-chunk".to_string())
+        );
+    }
+}
+
+// ============================================================================
+// Stream Observer Tests
+// ============================================================================
+
+/// Tests that on_stream_event sees every parsed event while chat_stream yields text
+///
+/// Arrange: Client with a recording observer, server streams reasoning, text and metadata
+/// Act: Drain chat_stream()
+/// Assert: Consumer gets only text; observer saw every event in order
+#[tokio::test]
+async fn test_on_stream_event_observes_all_events() {
+    // Arrange
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let recorder = Arc::clone(&seen);
+    let (client, mock_server) = setup_test_client_with(move |config| {
+        config.on_stream_event = Some(Arc::new(move |event: &StreamEvent| {
+            recorder.lock().unwrap().push(event.clone());
+        }));
+    })
+    .await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(INTERLEAVED_REASONING_BODY))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let mut stream = client
+        .chat_stream(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start");
+
+    let mut chunks = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        chunks.push(chunk.unwrap());
+    }
+
+    // Assert
+    assert_eq!(chunks, vec!["The answer ", "is 42."]);
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![
+            StreamEvent::ReasoningDelta("Thinking about it. ".to_string()),
+            StreamEvent::TextDelta("The answer ".to_string()),
+            StreamEvent::ReasoningDelta("Double-checking.".to_string()),
+            StreamEvent::TextDelta("is 42.".to_string()),
+            StreamEvent::SessionId("session-reasoning".to_string()),
+            StreamEvent::Done,
+        ]
+    );
+}
+
+// ============================================================================
+// Send / Spawn Tests
+// ============================================================================
+
+/// Compile-time check that stream types can be moved into spawned tasks
+#[test]
+fn test_stream_types_are_send_and_static() {
+    fn assert_send_static<T: Send + 'static>() {}
+
+    assert_send_static::<ChippStream>();
+    assert_send_static::<ChippEventStream>();
+    assert_send_static::<BufferedChippStream>();
+}
+
+/// Tests that a boxed stream can be drained from a spawned task
+///
+/// Arrange: Mock server returns SSE text deltas
+/// Act: Box the stream, move it into tokio::spawn, forward chunks over a channel
+/// Assert: All chunks arrive through the channel in order
+#[tokio::test]
+async fn test_chat_stream_into_boxed_forwards_from_spawned_task() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(INTERLEAVED_REASONING_BODY))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+    let mut stream = client
+        .chat_stream(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start")
+        .into_boxed();
+    let (tx, mut rx) = tokio::sync::mpsc::channel(8);
+
+    // Act
+    let forwarder = tokio::spawn(async move {
+        while let Some(chunk) = stream.next().await {
+            tx.send(chunk.unwrap()).await.unwrap();
+        }
+    });
+
+    let mut chunks = Vec::new();
+    while let Some(chunk) = rx.recv().await {
+        chunks.push(chunk);
+    }
+    forwarder.await.unwrap();
+
+    // Assert
+    assert_eq!(chunks, vec!["The answer ", "is 42."]);
+}
+
+// ============================================================================
+// Session ID Deduplication Tests
+// ============================================================================
+
+/// Tests that repeated identical session IDs are reported only once
+///
+/// Arrange: Server sends the same persistedMessageId in two metadata events
+/// Act: Drain chat_stream_events() with a counting observer
+/// Assert: Exactly one SessionId event is yielded and observed
+#[tokio::test]
+async fn test_duplicate_session_id_events_are_deduplicated() {
+    // Arrange
+    let observed = Arc::new(Mutex::new(0));
+    let counter = Arc::clone(&observed);
+    let (client, mock_server) = setup_test_client_with(move |config| {
+        config.on_stream_event = Some(Arc::new(move |event: &StreamEvent| {
+            if matches!(event, StreamEvent::SessionId(_)) {
+                *counter.lock().unwrap() += 1;
+            }
+        }));
+    })
+    .await;
+
+    let stream_body = r#"data: {"type":"text-delta","id":"msg","delta":"Hi"}
+
+data: {"type":"message-metadata","messageMetadata":{"annotations":[{"persistedMessageId":"session-dup"}]}}
+
+data: {"type":"message-metadata","messageMetadata":{"annotations":[{"persistedMessageId":"session-dup"}]}}
+
+data: [DONE]
+"#;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(stream_body))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let mut events = client
+        .chat_stream_events(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start");
+
+    let mut collected = Vec::new();
+    while let Some(event) = events.next().await {
+        collected.push(event.unwrap());
+    }
+
+    // Assert
+    assert_eq!(
+        collected,
+        vec![
+            StreamEvent::TextDelta("Hi".to_string()),
+            StreamEvent::SessionId("session-dup".to_string()),
+            StreamEvent::Done,
+        ]
+    );
+    assert_eq!(*observed.lock().unwrap(), 1);
+    assert_eq!(events.session_id().await, Some("session-dup".to_string()));
+}
+
+/// Tests that repeated session IDs read ahead while opening the stream are reported once
+///
+/// Arrange: retry_stream_start enabled, counting observer, server sends the
+///          same persistedMessageId twice before the first text delta
+/// Act: Drain chat_stream_events()
+/// Assert: Exactly one SessionId event is yielded and observed
+#[tokio::test]
+async fn test_duplicate_session_id_events_read_ahead_are_deduplicated() {
+    // Arrange
+    let observed = Arc::new(Mutex::new(0));
+    let counter = Arc::clone(&observed);
+    let (client, mock_server) = setup_test_client_with(move |config| {
+        config.retry_stream_start = true;
+        config.on_stream_event = Some(Arc::new(move |event: &StreamEvent| {
+            if matches!(event, StreamEvent::SessionId(_)) {
+                *counter.lock().unwrap() += 1;
+            }
+        }));
+    })
+    .await;
+
+    let stream_body = r#"data: {"type":"message-metadata","messageMetadata":{"annotations":[{"persistedMessageId":"session-dup"}]}}
+
+data: {"type":"message-metadata","messageMetadata":{"annotations":[{"persistedMessageId":"session-dup"}]}}
+
+data: {"type":"text-delta","id":"msg","delta":"Hi"}
+
+data: [DONE]
+"#;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(stream_body))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let mut events = client
+        .chat_stream_events(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start");
+
+    let mut collected = Vec::new();
+    while let Some(event) = events.next().await {
+        collected.push(event.unwrap());
+    }
+
+    // Assert
+    assert_eq!(
+        collected,
+        vec![
+            StreamEvent::SessionId("session-dup".to_string()),
+            StreamEvent::TextDelta("Hi".to_string()),
+            StreamEvent::Done,
+        ]
+    );
+    assert_eq!(*observed.lock().unwrap(), 1);
+}
+
+// ============================================================================
+// Usage Mode Tests
+// ============================================================================
+
+/// SSE body whose finish event reports token usage
+const FINISH_WITH_USAGE_BODY: &str = r#"data: {"type":"text-delta","id":"msg","delta":"Hello "}
+
+data: {"type":"text-delta","id":"msg","delta":"there"}
+
+data: {"type":"finish","usage":{"promptTokens":12,"completionTokens":3}}
+
+data: [DONE]
+"#;
+
+/// Tests that with_usage() yields text items followed by a final usage item
+///
+/// Arrange: Mock server streams text deltas and a finish event with usage
+/// Act: Drain chat_stream().with_usage()
+/// Assert: Text items carry no usage; the final item carries the token counts
+#[tokio::test]
+async fn test_chat_stream_with_usage_yields_final_usage_item() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(FINISH_WITH_USAGE_BODY))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let mut stream = client
+        .chat_stream(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start")
+        .with_usage();
+
+    let mut items = Vec::new();
+    while let Some(item) = stream.next().await {
+        items.push(item.unwrap());
+    }
+
+    // Assert
+    let expected_usage = Usage {
+        prompt_tokens: 12,
+        completion_tokens: 3,
+        total_tokens: 15,
+    };
+    assert_eq!(
+        items,
+        vec![
+            ("Hello ".to_string(), None),
+            ("there".to_string(), None),
+            (String::new(), Some(expected_usage)),
+        ]
+    );
+}
+
+/// Tests that a usage stream still reports the session ID once drained
+///
+/// Arrange: Mock server streams a text delta and a session ID
+/// Act: Drain chat_stream().with_usage(), then read session_id()
+/// Assert: The session ID is available to continue the conversation
+#[tokio::test]
+async fn test_chat_stream_with_usage_keeps_session_id() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(sse_body_with_session("Hi", "session-usage")),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let mut stream = client
+        .chat_stream(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start")
+        .with_usage();
+
+    while let Some(item) = stream.next().await {
+        item.unwrap();
+    }
+
+    // Assert
+    assert_eq!(stream.session_id().as_deref(), Some("session-usage"));
+    assert_eq!(stream.final_usage(), None);
+}
+
+/// Tests that the plain text stream is unchanged and exposes final_usage()
+///
+/// Arrange: Mock server streams text deltas and a finish event with usage
+/// Act: Drain chat_stream()
+/// Assert: Only text is yielded; final_usage() reports the token counts
+#[tokio::test]
+async fn test_chat_stream_final_usage() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(FINISH_WITH_USAGE_BODY))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let mut stream = client
+        .chat_stream(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start");
+
+    assert!(stream.final_usage().is_none());
+
+    let mut chunks = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        chunks.push(chunk.unwrap());
+    }
+
+    // Assert
+    assert_eq!(chunks, vec!["Hello ", "there"]);
+    assert_eq!(stream.final_usage().map(|u| u.total_tokens), Some(15));
+}
+
+// ============================================================================
+// Response Header Tests
+// ============================================================================
+
+/// Tests that chat_stream_with_headers() exposes response headers alongside chunks
+///
+/// Arrange: Mock server streams SSE with X-Request-ID and rate-limit headers
+/// Act: Call chat_stream_with_headers() and drain the stream
+/// Assert: Headers are readable and all chunks are received
+#[tokio::test]
+async fn test_chat_stream_with_headers_exposes_response_headers() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("X-Request-ID", "req-789")
+                .insert_header("X-RateLimit-Remaining", "41")
+                .set_body_raw(INTERLEAVED_REASONING_BODY, "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let mut handle = client
+        .chat_stream_with_headers(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start");
+
+    let mut chunks = Vec::new();
+    while let Some(chunk) = handle.next().await {
+        chunks.push(chunk.unwrap());
+    }
+
+    // Assert
+    assert_eq!(handle.headers().request_id(), Some("req-789"));
+    assert_eq!(handle.headers().rate_limit_remaining(), Some(41));
+    assert_eq!(handle.headers().content_type(), Some("text/event-stream"));
+    assert_eq!(chunks, vec!["The answer ", "is 42."]);
+    assert_eq!(
+        handle.session_id().await,
+        Some("session-reasoning".to_string())
+    );
+}
+
+// ============================================================================
+// Sentence Re-chunking Tests
+// ============================================================================
+
+/// Helper to stream the given text deltas through sentences() and collect the output
+async fn collect_sentences(deltas: &[&str]) -> Vec<String> {
+    let (client, mock_server) = setup_test_client().await;
+
+    let body: String = deltas
+        .iter()
+        .map(|delta| {
+            format!(
+                "data: {}\n\n",
+                serde_json::json!({"type": "text-delta", "id": "msg", "delta": delta})
+            )
+        })
+        .chain(std::iter::once("data: [DONE]\n".to_string()))
+        .collect();
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+    let mut sentences = client
+        .chat_stream(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start")
+        .sentences();
+
+    let mut collected = Vec::new();
+    while let Some(sentence) = sentences.next().await {
+        collected.push(sentence.unwrap());
+    }
+    collected
+}
+
+/// Tests that deltas split mid-sentence are re-chunked into whole sentences
+///
+/// Arrange: Deltas "Hello. How" and " are you?"
+/// Act: Collect sentences()
+/// Assert: Yields "Hello." then "How are you?"
+#[tokio::test]
+async fn test_sentences_rechunks_deltas() {
+    // Arrange
+    let deltas = ["Hello. How", " are you?"];
+
+    // Act
+    let sentences = collect_sentences(&deltas).await;
+
+    // Assert
+    assert_eq!(sentences, vec!["Hello.", "How are you?"]);
+}
+
+/// Tests that abbreviations don't end sentences
+///
+/// Arrange: Deltas containing "e.g.", "Dr." and a single-letter initial
+/// Act: Collect sentences()
+/// Assert: Sentences are split only at real boundaries
+#[tokio::test]
+async fn test_sentences_does_not_split_on_abbreviations() {
+    // Arrange
+    let deltas = [
+        "Use a fruit, e.g. an apple. Ask Dr. J. Smith",
+        " first! Then eat it",
+    ];
+
+    // Act
+    let sentences = collect_sentences(&deltas).await;
+
+    // Assert
+    assert_eq!(
+        sentences,
+        vec![
+            "Use a fruit, e.g. an apple.",
+            "Ask Dr. J. Smith first!",
+            "Then eat it",
+        ]
+    );
+}
+
+/// Tests that "No." ends a sentence unless a number follows it
+///
+/// Arrange: Deltas answering a question with "No." and citing "No. 5", split
+///          so the number arrives in a later delta
+/// Act: Collect sentences()
+/// Assert: The answer is its own sentence; "No. 5" stays in one
+#[tokio::test]
+async fn test_sentences_splits_after_no_unless_number_follows() {
+    // Arrange
+    let deltas = [
+        "Is it raining? No.",
+        " It's sunny. Try No.",
+        " 5 on the list.",
+    ];
+
+    // Act
+    let sentences = collect_sentences(&deltas).await;
+
+    // Assert
+    assert_eq!(
+        sentences,
+        vec![
+            "Is it raining?",
+            "No.",
+            "It's sunny.",
+            "Try No. 5 on the list.",
+        ]
+    );
+}
+
+/// Tests that the sentence stream still reports the session ID once drained
+///
+/// Arrange: Mock server streams a text delta and a session ID
+/// Act: Drain chat_stream().sentences(), then read session_id()
+/// Assert: The session ID is available to continue the conversation
+#[tokio::test]
+async fn test_sentences_keeps_session_id() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(sse_body_with_session("Hi.", "session-sentences")),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let mut sentences = client
+        .chat_stream(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start")
+        .sentences();
+
+    while let Some(sentence) = sentences.next().await {
+        sentence.unwrap();
+    }
+
+    // Assert
+    assert_eq!(sentences.session_id().as_deref(), Some("session-sentences"));
+    assert_eq!(sentences.final_usage(), None);
+}
+
+// ============================================================================
+// Tee Tests
+// ============================================================================
+
+/// Tests that chat_stream_tee() fills the shared buffer as chunks are yielded
+///
+/// Arrange: Mock server returns SSE text deltas
+/// Act: Call chat_stream_tee() and drain the stream
+/// Assert: Buffer grows with each chunk and ends as their concatenation
+#[tokio::test]
+async fn test_chat_stream_tee_collects_into_shared_buffer() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    let stream_body = r#"data: {"type":"text-delta","id":"msg123","delta":"Hello, "}
+
+data: {"type":"reasoning-delta","id":"msg123","delta":"thinking"}
+
+data: {"type":"text-delta","id":"msg123","delta":"world"}
+
+data: {"type":"text-delta","id":"msg123","delta":"!"}
+
+data: [DONE]
+"#;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(stream_body))
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let (mut stream, buffer) = client
+        .chat_stream_tee(&mut ChippSession::new(), &create_test_messages())
+        .await
+        .unwrap();
+    assert!(buffer.is_empty());
+
+    let mut chunks = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        chunks.push(chunk.unwrap());
+        // The buffer already holds everything yielded so far
+        assert_eq!(buffer.text(), chunks.concat());
+    }
+
+    // Assert
+    assert_eq!(chunks, vec!["Hello, ", "world", "!"]);
+    assert_eq!(buffer.text(), "Hello, world!");
+    assert_eq!(buffer.len(), "Hello, world!".len());
+}
+
+// ============================================================================
+// Concurrent Session Tests
+// ============================================================================
+
+/// Helper to build an SSE body with one text delta and a session ID
+fn sse_body_with_session(text: &str, session_id: &str) -> String {
+    format!(
+        "data: {{\"type\":\"text-delta\",\"id\":\"msg\",\"delta\":\"{}\"}}\n\n\
+         data: {{\"type\":\"message-metadata\",\"messageMetadata\":\
+         {{\"annotations\":[{{\"persistedMessageId\":\"{}\"}}]}}}}\n\n\
+         data: [DONE]\n",
+        text, session_id
+    )
+}
+
+/// Tests that streams on distinct sessions run in parallel without mixing state
+///
+/// Arrange: Mock server answers each prompt with its own text and session ID,
+///          the first one slowly so the streams overlap
+/// Act: Spawn two chat_stream_collect() calls on two sessions concurrently
+/// Assert: Both complete, each with its own text and session ID
+#[tokio::test]
+async fn test_concurrent_streams_on_distinct_sessions() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    for (prompt, text, session_id, delay) in [
+        ("first", "One", "session-a", Duration::from_millis(100)),
+        ("second", "Two", "session-b", Duration::ZERO),
+    ] {
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .and(body_partial_json(
+                serde_json::json!({"messages": [{"content": prompt}]}),
+            ))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(sse_body_with_session(text, session_id))
+                    .set_delay(delay),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+    }
+
+    let spawn_chat = |prompt: &'static str| {
+        let client = client.clone();
+        tokio::spawn(async move {
+            let mut session = ChippSession::new();
+            let text = client
+                .chat_stream_collect(&mut session, &[ChippMessage::user(prompt)])
+                .await
+                .unwrap();
+            (text, session)
+        })
+    };
+
+    // Act
+    let (first, second) = tokio::join!(spawn_chat("first"), spawn_chat("second"));
+
+    // Assert
+    let (first_text, first_session) = first.unwrap();
+    let (second_text, second_session) = second.unwrap();
+    assert_eq!(first_text, "One");
+    assert_eq!(first_session.id(), Some("session-a"));
+    assert_eq!(second_text, "Two");
+    assert_eq!(second_session.id(), Some("session-b"));
+}
+
+// ============================================================================
+// Retry Directive Tests
+// ============================================================================
+
+/// Tests that an SSE retry: line is exposed as a reconnection hint
+///
+/// Arrange: Mock server streams a retry: 3000 line, an unknown directive, and text
+/// Act: Drain chat_stream()
+/// Assert: Text is unaffected and retry_hint() reports 3 seconds
+#[tokio::test]
+async fn test_chat_stream_parses_retry_directive() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    let stream_body = r#"retry: 3000
+
+event: message
+data: {"type":"text-delta","id":"msg123","delta":"Hello"}
+
+retry: soon
+
+data: [DONE]
+"#;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(stream_body))
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let mut stream = client
+        .chat_stream(&mut ChippSession::new(), &create_test_messages())
+        .await
+        .unwrap();
+    assert!(stream.retry_hint().is_none());
+
+    let mut chunks = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        chunks.push(chunk.unwrap());
+    }
+
+    // Assert
+    assert_eq!(chunks, vec!["Hello"]);
+    // The malformed retry: line is ignored rather than clearing the hint
+    assert_eq!(stream.retry_hint(), Some(Duration::from_millis(3000)));
+}
+
+// ============================================================================
+// Text Mapping Tests
+// ============================================================================
+
+/// Tests that map_text() transforms chunks while still capturing the session ID
+///
+/// Arrange: Server streams text deltas followed by session metadata
+/// Act: Wrap every chunk in brackets with map_text() and drain the stream
+/// Assert: Chunks are transformed; the session ID is still captured
+#[tokio::test]
+async fn test_chat_stream_map_text_keeps_session_capture() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(create_numbered_stream_body(3)))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let mut stream = client
+        .chat_stream(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start")
+        .map_text(|text| format!("<{}>", text.trim()));
+
+    let mut chunks = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        chunks.push(chunk.unwrap());
+    }
+
+    // Assert
+    assert_eq!(chunks, vec!["<0>", "<1>", "<2>"]);
+    assert_eq!(
+        stream.session_id().await,
+        Some("session-buffered".to_string())
+    );
+}
+
+/// Tests that repeated map_text() calls apply in order
+///
+/// Arrange: Server streams "hello world"
+/// Act: Uppercase, then mask "WORLD", then drain
+/// Assert: The masked uppercase text is yielded
+#[tokio::test]
+async fn test_chat_stream_map_text_chains() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    let stream_body = r#"data: {"type":"text-delta","id":"msg","delta":"hello world"}
+
+data: [DONE]
+"#;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(stream_body))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let stream = client
+        .chat_stream(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start")
+        .map_text(|text| text.to_uppercase())
+        .map_text(|text| text.replace("WORLD", "*****"));
+
+    let chunks: Vec<String> = stream.map(|chunk| chunk.unwrap()).collect().await;
+
+    // Assert
+    assert_eq!(chunks, vec!["HELLO *****"]);
+}
+
+// ============================================================================
+// Stream Start Retry Tests
+// ============================================================================
+
+/// Helper to start a raw HTTP server whose first connection is reset mid-body
+///
+/// The first request gets SSE headers promising more body than is sent before
+/// the connection closes; later requests get `body` in full. Returns the base
+/// URL and a count of connections served.
+async fn start_resetting_server(body: &'static str) -> (String, Arc<AtomicUsize>) {
+    start_raw_server(move |request| {
+        let response = if request == 0 {
+            "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\n\
+             content-length: 1000\r\n\r\n"
+                .to_string()
+        } else {
+            format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\n\
+                 content-length: {}\r\nconnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+        };
+        vec![Step::Write(response), Step::Close]
+    })
+    .await
+}
+
+/// Helper to create a client for the resetting server
+fn create_retry_start_client(base_url: String, retry_stream_start: bool) -> ChippClient {
+    client_with(&base_url, |config| {
+        config.max_retries = 2;
+        config.retry_stream_start = retry_stream_start;
+    })
+}
+
+const RESET_RETRY_BODY: &str = concat!(
+    "data: {\"type\":\"text-delta\",\"id\":\"m\",\"delta\":\"Recovered\"}\n\n",
+    "data: {\"type\":\"message-metadata\",\"messageMetadata\":",
+    "{\"annotations\":[{\"persistedMessageId\":\"session-retried\"}]}}\n\n",
+    "data: [DONE]\n",
+);
+
+/// Tests that a connection reset before the first chunk is retried when enabled
+///
+/// Arrange: Server resets the first connection after the headers, then serves SSE
+/// Act: Open chat_stream() with retry_stream_start enabled and drain it
+/// Assert: The stream yields the retried response; two connections were made
+#[tokio::test]
+async fn test_chat_stream_retries_reset_before_first_chunk() {
+    // Arrange
+    let (base_url, connections) = start_resetting_server(RESET_RETRY_BODY).await;
+    let client = create_retry_start_client(base_url, true);
+    let mut session = ChippSession::new();
+
+    // Act
+    let mut stream = client
+        .chat_stream(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should open after retrying");
+
+    let mut chunks = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        chunks.push(chunk.unwrap());
+    }
+
+    // Assert
+    assert_eq!(chunks, vec!["Recovered"]);
+    assert_eq!(
+        stream.session_id().await,
+        Some("session-retried".to_string())
+    );
+    assert_eq!(connections.load(Ordering::SeqCst), 2);
+}
+
+/// Tests that without retry_stream_start the reset surfaces on the stream
+///
+/// Arrange: Server resets the first connection after the headers
+/// Act: Open chat_stream() with the default config and poll it
+/// Assert: Opening succeeds, the first item is an HTTP error, one connection was made
+#[tokio::test]
+async fn test_chat_stream_reset_surfaces_without_start_retry() {
+    // Arrange
+    let (base_url, connections) = start_resetting_server(RESET_RETRY_BODY).await;
+    let client = create_retry_start_client(base_url, false);
+    let mut session = ChippSession::new();
+
+    // Act
+    let mut stream = client
+        .chat_stream(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should open");
+    let first = stream.next().await;
+
+    // Assert
+    assert!(matches!(first, Some(Err(ChippClientError::HttpError(_)))));
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+}
+
+/// Tests that stream start retries follow the rate limit budget and Retry-After
+///
+/// Arrange: Mock server answers 429 with Retry-After: 1 once, then streams
+/// Act: Open chat_stream() with max_retries 0 and a rate limit budget of 1
+/// Assert: The stream opens after waiting about a second; two requests were made
+#[tokio::test]
+async fn test_chat_stream_start_retry_honours_rate_limit_policy() {
+    // Arrange
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("retry-after", "1")
+                .set_body_string("Slow down"),
+        )
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(RESET_RETRY_BODY))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    let client = client_with(&mock_server.uri(), |config| {
+        config.max_retries = 0;
+        config.max_retry_delay = Duration::from_secs(5);
+        config.rate_limit_max_retries = Some(1);
+        config.retry_stream_start = true;
+    });
+    let mut session = ChippSession::new();
+    let start = std::time::Instant::now();
+
+    // Act
+    let mut stream = client
+        .chat_stream(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should open after the rate limit retry");
+    let first = stream.next().await;
+
+    // Assert
+    assert!(start.elapsed() >= Duration::from_millis(900));
+    assert_eq!(first.unwrap().unwrap(), "Recovered");
+}
+
+// ============================================================================
+// Buffered Stream Tests
+// ============================================================================
+
+/// Helper to build an SSE body with `count` numbered text deltas and a session ID
+fn create_numbered_stream_body(count: usize) -> String {
+    let mut body = String::new();
+    for i in 0..count {
+        body.push_str(&format!(
+            "data: {{\"type\":\"text-delta\",\"id\":\"msg\",\"delta\":\"{} \"}}\n\n",
+            i
+        ));
+    }
+    body.push_str(
+        "data: {\"type\":\"message-metadata\",\"messageMetadata\":\
+         {\"annotations\":[{\"persistedMessageId\":\"session-buffered\"}]}}\n\n",
+    );
+    body.push_str("data: [DONE]\n");
+    body
+}
+
+/// Tests that a buffered stream stops reading ahead of a slow consumer
+///
+/// Arrange: Observer counting parsed text deltas, server streams 50 chunks
+/// Act: Take one chunk, stall, then drain the rest
+/// Assert: While stalled, the reader stays within the buffer capacity;
+///         every chunk still arrives in order with the session ID
+#[tokio::test]
+async fn test_chat_stream_buffered_bounds_read_ahead() {
+    // Arrange
+    let parsed = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let counter = Arc::clone(&parsed);
+    let (client, mock_server) = setup_test_client_with(move |config| {
+        config.on_stream_event = Some(Arc::new(move |event: &StreamEvent| {
+            if matches!(event, StreamEvent::TextDelta(_)) {
+                counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            }
+        }));
+    })
+    .await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(create_numbered_stream_body(50)))
+        .mount(&mock_server)
+        .await;
+
+    let capacity = 2;
+    let mut session = ChippSession::new();
+
+    // Act
+    let mut stream = client
+        .chat_stream_buffered(&mut session, &create_test_messages(), capacity)
+        .await
+        .expect("Stream should start");
+
+    let first = stream.next().await.unwrap().unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let parsed_while_stalled = parsed.load(std::sync::atomic::Ordering::SeqCst);
+
+    let mut chunks = vec![first];
+    while let Some(chunk) = stream.next().await {
+        chunks.push(chunk.unwrap());
+    }
+
+    // Assert
+    // One consumed, `capacity` queued, and one held by the blocked send
+    assert!(
+        parsed_while_stalled <= capacity + 2,
+        "reader ran ahead to {} chunks with capacity {}",
+        parsed_while_stalled,
+        capacity
+    );
+    let expected: Vec<String> = (0..50).map(|i| format!("{} ", i)).collect();
+    assert_eq!(chunks, expected);
+    assert_eq!(
+        stream.session_id().await,
+        Some("session-buffered".to_string())
+    );
+}
+
+/// Tests that a zero-capacity buffer is rejected before sending
+///
+/// Arrange: Mock server expecting no requests
+/// Act: Call chat_stream_buffered() with capacity 0
+/// Assert: ConfigError is returned
+#[tokio::test]
+async fn test_chat_stream_buffered_rejects_zero_capacity() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let result = client
+        .chat_stream_buffered(&mut session, &create_test_messages(), 0)
+        .await;
+
+    // Assert
+    assert!(matches!(result, Err(ChippClientError::ConfigError(_))));
+}
+
+// =============================================================================
+// Bounded Collect Tests
+// =============================================================================
+
+/// Helper to start a raw server that sends `body` and then stalls without closing
+async fn start_stalling_server(body: &'static str) -> String {
+    let (base_url, _) = start_raw_server(move |_| {
+        vec![
+            Step::Write(format!("{}{}", SSE_HEAD, body)),
+            Step::Sleep(Duration::from_secs(30)),
+            Step::Close,
+        ]
+    })
+    .await;
+    base_url
+}
+
+/// Tests that chat_stream_collect_timeout() gives up with the partial text
+///
+/// Arrange: Server sends one chunk and a session ID, then stalls
+/// Act: Call chat_stream_collect_timeout() with a 300ms total
+/// Assert: Timeout carries the partial text; the session ID is written back
+#[tokio::test]
+async fn test_chat_stream_collect_timeout_returns_partial_text() {
+    // Arrange
+    let base_url = start_stalling_server(concat!(
+        "data: {\"type\":\"text-delta\",\"id\":\"m\",\"delta\":\"Partial \"}\n\n",
+        "data: {\"type\":\"message-metadata\",\"messageMetadata\":",
+        "{\"annotations\":[{\"persistedMessageId\":\"session-slow\"}]}}\n\n",
+    ))
+    .await;
+    let client = client_for(&base_url);
+    let mut session = ChippSession::new();
+
+    // Act
+    let result = client
+        .chat_stream_collect_timeout(
+            &mut session,
+            &create_test_messages(),
+            Duration::from_millis(300),
+        )
+        .await;
+
+    // Assert
+    match result {
+        Err(ChippClientError::Timeout { limit, partial }) => {
+            assert_eq!(limit, Duration::from_millis(300));
+            assert_eq!(partial, "Partial ");
+        }
+        other => panic!("Expected Timeout, got {:?}", other),
+    }
+    assert_eq!(session.chat_session_id, Some("session-slow".to_string()));
+}
+
+/// Tests that chat_stream_collect_timeout() returns the full text within the deadline
+///
+/// Arrange: Mock server returning a complete stream
+/// Act: Call chat_stream_collect_timeout() with a generous total
+/// Assert: The full text is returned
+#[tokio::test]
+async fn test_chat_stream_collect_timeout_completes_within_deadline() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(RESET_RETRY_BODY))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let result = client
+        .chat_stream_collect_timeout(
+            &mut session,
+            &create_test_messages(),
+            Duration::from_secs(5),
+        )
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), "Recovered");
+    assert_eq!(session.chat_session_id, Some("session-retried".to_string()));
+}
+
+/// Tests that shutting the client down ends an open stream with Cancelled
+///
+/// Arrange: Server sends one chunk, then stalls
+/// Act: Read the first chunk, call shutdown(), poll again
+/// Assert: The next item is Cancelled and the stream then ends
+#[tokio::test]
+async fn test_shutdown_ends_open_stream() {
+    // Arrange
+    let base_url = start_stalling_server(
+        "data: {\"type\":\"text-delta\",\"id\":\"m\",\"delta\":\"First \"}\n\n",
+    )
+    .await;
+    let client = client_for(&base_url);
+    let mut session = ChippSession::new();
+    let mut stream = client
+        .chat_stream(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start");
+    assert_eq!(stream.next().await.unwrap().unwrap(), "First ");
+
+    // Act
+    let owner = client.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        owner.shutdown();
+    });
+    let next = tokio::time::timeout(Duration::from_secs(5), stream.next())
+        .await
+        .expect("Shutdown should wake the stalled stream");
+
+    // Assert
+    assert!(
+        matches!(next, Some(Err(ChippClientError::Cancelled))),
+        "Expected Cancelled, got: {:?}",
+        next
+    );
+    assert!(stream.next().await.is_none());
+}
+
+/// Tests that the caller's token cancels a stream while opening and while reading
+///
+/// Arrange: Server sends one chunk, then stalls
+/// Act: Open with an already-cancelled token; then open, read a chunk and cancel
+/// Assert: The first open fails with Cancelled; the open stream yields Cancelled and ends
+#[tokio::test]
+async fn test_chat_stream_cancellable_honours_token() {
+    // Arrange
+    let base_url = start_stalling_server(
+        "data: {\"type\":\"text-delta\",\"id\":\"m\",\"delta\":\"First \"}\n\n",
+    )
+    .await;
+    let client = client_for(&base_url);
+    let mut session = ChippSession::new();
+    let cancelled = CancellationToken::new();
+    cancelled.cancel();
+
+    // Act
+    let rejected = client
+        .chat_stream_cancellable(&mut session, &create_test_messages(), &cancelled)
+        .await;
+    let cancel = CancellationToken::new();
+    let mut stream = client
+        .chat_stream_cancellable(&mut session, &create_test_messages(), &cancel)
+        .await
+        .expect("Stream should start");
+    assert_eq!(stream.next().await.unwrap().unwrap(), "First ");
+    let canceller = cancel.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        canceller.cancel();
+    });
+    let next = tokio::time::timeout(Duration::from_secs(5), stream.next())
+        .await
+        .expect("Cancelling should wake the stalled stream");
+
+    // Assert
+    assert!(matches!(rejected, Err(ChippClientError::Cancelled)));
+    assert!(
+        matches!(next, Some(Err(ChippClientError::Cancelled))),
+        "Expected Cancelled, got: {:?}",
+        next
+    );
+    assert!(stream.next().await.is_none());
+    assert!(!client.is_shut_down());
+}
+
+/// Tests that chat_stream_collect_until() honours an expired or generous deadline
+///
+/// Arrange: Mock server streaming 3 chunks
+/// Act: Collect with a deadline in the past, then with one 5 seconds away
+/// Assert: The first returns DeadlineExceeded without a request; the second the full text
+#[tokio::test]
+async fn test_chat_stream_collect_until_deadlines() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(create_numbered_stream_body(3)))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+    let expired = std::time::Instant::now() - Duration::from_millis(1);
+    let generous = std::time::Instant::now() + Duration::from_secs(5);
+
+    // Act
+    let late = client
+        .chat_stream_collect_until(&mut session, &create_test_messages(), expired)
+        .await;
+    let on_time = client
+        .chat_stream_collect_until(&mut session, &create_test_messages(), generous)
+        .await;
+
+    // Assert
+    assert!(matches!(
+        late,
+        Err(ChippClientError::DeadlineExceeded { deadline, .. }) if deadline == expired
+    ));
+    assert_eq!(on_time.unwrap(), "0 1 2 ");
+}
+
+// =============================================================================
+// Metadata Annotation Tests
+// =============================================================================
+
+/// Tests that metadata annotations are exposed whole, with their extra fields
+///
+/// Arrange: Stream with a session annotation, a citation annotation, and a repeat
+/// Act: Drain chat_stream() and read metadata_annotations()
+/// Assert: Each distinct annotation appears once with all its fields; session ID still works
+#[tokio::test]
+async fn test_chat_stream_exposes_metadata_annotations() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    let stream_body = concat!(
+        "data: {\"type\":\"text-delta\",\"id\":\"m\",\"delta\":\"Cited answer\"}\n\n",
+        "data: {\"type\":\"message-metadata\",\"messageMetadata\":{\"annotations\":[",
+        "{\"persistedMessageId\":\"session-annotated\",\"feedbackId\":\"fb-1\"},",
+        "{\"type\":\"citation\",\"source\":\"https://example.com/doc\",\"score\":0.87}",
+        "]}}\n\n",
+        "data: {\"type\":\"message-metadata\",\"messageMetadata\":{\"annotations\":[",
+        "{\"persistedMessageId\":\"session-annotated\",\"feedbackId\":\"fb-1\"}",
+        "]}}\n\n",
+        "data: [DONE]\n",
+    );
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(stream_body))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let mut stream = client
+        .chat_stream(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start");
+    assert!(stream.metadata_annotations().is_empty());
+
+    let mut chunks = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        chunks.push(chunk.unwrap());
+    }
+
+    // Assert
+    assert_eq!(chunks, vec!["Cited answer"]);
+    let annotations = stream.metadata_annotations();
+    assert_eq!(annotations.len(), 2);
+    assert_eq!(annotations[0]["feedbackId"], "fb-1");
+    assert_eq!(annotations[1]["type"], "citation");
+    assert_eq!(annotations[1]["source"], "https://example.com/doc");
+    assert_eq!(annotations[1]["score"], 0.87);
+    assert_eq!(
+        stream.session_id().await,
+        Some("session-annotated".to_string())
+    );
+}
+
+// =============================================================================
+// Chunk Timing Tests
+// =============================================================================
+
+/// Helper to start a raw server that sends each chunk after waiting `gap`
+async fn start_trickling_server(chunks: &'static [&'static str], gap: Duration) -> String {
+    let (base_url, _) = start_raw_server(move |_| {
+        let mut steps = vec![Step::Write(SSE_HEAD.to_string())];
+        for chunk in chunks {
+            steps.push(Step::Sleep(gap));
+            steps.push(Step::Write(chunk.to_string()));
+        }
+        steps.push(Step::Close);
+        steps
+    })
+    .await;
+    base_url
+}
+
+/// Tests that chat_stream_timed() reports the gap before each chunk
+///
+/// Arrange: Raw server waiting 150ms before the first chunk and between chunks
+/// Act: Drain chat_stream_timed()
+/// Assert: Every chunk arrives with its text and a gap of at least the injected delay
+#[tokio::test]
+async fn test_chat_stream_timed_reports_chunk_gaps() {
+    // Arrange
+    const GAP: Duration = Duration::from_millis(150);
+    const JITTER: Duration = Duration::from_millis(10);
+    let base_url = start_trickling_server(
+        &[
+            "data: {\"type\":\"text-delta\",\"id\":\"m\",\"delta\":\"One \"}\n\n",
+            "data: {\"type\":\"text-delta\",\"id\":\"m\",\"delta\":\"two \"}\n\n",
+            "data: {\"type\":\"text-delta\",\"id\":\"m\",\"delta\":\"three\"}\n\n",
+            "data: [DONE]\n",
+        ],
+        GAP,
+    )
+    .await;
+    let client = client_for(&base_url);
+    let mut session = ChippSession::new();
+
+    // Act
+    let mut stream: TimedChippStream = client
+        .chat_stream_timed(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start");
+    let mut chunks = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        chunks.push(chunk.unwrap());
+    }
+
+    // Assert
+    let texts: Vec<&str> = chunks.iter().map(|(text, _)| text.as_str()).collect();
+    assert_eq!(texts, vec!["One ", "two ", "three"]);
+    for (text, gap) in &chunks {
+        // Arrival is when the client reads the bytes, which can jitter by a few ms
+        assert!(*gap >= GAP - JITTER, "{:?} arrived after {:?}", text, gap);
+    }
+}
+
+/// Tests that the first chunk read while opening isn't timed as a near-zero gap
+///
+/// Arrange: Raw server waiting 150ms before the first chunk and between chunks
+/// Act: Open chat_stream_timed(), wait 400ms, then drain it
+/// Assert: The first gap ends when the chunk arrived, and the next one isn't short
+#[tokio::test]
+async fn test_chat_stream_timed_first_chunk_measured_at_arrival() {
+    // Arrange
+    const GAP: Duration = Duration::from_millis(150);
+    const JITTER: Duration = Duration::from_millis(10);
+    let base_url = start_trickling_server(
+        &[
+            "data: {\"type\":\"text-delta\",\"id\":\"m\",\"delta\":\"One \"}\n\n",
+            "data: {\"type\":\"text-delta\",\"id\":\"m\",\"delta\":\"two\"}\n\n",
+            "data: [DONE]\n",
+        ],
+        GAP,
+    )
+    .await;
+    let client = client_for(&base_url);
+
+    // Act
+    let mut stream = client
+        .chat_stream_timed(&mut ChippSession::new(), &create_test_messages())
+        .await
+        .expect("Stream should start");
+    tokio::time::sleep(Duration::from_millis(400)).await;
+    let mut gaps = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        gaps.push(chunk.unwrap().1);
+    }
+
+    // Assert
+    assert_eq!(gaps.len(), 2);
+    assert!(gaps[0] >= GAP - JITTER && gaps[0] < Duration::from_millis(350));
+    // Read only once polled, so this one includes the wait, but is never short
+    assert!(gaps[1] >= GAP - JITTER, "gap {:?} too short", gaps[1]);
+}
+
+// =============================================================================
+// Channel Sink Tests
+// =============================================================================
+
+/// Tests that chat_stream_to_sender() delivers every chunk in order
+///
+/// Arrange: Server streams 20 chunks and a finish event with usage; bounded channel of 2
+/// Act: Stream into the channel while a separate task drains it
+/// Assert: The consumer sees all chunks in order; usage and session ID are returned
+#[tokio::test]
+async fn test_chat_stream_to_sender_delivers_chunks_in_order() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+    let body = create_numbered_stream_body(20).replace(
+        "data: [DONE]\n",
+        "data: {\"type\":\"finish\",\"usage\":{\"promptTokens\":12,\"completionTokens\":20}}\n\n\
+         data: [DONE]\n",
+    );
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(&mock_server)
+        .await;
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel(2);
+    let consumer = tokio::spawn(async move {
+        let mut received = Vec::new();
+        while let Some(chunk) = rx.recv().await {
+            received.push(chunk);
+        }
+        received
+    });
+    let mut session = ChippSession::new();
+
+    // Act
+    let usage = client
+        .chat_stream_to_sender(&mut session, &create_test_messages(), tx)
+        .await
+        .expect("Stream should complete");
+    let received = consumer.await.unwrap();
+
+    // Assert
+    let expected: Vec<String> = (0..20).map(|i| format!("{} ", i)).collect();
+    assert_eq!(received, expected);
+    assert_eq!(usage.prompt_tokens, 12);
+    assert_eq!(usage.completion_tokens, 20);
+    assert_eq!(session.id(), Some("session-buffered"));
+}
+
+/// Tests that chat_stream_to_sender() stops cleanly when the receiver is gone
+///
+/// Arrange: Server streams 20 chunks; the channel's receiver is already dropped
+/// Act: Stream into the channel
+/// Assert: Returns Ok with default usage instead of an error
+#[tokio::test]
+async fn test_chat_stream_to_sender_stops_when_receiver_dropped() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(create_numbered_stream_body(20)))
+        .mount(&mock_server)
+        .await;
+
+    let (tx, rx) = tokio::sync::mpsc::channel(2);
+    drop(rx);
+    let mut session = ChippSession::new();
+
+    // Act
+    let result = client
+        .chat_stream_to_sender(&mut session, &create_test_messages(), tx)
+        .await;
+
+    // Assert
+    assert_eq!(result.unwrap(), Usage::default());
+}
+
+// =============================================================================
+// Lossy Collect Tests
+// =============================================================================
+
+/// Helper to start a raw HTTP server that closes the connection mid-body
+///
+/// Sends SSE headers promising more body than `body`, then `body`, then closes.
+async fn start_truncating_server(body: &'static str) -> String {
+    let (base_url, _) = start_raw_server(move |_| {
+        let response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\n\
+             content-length: {}\r\n\r\n{}",
+            body.len() + 1000,
+            body
+        );
+        vec![Step::Write(response), Step::Close]
+    })
+    .await;
+    base_url
+}
+
+/// Tests that chat_stream_collect_lossy() keeps the text received before a failure
+///
+/// Arrange: Server sends two of three chunks, then drops the connection
+/// Act: Call chat_stream_collect_lossy()
+/// Assert: The two-chunk prefix is returned alongside the error
+#[tokio::test]
+async fn test_chat_stream_collect_lossy_keeps_prefix_on_failure() {
+    // Arrange
+    let base_url = start_truncating_server(concat!(
+        "data: {\"type\":\"text-delta\",\"id\":\"m\",\"delta\":\"Almost \"}\n\n",
+        "data: {\"type\":\"text-delta\",\"id\":\"m\",\"delta\":\"there\"}\n\n",
+        "data: {\"type\":\"message-metadata\",\"messageMetadata\":",
+        "{\"annotations\":[{\"persistedMessageId\":\"session-partial\"}]}}\n\n",
+    ))
+    .await;
+    let client = client_for(&base_url);
+    let mut session = ChippSession::new();
+
+    // Act
+    let response = client
+        .chat_stream_collect_lossy(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should open");
+
+    // Assert
+    assert_eq!(response.text, "Almost there");
+    assert!(!response.is_complete());
+    assert!(matches!(
+        response.error,
+        Some(ChippClientError::HttpError(_))
+    ));
+    assert_eq!(session.id(), Some("session-partial"));
+}
+
+/// Tests that chat_stream_collect_lossy() reports a complete stream
+///
+/// Arrange: Mock server streams 3 chunks and finishes normally
+/// Act: Call chat_stream_collect_lossy()
+/// Assert: All text is returned with no error
+#[tokio::test]
+async fn test_chat_stream_collect_lossy_complete_stream() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(create_numbered_stream_body(3)))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let response = client
+        .chat_stream_collect_lossy(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should open");
+
+    // Assert
+    assert!(response.is_complete());
+    assert_eq!(response.into_result().unwrap(), "0 1 2 ");
+}
+
+// =============================================================================
+// Early Failure Tests
+// =============================================================================
+
+/// Tests that only a bounded prefix of a huge streaming error body is kept
+///
+/// Arrange: Mock server answers 500 with a 100KB body
+/// Act: Call chat_stream()
+/// Assert: ApiError holds at most 8KB, taken from the start of the body
+#[tokio::test]
+async fn test_chat_stream_error_body_is_bounded() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+    let body = format!(
+        "Traceback (most recent call last):\n{}",
+        "x".repeat(100_000)
+    );
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(500).set_body_string(body))
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let result = client
+        .chat_stream(&mut ChippSession::new(), &create_test_messages())
+        .await;
+
+    // Assert
+    match result {
+        Err(ChippClientError::ApiError { status, message }) => {
+            assert_eq!(status, 500);
+            assert_eq!(message.len(), 8 * 1024);
+            assert!(message.starts_with("Traceback"));
+        }
+        Err(e) => panic!("Expected ApiError, got {:?}", e),
+        Ok(_) => panic!("Expected ApiError, got a stream"),
+    }
+}
+
+/// Tests that an error event at the start of the stream fails chat_stream() itself
+///
+/// Arrange: Mock server answers 200 with an SSE error event first
+/// Act: Call chat_stream()
+/// Assert: Returns StreamError carrying the server's error text
+#[tokio::test]
+async fn test_chat_stream_immediate_error_event_fails_open() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    let stream_body = r#"data: {"type":"error","errorText":"Model overloaded"}
+
+data: [DONE]
+"#;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(stream_body))
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let result = client
+        .chat_stream(&mut ChippSession::new(), &create_test_messages())
+        .await;
+
+    // Assert
+    match result {
+        Err(ChippClientError::StreamError(message)) => {
+            assert!(message.contains("Model overloaded"), "{}", message);
+        }
+        Err(e) => panic!("Expected StreamError, got {:?}", e),
+        Ok(_) => panic!("Expected StreamError, got a stream"),
+    }
+}
+
+/// Tests that an error event after some text ends the stream with an error
+///
+/// Arrange: Mock server streams one text delta, then an error event
+/// Act: Open chat_stream() and drain it
+/// Assert: The text arrives, then a StreamError, then the stream ends
+#[tokio::test]
+async fn test_chat_stream_late_error_event_ends_stream() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    let stream_body = r#"data: {"type":"text-delta","id":"msg","delta":"Hel"}
+
+data: {"type":"error","errorText":"Connection to model lost"}
+
+data: {"type":"text-delta","id":"msg","delta":"lo"}
+"#;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(stream_body))
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let mut stream = client
+        .chat_stream(&mut ChippSession::new(), &create_test_messages())
+        .await
+        .expect("Stream should open");
+    let first = stream.next().await;
+    let second = stream.next().await;
+    let third = stream.next().await;
+
+    // Assert
+    assert_eq!(first.unwrap().unwrap(), "Hel");
+    assert!(matches!(
+        second,
+        Some(Err(ChippClientError::StreamError(_)))
+    ));
+    assert!(third.is_none());
+}
+
+// ============================================================================
+// Usage Delta Tests
+// ============================================================================
+
+/// SSE body reporting cumulative usage twice while streaming, then a smaller final total
+const CUMULATIVE_USAGE_BODY: &str = r#"data: {"type":"text-delta","id":"msg","delta":"Hello "}
+
+data: {"type":"message-metadata","messageMetadata":{"annotations":[]},"usage":{"promptTokens":12,"completionTokens":1}}
+
+data: {"type":"text-delta","id":"msg","delta":"there"}
+
+data: {"type":"message-metadata","messageMetadata":{"annotations":[]},"usage":{"promptTokens":12,"completionTokens":4}}
+
+data: {"type":"finish","usage":{"promptTokens":12,"completionTokens":3}}
+
+data: [DONE]
+"#;
+
+/// Tests that usage_delta() reports the increment of each cumulative usage event
+///
+/// Arrange: Mock server streams two growing usage reports and a smaller final one
+/// Act: Drain chat_stream_events(), reading usage() and usage_delta() after each Usage event
+/// Assert: Deltas tick up by the growth, and the shrinking report gives a zero delta
+#[tokio::test]
+async fn test_chat_stream_events_usage_delta_per_report() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(CUMULATIVE_USAGE_BODY))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let mut events = client
+        .chat_stream_events(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start");
+
+    let mut reports = Vec::new();
+    while let Some(event) = events.next().await {
+        if let StreamEvent::Usage(usage) = event.unwrap() {
+            assert_eq!(events.usage(), Some(usage));
+            reports.push((usage.completion_tokens, events.usage_delta().unwrap()));
+        }
+    }
+
+    // Assert
+    let delta = |completion_tokens: u32, prompt_tokens: u32| Usage {
+        prompt_tokens,
+        completion_tokens,
+        total_tokens: prompt_tokens + completion_tokens,
+    };
+    assert_eq!(
+        reports,
+        vec![(1, delta(1, 12)), (4, delta(3, 0)), (3, Usage::default()),]
+    );
+}
+
+/// Tests that a metadata event carrying a session ID and usage yields both
+///
+/// Arrange: Mock server streams one message-metadata event with persistedMessageId and usage
+/// Act: Drain chat_stream_events()
+/// Assert: SessionId is followed by Usage, and the session and usage() are both updated
+#[tokio::test]
+async fn test_chat_stream_events_metadata_with_session_and_usage() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+    let body = r#"data: {"type":"text-delta","id":"msg","delta":"Hi"}
+
+data: {"type":"message-metadata","messageMetadata":{"annotations":[{"persistedMessageId":"session-both"}]},"usage":{"promptTokens":5,"completionTokens":2}}
+
+data: [DONE]
+"#;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let mut events = client
+        .chat_stream_events(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start");
+    let mut received = Vec::new();
+    while let Some(event) = events.next().await {
+        received.push(event.unwrap());
+    }
+
+    // Assert
+    let usage = Usage {
+        prompt_tokens: 5,
+        completion_tokens: 2,
+        total_tokens: 7,
+    };
+    assert_eq!(
+        received,
+        vec![
+            StreamEvent::TextDelta("Hi".to_string()),
+            StreamEvent::SessionId("session-both".to_string()),
+            StreamEvent::Usage(usage),
+            StreamEvent::Done,
+        ]
+    );
+    assert_eq!(events.usage(), Some(usage));
+    assert_eq!(events.session_id().await.as_deref(), Some("session-both"));
+}
+
+// ============================================================================
+// Fused Stream Tests
+// ============================================================================
+
+/// Tests that ChippStream stays terminated once it has returned None
+///
+/// Arrange: Mock server streams two text deltas
+/// Act: Drain the stream, then poll it several more times
+/// Assert: is_terminated() flips from false to true, and every extra poll returns None
+#[tokio::test]
+async fn test_chat_stream_is_fused_after_completion() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(FINISH_WITH_USAGE_BODY))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+    let mut stream = client
+        .chat_stream(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start");
+    assert!(!stream.is_terminated());
+
+    // Act
+    let mut chunks = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        chunks.push(chunk.unwrap());
+    }
+
+    // Assert
+    assert_eq!(chunks, vec!["Hello ", "there"]);
+    assert!(stream.is_terminated());
+    for _ in 0..3 {
+        assert!(stream.next().await.is_none());
+    }
+    assert!(stream.is_terminated());
+}
+
+/// Tests that TimedChippStream is fused like the stream it wraps
+///
+/// Arrange: Mock server streams two text deltas and a finish event
+/// Act: Drain chat_stream_timed(), then poll it again
+/// Assert: is_terminated() flips to true, extra polls return None, and
+///         session_id() and final_usage() read without awaiting
+#[tokio::test]
+async fn test_chat_stream_timed_is_fused_after_completion() {
+    // Arrange
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(FINISH_WITH_USAGE_BODY))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+    let mut stream = client
+        .chat_stream_timed(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start");
+    assert!(!stream.is_terminated());
+
+    // Act
+    let mut texts = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        texts.push(chunk.unwrap().0);
+    }
+
+    // Assert
+    assert_eq!(texts, vec!["Hello ", "there"]);
+    assert!(stream.is_terminated());
+    assert!(stream.next().await.is_none());
+    assert_eq!(stream.session_id(), None);
+    assert_eq!(
+        stream.final_usage().map(|usage| usage.total_tokens),
+        Some(15)
+    );
+}
+
+// ============================================================================
+// Raw Payload Tests
+// ============================================================================
+
+/// Tests that chat_stream_raw() yields every data payload verbatim, in order
+///
+/// Arrange: Mock server streams text, metadata, finish and [DONE] lines
+/// Act: Drain chat_stream_raw(), then replay the payloads through replay_sse_payloads()
+/// Assert: Payloads match the body's data lines exactly and replay to the same events
+#[tokio::test]
+async fn test_chat_stream_raw_yields_verbatim_payloads() {
+    // Arrange
+    let payloads = [
+        r#"{"type":"text-delta","id":"msg","delta":"Hello"}"#,
+        r#"{"type":"message-metadata","messageMetadata":{"annotations":[{"persistedMessageId":"session-raw"}]}}"#,
+        r#"{"type":"finish","usage":{"promptTokens":2,"completionTokens":1}}"#,
+        "[DONE]",
+    ];
+    let body: String = payloads
+        .iter()
+        .map(|payload| format!("data: {}\n\n", payload))
+        .collect();
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let mut raw = client
+        .chat_stream_raw(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start");
+    let mut captured = Vec::new();
+    while let Some(payload) = raw.next().await {
+        captured.push(payload.unwrap());
+    }
+    let replayed: Vec<StreamEvent> = replay_sse_payloads(&captured)
+        .collect::<Result<_, _>>()
+        .expect("Replay should succeed");
+
+    // Assert
+    assert_eq!(captured, payloads);
+    assert_eq!(
+        replayed,
+        vec![
+            StreamEvent::TextDelta("Hello".to_string()),
+            StreamEvent::SessionId("session-raw".to_string()),
+            StreamEvent::Usage(Usage {
+                prompt_tokens: 2,
+                completion_tokens: 1,
+                total_tokens: 3,
+            }),
+            StreamEvent::Done,
+        ]
+    );
+    assert_eq!(raw.session_id().await, Some("session-raw".to_string()));
+}
+
+/// Tests that a captured error event replays as the error the live stream returned
+///
+/// Arrange: Mock server streams a text delta, then an error event
+/// Act: Drain chat_stream_raw(), then replay the payloads through replay_sse_payloads()
+/// Assert: Both payloads are captured, and the replay yields the text then a StreamError
+#[tokio::test]
+async fn test_replay_sse_payloads_reports_error_events() {
+    // Arrange
+    let body = r#"data: {"type":"text-delta","id":"msg","delta":"Hel"}
+
+data: {"type":"error","errorText":"model overloaded"}
+
+"#;
+    let (client, mock_server) = setup_test_client().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(&mock_server)
+        .await;
+
+    let mut session = ChippSession::new();
+
+    // Act
+    let mut raw = client
+        .chat_stream_raw(&mut session, &create_test_messages())
+        .await
+        .expect("Stream should start");
+    let mut captured = Vec::new();
+    while let Some(Ok(payload)) = raw.next().await {
+        captured.push(payload);
+    }
+    let replayed: Vec<_> = replay_sse_payloads(&captured).collect();
+
+    // Assert
+    assert_eq!(captured.len(), 2);
+    assert_eq!(replayed.len(), 2);
+    assert_eq!(
+        replayed[0].as_ref().unwrap(),
+        &StreamEvent::TextDelta("Hel".to_string())
+    );
+    assert!(matches!(
+        &replayed[1],
+        Err(ChippClientError::StreamError(message)) if message.contains("model overloaded")
+    ));
+}
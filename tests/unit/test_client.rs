@@ -0,0 +1,22 @@
+//! Mock-server clients shared by the test modules
+//!
+//! Every client starts from `chipp::test_util::config_for`, so a test only
+//! spells out the settings it is about.
+
+use chipp::test_util::client_with;
+use chipp::{ChippClient, ChippConfig};
+use wiremock::MockServer;
+
+/// Helper to create test client with mock server
+pub(super) async fn setup_test_client() -> (ChippClient, MockServer) {
+    setup_test_client_with(|_| {}).await
+}
+
+/// Helper to create test client with mock server, adjusting the test config first
+pub(super) async fn setup_test_client_with(
+    configure: impl FnOnce(&mut ChippConfig),
+) -> (ChippClient, MockServer) {
+    let mock_server = MockServer::start().await;
+    let client = client_with(&mock_server.uri(), configure);
+    (client, mock_server)
+}
@@ -1,6 +1,7 @@
 //! Tests for ChippMessage, ChippSession, and MessageRole types.
 
-use chipp::{ChippMessage, ChippSession, MessageRole};
+use chipp::{truncate_messages, ChippMessage, ChippSession, MessageRole};
+use std::collections::HashSet;
 
 // ============================================================================
 // MessageRole Tests
@@ -117,6 +118,17 @@ fn test_message_clone() {
     assert_eq!(msg.role, cloned.role);
 }
 
+#[test]
+fn test_message_hash_dedupes_identical_messages() {
+    let mut set = HashSet::new();
+    set.insert(ChippMessage::user("Hello"));
+    set.insert(ChippMessage::user("Hello"));
+    set.insert(ChippMessage::assistant("Hello"));
+
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(&ChippMessage::user("Hello")));
+}
+
 #[test]
 fn test_message_debug() {
     let msg = ChippMessage::user("Test");
@@ -127,6 +139,75 @@ fn test_message_debug() {
     assert!(debug.contains("Test"));
 }
 
+// ============================================================================
+// ChippMessage Truncation Tests
+// ============================================================================
+
+#[test]
+fn test_message_truncated_does_not_split_multibyte_chars() {
+    // Byte 5 falls inside the second crab (each is 4 bytes), so a byte slice would panic
+    let msg = ChippMessage::user("🦀🦀🦀 rust");
+
+    let truncated = msg.truncated(2);
+
+    assert_eq!(truncated.content, "🦀🦀");
+    assert!(std::str::from_utf8(truncated.content.as_bytes()).is_ok());
+    assert_eq!(truncated.role, MessageRole::User);
+}
+
+#[test]
+fn test_message_truncated_within_budget_is_unchanged() {
+    let msg = ChippMessage::user("héllo");
+
+    assert_eq!(msg.clone().truncated(5), msg);
+    assert_eq!(msg.clone().truncated(100), msg);
+}
+
+#[test]
+fn test_message_truncated_to_zero_is_empty() {
+    assert_eq!(ChippMessage::user("🦀").truncated(0).content, "");
+}
+
+#[test]
+fn test_message_truncated_with_ellipsis_fits_budget() {
+    let truncated = ChippMessage::user("😀😀😀😀").truncated_with_ellipsis(3);
+
+    assert_eq!(truncated.content, "😀😀…");
+    assert_eq!(truncated.content.chars().count(), 3);
+}
+
+#[test]
+fn test_message_truncated_with_ellipsis_only_marks_cuts() {
+    assert_eq!(
+        ChippMessage::user("short")
+            .truncated_with_ellipsis(5)
+            .content,
+        "short"
+    );
+    assert_eq!(
+        ChippMessage::user("short")
+            .truncated_with_ellipsis(0)
+            .content,
+        ""
+    );
+}
+
+#[test]
+fn test_truncate_messages_caps_each_message() {
+    let mut messages = vec![
+        ChippMessage::system("日本語のシステム"),
+        ChippMessage::user("hi"),
+        ChippMessage::assistant("👍🏽 sounds good"),
+    ];
+
+    truncate_messages(&mut messages, 3);
+
+    assert_eq!(messages[0].content, "日本語");
+    assert_eq!(messages[1].content, "hi");
+    // The skin tone modifier is a separate code point, so it counts as a character
+    assert_eq!(messages[2].content, "👍🏽 ");
+}
+
 // ============================================================================
 // ChippSession Tests
 // ============================================================================
@@ -162,6 +243,27 @@ fn test_session_reset_clears_id() {
     assert!(session.chat_session_id.is_none());
 }
 
+#[test]
+fn test_session_id_accessor_reads_id() {
+    assert_eq!(ChippSession::new().id(), None);
+    assert_eq!(ChippSession::with_id("read-me").id(), Some("read-me"));
+}
+
+#[test]
+fn test_session_set_id_replaces_id() {
+    let mut session = ChippSession::new();
+
+    session.set_id("first-id");
+    assert_eq!(session.id(), Some("first-id"));
+
+    session.set_id(String::from("second-id"));
+    assert_eq!(session.id(), Some("second-id"));
+    assert_eq!(session.chat_session_id, Some("second-id".to_string()));
+
+    session.reset();
+    assert_eq!(session.id(), None);
+}
+
 #[test]
 fn test_session_default_is_empty() {
     let session = ChippSession::default();
@@ -184,3 +286,46 @@ fn test_session_debug() {
     assert!(debug.contains("ChippSession"));
     assert!(debug.contains("debug-id"));
 }
+
+#[test]
+fn test_session_fork_shares_server_id() {
+    let session = ChippSession::with_id("shared-id");
+    let fork = session.fork();
+
+    assert_eq!(fork.chat_session_id, session.chat_session_id);
+}
+
+#[test]
+fn test_session_fork_mutation_leaves_original_unchanged() {
+    let session = ChippSession::with_id("original-id");
+    let mut fork = session.fork();
+
+    fork.chat_session_id = Some("branch-id".to_string());
+    assert_eq!(session.chat_session_id, Some("original-id".to_string()));
+
+    fork.reset();
+    assert_eq!(session.chat_session_id, Some("original-id".to_string()));
+    assert!(fork.chat_session_id.is_none());
+}
+
+#[test]
+fn test_session_merge_adopts_other_id() {
+    let mut session = ChippSession::with_id("main-id");
+    let mut fork = session.fork();
+    fork.chat_session_id = Some("branch-id".to_string());
+
+    session.merge(fork);
+
+    assert_eq!(session.chat_session_id, Some("branch-id".to_string()));
+}
+
+#[test]
+fn test_session_merge_adopts_reset_branch() {
+    let mut session = ChippSession::with_id("main-id");
+    let mut fork = session.fork();
+    fork.reset();
+
+    session.merge(fork);
+
+    assert!(session.chat_session_id.is_none());
+}